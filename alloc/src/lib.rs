@@ -0,0 +1,163 @@
+//! A heap allocator for Tock processes.
+//!
+//! `BrkAllocator` places the heap between the initial process break (the end
+//! of `.bss`) and the grant region, and grows it on demand by moving the
+//! process break using the Memop system call. It is intended to be installed
+//! as the `#[global_allocator]`; `libtock2` does so when its `alloc` feature is
+//! enabled.
+
+#![no_std]
+#![warn(unsafe_op_in_unsafe_fn)]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// A bump allocator that obtains memory from the kernel by moving the process
+/// break.
+///
+/// `BrkAllocator` is optimized for code size rather than memory reuse: freed
+/// memory is only reclaimed if it is the most recent allocation. Growing or
+/// shrinking the most recent allocation (which is the common case when pushing
+/// to a `Vec` or `String`) is done in place. The allocator never lowers the
+/// process break.
+pub struct BrkAllocator<S> {
+    // The end of the memory that has been handed out. Null until the first
+    // allocation, at which point the allocator looks up the initial break.
+    next: Cell<*mut u8>,
+
+    // The current process break. Memory between next and app_break is owned by
+    // the allocator but has not been handed out.
+    app_break: Cell<*mut u8>,
+
+    // The start of the most recent allocation that has not been deallocated,
+    // or null if there is no such allocation.
+    last: Cell<*mut u8>,
+
+    _syscalls: PhantomData<S>,
+}
+
+// new() is in a separate impl block because const fns cannot have trait bounds
+// on stable Rust.
+impl<S> BrkAllocator<S> {
+    pub const fn new() -> BrkAllocator<S> {
+        BrkAllocator {
+            next: Cell::new(core::ptr::null_mut()),
+            app_break: Cell::new(core::ptr::null_mut()),
+            last: Cell::new(core::ptr::null_mut()),
+            _syscalls: PhantomData,
+        }
+    }
+}
+
+impl<S: Syscalls> BrkAllocator<S> {
+    // Makes sure the memory up to `end` is accessible to the process, moving
+    // the process break if necessary.
+    fn reserve(&self, end: *mut u8) -> Result<(), ErrorCode> {
+        let app_break = self.app_break.get();
+        if (end as usize) <= (app_break as usize) {
+            return Ok(());
+        }
+        let increment = (end as usize - app_break as usize)
+            .try_into()
+            .map_err(|_| ErrorCode::NoMem)?;
+        // Safety: increment is positive, so this does not revoke access to any
+        // memory.
+        unsafe { S::memop_sbrk(increment) }?;
+        self.app_break.set(end);
+        Ok(())
+    }
+
+    // Returns the end of the memory that has been handed out, looking up the
+    // initial process break on first use.
+    fn next(&self) -> Result<*mut u8, ErrorCode> {
+        if self.next.get().is_null() {
+            // Safety: an increment of 0 does not move the process break.
+            let initial_break = unsafe { S::memop_sbrk(0) }?;
+            self.next.set(initial_break);
+            self.app_break.set(initial_break);
+        }
+        Ok(self.next.get())
+    }
+
+    fn try_alloc(&self, layout: Layout) -> Result<*mut u8, ErrorCode> {
+        let next = self.next()?;
+        // layout.align() is a power of two, so this computes the distance
+        // from next to the next multiple of align.
+        let padding = (next as usize).wrapping_neg() & (layout.align() - 1);
+        let size = padding.checked_add(layout.size()).ok_or(ErrorCode::NoMem)?;
+        (next as usize).checked_add(size).ok_or(ErrorCode::NoMem)?;
+        let start = next.wrapping_add(padding);
+        let end = next.wrapping_add(size);
+        self.reserve(end)?;
+        self.next.set(end);
+        self.last.set(start);
+        Ok(start)
+    }
+
+    // Returns true if ptr is the most recent allocation (and therefore can be
+    // freed or resized in place).
+    fn is_last(&self, ptr: *mut u8, size: usize) -> bool {
+        ptr == self.last.get() && ptr.wrapping_add(size) == self.next.get()
+    }
+}
+
+impl<S: Syscalls> Default for BrkAllocator<S> {
+    fn default() -> BrkAllocator<S> {
+        BrkAllocator::new()
+    }
+}
+
+// Safety: Tock processes are single-threaded, and upcalls only run within yield
+// (which the allocator never calls), so BrkAllocator is never accessed
+// concurrently.
+unsafe impl<S: Syscalls> Sync for BrkAllocator<S> {}
+
+unsafe impl<S: Syscalls> GlobalAlloc for BrkAllocator<S> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.try_alloc(layout).unwrap_or(core::ptr::null_mut())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if self.is_last(ptr, layout.size()) {
+            self.next.set(ptr);
+            self.last.set(core::ptr::null_mut());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if self.is_last(ptr, layout.size()) {
+            let end = ptr.wrapping_add(new_size);
+            if (ptr as usize).checked_add(new_size).is_none() || self.reserve(end).is_err() {
+                return core::ptr::null_mut();
+            }
+            self.next.set(end);
+            return ptr;
+        }
+        // Safety: the caller upholds realloc's requirements, which imply the
+        // requirements of Layout::from_size_align_unchecked, alloc, and dealloc.
+        unsafe {
+            let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+            let new_ptr = self.alloc(new_layout);
+            if !new_ptr.is_null() {
+                core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                self.dealloc(ptr, layout);
+            }
+            new_ptr
+        }
+    }
+}
+
+/// Reports an allocation failure using the `LowLevelDebug` capsule, then
+/// terminates the process. This is intended to be called from the app's
+/// `#[alloc_error_handler]`.
+pub fn handle_alloc_error<S: Syscalls>(layout: Layout) -> ! {
+    LowLevelDebug::<S>::print_alert_code(AlertCode::Panic);
+    LowLevelDebug::<S>::print_2(layout.size() as u32, layout.align() as u32);
+    S::exit_terminate(ErrorCode::NoMem as u32);
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use libtock_unittest::{fake, ExpectedSyscall};
+
+type BrkAllocator = super::BrkAllocator<fake::Syscalls>;
+
+#[test]
+fn alloc() {
+    let kernel = fake::Kernel::new();
+    let allocator = BrkAllocator::new();
+    let initial_break = kernel.app_break();
+
+    let layout = Layout::from_size_align(3, 1).unwrap();
+    let first = unsafe { allocator.alloc(layout) };
+    assert_eq!(first, initial_break);
+    assert_eq!(kernel.app_break(), initial_break.wrapping_add(3));
+
+    // The second allocation should be aligned, and the allocations should not
+    // overlap.
+    let layout = Layout::from_size_align(8, 4).unwrap();
+    let second = unsafe { allocator.alloc(layout) };
+    assert_eq!(second as usize % 4, 0);
+    assert!(second as usize >= first as usize + 3);
+    assert_eq!(kernel.app_break(), second.wrapping_add(8));
+
+    // The allocated memory is usable.
+    unsafe {
+        first.write_bytes(0x12, 3);
+        second.write_bytes(0x34, 8);
+        assert_eq!(first.add(2).read(), 0x12);
+        assert_eq!(second.read(), 0x34);
+    }
+}
+
+#[test]
+fn alloc_failure() {
+    let kernel = fake::Kernel::new();
+    let allocator = BrkAllocator::new();
+
+    // The fake kernel's process memory is much smaller than this.
+    let layout = Layout::from_size_align(0x10000, 1).unwrap();
+    assert!(unsafe { allocator.alloc(layout) }.is_null());
+
+    // An error from the kernel results in a null pointer as well.
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: libtock_platform::memop_id::SBRK,
+        argument0: 4,
+        return_error: Some(ErrorCode::NoMem),
+    });
+    let layout = Layout::from_size_align(4, 1).unwrap();
+    assert!(unsafe { allocator.alloc(layout) }.is_null());
+
+    // Failed allocations do not leak memory.
+    let initial_break = kernel.app_break();
+    assert_eq!(unsafe { allocator.alloc(layout) }, initial_break);
+}
+
+#[test]
+fn dealloc() {
+    let kernel = fake::Kernel::new();
+    let allocator = BrkAllocator::new();
+    let layout = Layout::from_size_align(16, 4).unwrap();
+
+    let first = unsafe { allocator.alloc(layout) };
+    let second = unsafe { allocator.alloc(layout) };
+    let app_break = kernel.app_break();
+
+    // Deallocating the most recent allocation makes its memory available
+    // again.
+    unsafe { allocator.dealloc(second, layout) };
+    assert_eq!(unsafe { allocator.alloc(layout) }, second);
+    assert_eq!(kernel.app_break(), app_break);
+
+    // Other memory is not reused.
+    unsafe { allocator.dealloc(first, layout) };
+    assert_eq!(unsafe { allocator.alloc(layout) }, app_break);
+}
+
+#[test]
+fn realloc() {
+    let kernel = fake::Kernel::new();
+    let allocator = BrkAllocator::new();
+    let layout = Layout::from_size_align(4, 4).unwrap();
+
+    let first = unsafe { allocator.alloc(layout) };
+    unsafe { first.write_bytes(0x56, 4) };
+
+    // The most recent allocation is resized in place.
+    let grown = unsafe { allocator.realloc(first, layout, 12) };
+    assert_eq!(grown, first);
+    assert_eq!(kernel.app_break(), first.wrapping_add(12));
+
+    // Other allocations are moved.
+    let layout = Layout::from_size_align(12, 4).unwrap();
+    let second = unsafe { allocator.alloc(layout) };
+    let moved = unsafe { allocator.realloc(first, layout, 16) };
+    assert_eq!(moved, second.wrapping_add(12));
+    assert_eq!(unsafe { moved.add(3).read() }, 0x56);
+
+    // Growing past the end of the process' memory fails.
+    let layout = Layout::from_size_align(16, 4).unwrap();
+    assert!(unsafe { allocator.realloc(moved, layout, 0x10000) }.is_null());
+}
+
+#[cfg(not(miri))]
+#[test]
+fn handle_alloc_error() {
+    let exit = libtock_unittest::exit_test("tests::handle_alloc_error", || {
+        let kernel = fake::Kernel::new();
+        let driver = fake::LowLevelDebug::new();
+        kernel.add_driver(&driver);
+        super::handle_alloc_error::<fake::Syscalls>(Layout::from_size_align(8, 4).unwrap());
+    });
+    assert_eq!(
+        exit,
+        libtock_unittest::ExitCall::Terminate(ErrorCode::NoMem as u32)
+    );
+}
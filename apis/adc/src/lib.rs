@@ -0,0 +1,306 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::ops::ControlFlow;
+use libtock_platform as platform;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The analog-to-digital converter driver.
+///
+/// It can take single samples, fill a buffer with samples, and sample
+/// continuously into a pair of buffers that the kernel fills alternately.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::adc::Adc;
+///
+/// let sample = Adc::read_single_sample(0).unwrap();
+/// ```
+pub struct Adc<
+    S: Syscalls,
+    C: platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<S: Syscalls, C: platform::allow_rw::Config + platform::subscribe::Config> Adc<S, C> {
+    /// Run a check against the adc capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success_u32()
+    }
+
+    /// Returns the number of ADC channels.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).to_result()
+    }
+
+    /// Returns the number of bits in each sample.
+    pub fn resolution_bits() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::RESOLUTION_BITS, 0, 0).to_result()
+    }
+
+    /// Returns the ADC's reference voltage, in millivolts. Returns `NoSupport`
+    /// if the reference voltage is unknown.
+    pub fn reference_voltage_mv() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::REFERENCE_VOLTAGE, 0, 0).to_result()
+    }
+
+    /// Takes a single sample from `channel`, blocking until the conversion
+    /// completes.
+    pub fn read_single_sample(channel: u32) -> Result<u16, ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::SAMPLES }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SAMPLES }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::SINGLE_SAMPLE, channel, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_, _, sample)) = done.get() {
+                    return Ok(sample as u16);
+                }
+            }
+        })
+    }
+
+    /// Fills `buffer` with samples from `channel`, taken at `frequency_hz`,
+    /// blocking until the buffer is full. Each sample occupies two bytes of
+    /// `buffer`; the returned iterator decodes them.
+    pub fn sample_buffer(
+        channel: u32,
+        frequency_hz: u32,
+        buffer: &mut [u8],
+    ) -> Result<Samples<'_>, ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        let count = share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::SAMPLES }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::BUFFER }>(allow_rw, buffer)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SAMPLES }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::BUFFERED_SAMPLE, channel, frequency_hz).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_, info, _)) = done.get() {
+                    return Ok(sample_count(info));
+                }
+            }
+        })?;
+        Ok(samples(buffer, count))
+    }
+
+    /// Samples `channel` continuously at `frequency_hz`. The kernel fills
+    /// `first` and `second` alternately, and `listener` is called with the
+    /// samples from each buffer once it is full. Sampling stops when
+    /// `listener` returns `ControlFlow::Break`.
+    ///
+    /// While `listener` reads one buffer, the other stays shared with the
+    /// kernel, so sampling continues into it. `listener` must return before
+    /// that buffer is full to avoid losing samples.
+    pub fn sample_continuous_buffered<F: FnMut(Samples) -> ControlFlow<()>>(
+        channel: u32,
+        frequency_hz: u32,
+        first: &mut [u8],
+        second: &mut [u8],
+        mut listener: F,
+    ) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::SAMPLES }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SAMPLES }>(subscribe, &done)?;
+
+            let mut started = false;
+            // The buffer the kernel is filling. It starts with the first
+            // buffer.
+            let mut filling_first = true;
+            let result = loop {
+                let step = if filling_first {
+                    Self::continuous_step::<{ allow_rw::BUFFER }, { allow_rw::SECOND_BUFFER }, _>(
+                        first,
+                        second,
+                        &done,
+                        &mut started,
+                        &mut listener,
+                        (channel, frequency_hz),
+                    )
+                } else {
+                    Self::continuous_step::<{ allow_rw::SECOND_BUFFER }, { allow_rw::BUFFER }, _>(
+                        second,
+                        first,
+                        &done,
+                        &mut started,
+                        &mut listener,
+                        (channel, frequency_hz),
+                    )
+                };
+                // After reporting a full buffer, the kernel fills the other one.
+                let flow = match step {
+                    Ok(Step::Filled(flow)) => {
+                        filling_first = !filling_first;
+                        flow
+                    }
+                    // The kernel reported the buffer it was expected to fill
+                    // next. Neither buffer is shared anymore, so it can be
+                    // read here, and the kernel keeps filling the same buffer.
+                    Ok(Step::NextFilled(count)) => {
+                        let buffer: &[u8] = if filling_first { second } else { first };
+                        listener(samples(buffer, count))
+                    }
+                    Err(error) => break Err(error),
+                };
+                if flow.is_break() {
+                    break Ok(());
+                }
+            };
+
+            if started {
+                // Stopping can only fail if sampling has already stopped.
+                let _ = S::command(DRIVER_NUM, command::STOP, 0, 0);
+            }
+            result
+        })
+    }
+
+    // Waits for the kernel to report a full buffer during continuous sampling.
+    // `next`, the buffer the kernel fills after `filling`, is shared in the
+    // outer scope so that it stays shared while `listener` reads `filling`.
+    // Both are shared again on every step because scopes can only nest; no
+    // Yield happens between ending one step and starting the next, so the
+    // kernel cannot deliver samples in between.
+    fn continuous_step<
+        const FILLING_NUM: u32,
+        const NEXT_NUM: u32,
+        F: FnMut(Samples) -> ControlFlow<()>,
+    >(
+        filling: &mut [u8],
+        next: &mut [u8],
+        done: &Cell<Option<(u32, u32, u32)>>,
+        started: &mut bool,
+        listener: &mut F,
+        (channel, frequency_hz): (u32, u32),
+    ) -> Result<Step, ErrorCode> {
+        let filling_address = filling.as_ptr() as u32;
+        share::scope::<AllowRw<_, DRIVER_NUM, NEXT_NUM>, _, _>(|allow_next| {
+            S::allow_rw::<C, DRIVER_NUM, NEXT_NUM>(allow_next, next)?;
+
+            let (info, address) =
+                share::scope::<AllowRw<_, DRIVER_NUM, FILLING_NUM>, _, _>(|allow_filling| {
+                    S::allow_rw::<C, DRIVER_NUM, FILLING_NUM>(allow_filling, filling)?;
+
+                    if !*started {
+                        S::command(
+                            DRIVER_NUM,
+                            command::CONTINUOUS_BUFFERED_SAMPLE,
+                            channel,
+                            frequency_hz,
+                        )
+                        .to_result()?;
+                        *started = true;
+                    }
+
+                    loop {
+                        S::yield_wait();
+                        if let Some((_, info, address)) = done.take() {
+                            return Ok::<_, ErrorCode>((info, address));
+                        }
+                    }
+                })?;
+
+            // The upcall reports the address of the buffer it filled.
+            if address != filling_address {
+                return Ok(Step::NextFilled(sample_count(info)));
+            }
+            Ok(Step::Filled(listener(samples(filling, sample_count(info)))))
+        })
+    }
+}
+
+// The outcome of a step of continuous sampling.
+enum Step {
+    // The buffer being filled was full and has been passed to the listener,
+    // which returned the given value.
+    Filled(ControlFlow<()>),
+    // The other buffer was full instead, with the given number of samples.
+    NextFilled(usize),
+}
+
+/// An iterator over the samples in a buffer filled by the ADC. Each sample is
+/// stored in the buffer as a little-endian `u16`.
+#[derive(Clone, Debug)]
+pub struct Samples<'a> {
+    bytes: core::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for Samples<'a> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        self.bytes
+            .next()
+            .map(|sample| u16::from_le_bytes([sample[0], sample[1]]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bytes.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Samples<'a> {}
+
+// Returns an iterator over the first `count` samples in `buffer`.
+fn samples(buffer: &[u8], count: usize) -> Samples {
+    let len = buffer.len().min(2 * count);
+    Samples {
+        bytes: buffer[..len].chunks_exact(2),
+    }
+}
+
+// Extracts the number of samples from the second argument of a buffered sample
+// upcall, which contains the channel in its low 8 bits and the number of
+// samples above that.
+fn sample_count(info: u32) -> usize {
+    (info >> 8) as usize
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x5;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SINGLE_SAMPLE: u32 = 1;
+    pub const BUFFERED_SAMPLE: u32 = 3;
+    pub const CONTINUOUS_BUFFERED_SAMPLE: u32 = 4;
+    pub const STOP: u32 = 5;
+    pub const RESOLUTION_BITS: u32 = 101;
+    pub const REFERENCE_VOLTAGE: u32 = 102;
+}
+
+mod subscribe {
+    pub const SAMPLES: u32 = 0;
+}
+
+mod allow_rw {
+    pub const BUFFER: u32 = 0;
+    pub const SECOND_BUFFER: u32 = 1;
+}
@@ -0,0 +1,166 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Adc = super::Adc<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Adc::driver_check());
+    assert_eq!(Adc::count(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(3);
+    kernel.add_driver(&driver);
+
+    assert!(Adc::driver_check());
+    assert_eq!(Adc::count(), Ok(3));
+}
+
+#[test]
+fn properties() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(1);
+    kernel.add_driver(&driver);
+
+    driver.set_resolution_bits(10);
+    assert_eq!(Adc::resolution_bits(), Ok(10));
+    assert_eq!(Adc::reference_voltage_mv(), Ok(3300));
+    driver.set_reference_voltage_mv(None);
+    assert_eq!(Adc::reference_voltage_mv(), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn single_sample() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(2);
+    kernel.add_driver(&driver);
+    driver.set_waveform(&[100, 4095]);
+
+    assert_eq!(Adc::read_single_sample(1), Ok(100));
+    assert_eq!(Adc::read_single_sample(0), Ok(4095));
+    assert_eq!(Adc::read_single_sample(2), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn sample_buffer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(1);
+    kernel.add_driver(&driver);
+    driver.set_waveform(&[1, 2, 0x1234]);
+
+    let mut buffer = [0; 7];
+    let samples = Adc::sample_buffer(0, 1000, &mut buffer).unwrap();
+    assert_eq!(samples.len(), 3);
+    assert!(samples.eq([1, 2, 0x1234]));
+    assert_eq!(buffer, [1, 0, 2, 0, 0x34, 0x12, 0]);
+}
+
+#[test]
+fn sample_continuous_buffered() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(1);
+    kernel.add_driver(&driver);
+    driver.set_waveform(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let mut first = [0; 6];
+    let mut second = [0; 4];
+    let mut buffers = 0;
+    let mut next = 0;
+    Adc::sample_continuous_buffered(0, 1000, &mut first, &mut second, |samples| {
+        // The buffers are filled alternately, each with consecutive samples.
+        let expected_len = if buffers % 2 == 0 { 3 } else { 2 };
+        assert_eq!(samples.len(), expected_len);
+        for sample in samples {
+            assert_eq!(sample, next % 10);
+            next += 1;
+        }
+        buffers += 1;
+        match buffers {
+            5 => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    })
+    .unwrap();
+    assert_eq!(buffers, 5);
+    assert_eq!(next, 13);
+    assert!(!driver.is_sampling());
+}
+
+#[test]
+fn sample_continuous_buffered_while_reading() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(1);
+    kernel.add_driver(&driver);
+    driver.set_waveform(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let mut first = [0; 4];
+    let mut second = [0; 4];
+    let mut buffers = 0;
+    let mut next = 0;
+    Adc::sample_continuous_buffered(0, 1000, &mut first, &mut second, |samples| {
+        // The ADC fills the other buffer while this one is being read.
+        driver.fill_next_buffer();
+        for sample in samples {
+            assert_eq!(sample, next % 10);
+            next += 1;
+        }
+        buffers += 1;
+        match buffers {
+            6 => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    })
+    .unwrap();
+    assert_eq!(buffers, 6);
+    assert_eq!(next, 12);
+    assert_eq!(driver.missed_buffers(), 0);
+}
+
+#[test]
+fn failed_sample_continuous_buffered() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new(1);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::SAMPLES,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::SECOND_BUFFER,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::BUFFER,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::CONTINUOUS_BUFFERED_SAMPLE,
+        argument0: 0,
+        argument1: 1000,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(
+        Adc::sample_continuous_buffered(0, 1000, &mut [0; 2], &mut [0; 2], |_| {
+            ControlFlow::Continue(())
+        }),
+        Err(ErrorCode::Busy)
+    );
+    // Sampling never started, so it should not be stopped.
+    assert!(kernel.take_syscall_log().into_iter().all(|entry| !matches!(
+        entry,
+        libtock_unittest::SyscallLogEntry::Command {
+            command_id: command::STOP,
+            ..
+        }
+    )));
+}
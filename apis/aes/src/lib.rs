@@ -0,0 +1,182 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The AES driver, which encrypts and decrypts data with AES-128 using the
+/// hardware AES engine.
+///
+/// An operation starts with `setup`, which selects the block cipher mode, the
+/// direction, the key and the IV. `crypt` can then be called repeatedly to
+/// process consecutive parts of the message; the cipher state (the CBC
+/// chaining value or the CTR counter) carries over between calls. `finish`
+/// ends the operation.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::aes::{Aes, AesMode, Direction};
+///
+/// let key = [0; 16];
+/// let iv = [0; 16];
+/// let mut ciphertext = [0; 32];
+/// Aes::setup(AesMode::Cbc, Direction::Encrypt, &key, &iv).unwrap();
+/// Aes::crypt(&[0x55; 32], &mut ciphertext).unwrap();
+/// Aes::finish().unwrap();
+/// ```
+pub struct Aes<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Aes<S, C>
+{
+    /// Run a check against the aes capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Starts an operation. `iv` is the initialization vector in CBC mode and
+    /// the initial counter block in CTR mode; it is ignored in ECB mode. The
+    /// kernel copies `key` and `iv`, so they do not need to stay shared.
+    pub fn setup(
+        mode: AesMode,
+        direction: Direction,
+        key: &[u8; KEY_LEN],
+        iv: &[u8; BLOCK_LEN],
+    ) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            command::SET_ALGORITHM,
+            mode as u32,
+            direction as u32,
+        )
+        .to_result::<(), _>()?;
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::IV }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_iv) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::IV }>(allow_iv, iv)?;
+
+            S::command(DRIVER_NUM, command::SETUP, 0, 0).to_result()
+        })
+    }
+
+    /// Encrypts or decrypts `source` into the start of `destination`. In ECB
+    /// and CBC mode, `source`'s length must be a multiple of `BLOCK_LEN`. Fails
+    /// with `ErrorCode::Size` if `destination` is shorter than `source`.
+    pub fn crypt(source: &[u8], destination: &mut [u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::SOURCE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::DESTINATION }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::SOURCE }>(allow_ro, source)?;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DESTINATION }>(allow_rw, destination)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::CRYPT, 0, 0).to_result()?;
+
+            wait_for_status::<S>(&done)
+        })
+    }
+
+    /// Ends the current operation.
+    pub fn finish() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result()
+    }
+}
+
+/// The length of an AES-128 key.
+pub const KEY_LEN: usize = 16;
+
+/// The length of an AES block.
+pub const BLOCK_LEN: usize = 16;
+
+/// The block cipher modes supported by the AES driver.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AesMode {
+    Ecb = 0,
+    Cbc = 1,
+    Ctr = 2,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Decrypt = 0,
+    Encrypt = 1,
+}
+
+// Waits for the DONE upcall and returns the status it carries.
+fn wait_for_status<S: Syscalls>(done: &Cell<Option<(u32,)>>) -> Result<(), ErrorCode> {
+    loop {
+        S::yield_wait();
+        if let Some((status,)) = done.get() {
+            return match ErrorCode::try_from(status) {
+                Ok(error) => Err(error),
+                Err(_) => Ok(()),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const SETUP: u32 = 2;
+    pub const CRYPT: u32 = 3;
+    pub const FINISH: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const IV: u32 = 1;
+    pub const SOURCE: u32 = 2;
+}
+
+mod allow_rw {
+    pub const DESTINATION: u32 = 0;
+}
@@ -0,0 +1,164 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Aes = super::Aes<fake::Syscalls>;
+
+// Known-answer vectors from NIST SP 800-38A (the first two blocks of the
+// AES-128 examples).
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const PLAINTEXT: [u8; 32] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+];
+const ECB_CIPHERTEXT: [u8; 32] = [
+    0x3a, 0xd7, 0x7b, 0xb4, 0x0d, 0x7a, 0x36, 0x60, 0xa8, 0x9e, 0xca, 0xf3, 0x24, 0x66, 0xef, 0x97,
+    0xf5, 0xd3, 0xd5, 0x85, 0x03, 0xb9, 0x69, 0x9d, 0xe7, 0x85, 0x89, 0x5a, 0x96, 0xfd, 0xba, 0xaf,
+];
+const CBC_IV: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+];
+const CBC_CIPHERTEXT: [u8; 32] = [
+    0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46, 0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19, 0x7d,
+    0x50, 0x86, 0xcb, 0x9b, 0x50, 0x72, 0x19, 0xee, 0x95, 0xdb, 0x11, 0x3a, 0x91, 0x76, 0x78, 0xb2,
+];
+const CTR_COUNTER: [u8; 16] = [
+    0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+];
+const CTR_CIPHERTEXT: [u8; 32] = [
+    0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+    0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Aes::driver_check());
+    assert_eq!(Aes::finish(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    assert!(Aes::driver_check());
+}
+
+#[test]
+fn ecb() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let mut output = [0; 32];
+    Aes::setup(AesMode::Ecb, Direction::Encrypt, &KEY, &[0; 16]).unwrap();
+    assert_eq!(Aes::crypt(&PLAINTEXT, &mut output), Ok(()));
+    assert_eq!(output, ECB_CIPHERTEXT);
+    Aes::finish().unwrap();
+
+    Aes::setup(AesMode::Ecb, Direction::Decrypt, &KEY, &[0; 16]).unwrap();
+    assert_eq!(Aes::crypt(&ECB_CIPHERTEXT, &mut output), Ok(()));
+    assert_eq!(output, PLAINTEXT);
+    Aes::finish().unwrap();
+    assert!(!driver.is_active());
+}
+
+#[test]
+fn cbc() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    // The chaining value carries over between calls to crypt.
+    let mut output = [0; 32];
+    Aes::setup(AesMode::Cbc, Direction::Encrypt, &KEY, &CBC_IV).unwrap();
+    assert_eq!(Aes::crypt(&PLAINTEXT[..16], &mut output[..16]), Ok(()));
+    assert_eq!(Aes::crypt(&PLAINTEXT[16..], &mut output[16..]), Ok(()));
+    assert_eq!(output, CBC_CIPHERTEXT);
+    Aes::finish().unwrap();
+
+    Aes::setup(AesMode::Cbc, Direction::Decrypt, &KEY, &CBC_IV).unwrap();
+    assert_eq!(Aes::crypt(&CBC_CIPHERTEXT, &mut output), Ok(()));
+    assert_eq!(output, PLAINTEXT);
+    Aes::finish().unwrap();
+}
+
+#[test]
+fn ctr() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    // CTR mode does not require whole blocks.
+    let mut output = [0; 32];
+    Aes::setup(AesMode::Ctr, Direction::Encrypt, &KEY, &CTR_COUNTER).unwrap();
+    assert_eq!(Aes::crypt(&PLAINTEXT[..5], &mut output[..5]), Ok(()));
+    assert_eq!(Aes::crypt(&PLAINTEXT[5..], &mut output[5..]), Ok(()));
+    assert_eq!(output, CTR_CIPHERTEXT);
+    Aes::finish().unwrap();
+
+    Aes::setup(AesMode::Ctr, Direction::Decrypt, &KEY, &CTR_COUNTER).unwrap();
+    assert_eq!(Aes::crypt(&CTR_CIPHERTEXT, &mut output), Ok(()));
+    assert_eq!(output, PLAINTEXT);
+    Aes::finish().unwrap();
+}
+
+#[test]
+fn invalid_lengths() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let mut output = [0; 32];
+    Aes::setup(AesMode::Cbc, Direction::Encrypt, &KEY, &CBC_IV).unwrap();
+    assert_eq!(
+        Aes::crypt(&PLAINTEXT[..15], &mut output),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        Aes::crypt(&PLAINTEXT, &mut output[..16]),
+        Err(ErrorCode::Size)
+    );
+    Aes::finish().unwrap();
+    assert_eq!(Aes::finish(), Err(ErrorCode::Already));
+}
+
+#[test]
+fn failed_crypt() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+    Aes::setup(AesMode::Ecb, Direction::Encrypt, &KEY, &[0; 16]).unwrap();
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::SOURCE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::DESTINATION,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::CRYPT,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    let mut output = [0; 16];
+    assert_eq!(
+        Aes::crypt(&PLAINTEXT[..16], &mut output),
+        Err(ErrorCode::Busy)
+    );
+}
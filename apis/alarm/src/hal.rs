@@ -0,0 +1,66 @@
+use crate::Alarm;
+use core::marker::PhantomData;
+use core::time::Duration;
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, Syscalls};
+
+/// A delay provider that implements the `embedded_hal::blocking::delay`
+/// traits by sleeping on the alarm. Like `Alarm::sleep`, delays replace any
+/// alarm set with `Alarm::set_relative` or `Alarm::set_absolute`.
+///
+/// The traits have no way to report errors, so the delays panic if the alarm
+/// driver fails.
+pub struct Delay<S: Syscalls, C: platform::subscribe::Config = DefaultConfig> {
+    _alarm: PhantomData<Alarm<S, C>>,
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Delay<S, C> {
+    pub fn new() -> Self {
+        Delay {
+            _alarm: PhantomData,
+        }
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Default for Delay<S, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayMs<u32> for Delay<S, C> {
+    fn delay_ms(&mut self, ms: u32) {
+        Alarm::<S, C>::sleep(Duration::from_millis(ms.into())).expect("alarm failed");
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayMs<u16> for Delay<S, C> {
+    fn delay_ms(&mut self, ms: u16) {
+        self.delay_ms(u32::from(ms));
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayMs<u8> for Delay<S, C> {
+    fn delay_ms(&mut self, ms: u8) {
+        self.delay_ms(u32::from(ms));
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayUs<u32> for Delay<S, C> {
+    fn delay_us(&mut self, us: u32) {
+        Alarm::<S, C>::sleep(Duration::from_micros(us.into())).expect("alarm failed");
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayUs<u16> for Delay<S, C> {
+    fn delay_us(&mut self, us: u16) {
+        self.delay_us(u32::from(us));
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> DelayUs<u8> for Delay<S, C> {
+    fn delay_us(&mut self, us: u8) {
+        self.delay_us(u32::from(us));
+    }
+}
@@ -0,0 +1,225 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::time::Duration;
+use libtock_platform as platform;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::Delay;
+
+/// The alarm driver.
+///
+/// It provides access to the kernel's clock and a single alarm per process,
+/// which can be used to sleep or to receive an upcall at a given time.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::alarm::{Alarm, Milliseconds};
+///
+/// // Sleeps for half a second.
+/// Alarm::sleep_for(Milliseconds(500)).unwrap();
+/// ```
+pub struct Alarm<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+/// A clock frequency, in Hertz.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Hz(pub u32);
+
+/// A duration measured in ticks of the alarm's clock.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ticks(pub u32);
+
+/// A duration measured in milliseconds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Milliseconds(pub u32);
+
+/// Converts a duration into a number of clock ticks.
+pub trait Convert {
+    /// Returns the number of ticks of a clock running at `frequency` that
+    /// corresponds to this duration, rounding up and saturating at
+    /// `u32::MAX`.
+    fn to_ticks(self, frequency: Hz) -> Ticks;
+}
+
+impl Convert for Ticks {
+    fn to_ticks(self, _frequency: Hz) -> Ticks {
+        self
+    }
+}
+
+impl Convert for Milliseconds {
+    fn to_ticks(self, frequency: Hz) -> Ticks {
+        let ticks = (self.0 as u64 * frequency.0 as u64 + 999) / 1000;
+        Ticks(ticks.try_into().unwrap_or(u32::MAX))
+    }
+}
+
+impl Ticks {
+    /// Converts this number of ticks of a clock running at `frequency` into
+    /// milliseconds, rounding down.
+    pub fn to_milliseconds(self, frequency: Hz) -> Milliseconds {
+        if frequency.0 == 0 {
+            return Milliseconds(0);
+        }
+        let ms = self.0 as u64 * 1000 / frequency.0 as u64;
+        Milliseconds(ms.try_into().unwrap_or(u32::MAX))
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
+    /// Run a check against the alarm capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the frequency of the alarm's clock.
+    pub fn get_frequency() -> Result<Hz, ErrorCode> {
+        S::command(DRIVER_NUM, command::FREQUENCY, 0, 0)
+            .to_result()
+            .map(Hz)
+    }
+
+    /// Returns the current value of the alarm's clock, in ticks. The clock
+    /// wraps around when it overflows.
+    pub fn get_ticks() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::TIME, 0, 0).to_result()
+    }
+
+    /// Sleeps for (at least) the given duration. This replaces any alarm set
+    /// with `set_relative` or `set_absolute`.
+    pub fn sleep_for<T: Convert>(time: T) -> Result<(), ErrorCode> {
+        let ticks = time.to_ticks(Self::get_frequency()?);
+        Self::sleep_ticks(ticks.0)
+    }
+
+    /// Sleeps for (at least) the given duration. Durations that are longer than
+    /// the alarm's clock can represent are split into multiple alarms. This
+    /// replaces any alarm set with `set_relative` or `set_absolute`.
+    pub fn sleep(duration: Duration) -> Result<(), ErrorCode> {
+        let frequency = Self::get_frequency()?.0 as u64;
+        let subsec_ticks =
+            (duration.subsec_nanos() as u64 * frequency + 999_999_999) / 1_000_000_000;
+        let mut ticks = duration
+            .as_secs()
+            .saturating_mul(frequency)
+            .saturating_add(subsec_ticks);
+        loop {
+            let chunk = ticks.min(u32::MAX as u64);
+            Self::sleep_ticks(chunk as u32)?;
+            ticks -= chunk;
+            if ticks == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn sleep_ticks(ticks: u32) -> Result<(), ErrorCode> {
+        let called = Cell::new(Option::<(u32,)>::None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, &called)?;
+
+            S::command(DRIVER_NUM, command::SET_RELATIVE, ticks, 0).to_result::<u32, _>()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_,)) = called.get() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    /// Sets the alarm to expire after the given duration, replacing any
+    /// previously set alarm. When the alarm expires, the kernel calls the
+    /// listener registered with `register_listener`. Returns the value of the
+    /// clock at which the alarm will expire.
+    pub fn set_relative<T: Convert>(time: T) -> Result<u32, ErrorCode> {
+        let ticks = time.to_ticks(Self::get_frequency()?);
+        S::command(DRIVER_NUM, command::SET_RELATIVE, ticks.0, 0).to_result()
+    }
+
+    /// Sets the alarm to expire `dt` after `reference`, a value previously read
+    /// from the clock, replacing any previously set alarm. If that time has
+    /// already passed, the alarm expires immediately. Returns the value of the
+    /// clock at which the alarm will expire.
+    pub fn set_absolute<T: Convert>(reference: u32, dt: T) -> Result<u32, ErrorCode> {
+        let ticks = dt.to_ticks(Self::get_frequency()?);
+        S::command(DRIVER_NUM, command::SET_ABSOLUTE, reference, ticks.0).to_result()
+    }
+
+    /// Cancels the pending alarm. Returns `Err(ErrorCode::Already)` if no alarm
+    /// is set.
+    pub fn cancel() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
+
+    /// Register an alarm listener
+    ///
+    /// There can be only one single listener registered at a time.
+    /// Each time this function is used, it will replace the
+    /// previously registered listener.
+    pub fn register_listener<'share, F: Fn(u32, u32)>(
+        listener: &'share AlarmListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::CALLBACK }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, listener)
+    }
+
+    /// Unregister the alarm listener
+    ///
+    /// This function may be used even if there was no
+    /// previously registered listener.
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::CALLBACK)
+    }
+}
+
+/// A wrapper around a closure to be registered and called when the alarm
+/// expires. The closure receives the value of the clock when the upcall was
+/// generated and the alarm's expiration time.
+///
+/// ```ignore
+/// let listener = AlarmListener(|now, expiration| {
+///     // handle the alarm
+/// });
+/// ```
+pub struct AlarmListener<F: Fn(u32, u32)>(pub F);
+
+impl<F: Fn(u32, u32)> Upcall<OneId<DRIVER_NUM, { subscribe::CALLBACK }>> for AlarmListener<F> {
+    fn upcall(&self, now: u32, expiration: u32, _arg2: u32) {
+        self.0(now, expiration)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const FREQUENCY: u32 = 1;
+    pub const TIME: u32 = 2;
+    pub const STOP: u32 = 3;
+    pub const SET_RELATIVE: u32 = 5;
+    pub const SET_ABSOLUTE: u32 = 6;
+}
+
+mod subscribe {
+    pub const CALLBACK: u32 = 0;
+}
@@ -0,0 +1,172 @@
+use super::*;
+use core::time::Duration;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Alarm = super::Alarm<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Alarm::driver_check());
+    assert_eq!(Alarm::get_frequency(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    assert!(Alarm::driver_check());
+    assert_eq!(Alarm::get_frequency(), Ok(Hz(1000)));
+}
+
+#[test]
+fn get_ticks() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Alarm::get_ticks(), Ok(0));
+    driver.advance(1234);
+    assert_eq!(Alarm::get_ticks(), Ok(1234));
+}
+
+#[test]
+fn conversions() {
+    let frequency = Hz(32768);
+    assert_eq!(Ticks(7).to_ticks(frequency), Ticks(7));
+    assert_eq!(Milliseconds(1000).to_ticks(frequency), Ticks(32768));
+    // Milliseconds round up to a whole number of ticks.
+    assert_eq!(Milliseconds(1).to_ticks(frequency), Ticks(33));
+    assert_eq!(Milliseconds(u32::MAX).to_ticks(frequency), Ticks(u32::MAX));
+    // Ticks round down to a whole number of milliseconds.
+    assert_eq!(Ticks(33).to_milliseconds(frequency), Milliseconds(1));
+    assert_eq!(Ticks(32768).to_milliseconds(frequency), Milliseconds(1000));
+    assert_eq!(Ticks(5).to_milliseconds(Hz(0)), Milliseconds(0));
+}
+
+#[test]
+fn sleep() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    Alarm::sleep_for(Milliseconds(50)).unwrap();
+    assert_eq!(driver.now(), 50);
+    Alarm::sleep_for(Ticks(7)).unwrap();
+    assert_eq!(driver.now(), 57);
+    Alarm::sleep(Duration::from_micros(2500)).unwrap();
+    assert_eq!(driver.now(), 60);
+}
+
+#[test]
+fn sleep_long() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(u32::MAX);
+    kernel.add_driver(&driver);
+
+    // 3 seconds is longer than the clock can represent, so it takes multiple
+    // alarms.
+    Alarm::sleep(Duration::from_secs(3)).unwrap();
+    assert_eq!(driver.now(), u32::MAX - 2);
+    let alarms = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter(|entry| {
+            matches!(
+                entry,
+                libtock_unittest::SyscallLogEntry::Command {
+                    command_id: command::SET_RELATIVE,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!(alarms, 3);
+}
+
+#[test]
+fn failed_sleep() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::FREQUENCY,
+        argument0: 0,
+        argument1: 0,
+        override_return: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::CALLBACK,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::SET_RELATIVE,
+        argument0: 10,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Fail)),
+    });
+
+    assert_eq!(Alarm::sleep_for(Milliseconds(10)), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn one_shot() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    let fired = Cell::new(None);
+    let listener = AlarmListener(|now, expiration| fired.set(Some((now, expiration))));
+    share::scope(|subscribe| {
+        Alarm::register_listener(&listener, subscribe).unwrap();
+
+        assert_eq!(Alarm::set_relative(Milliseconds(20)), Ok(20));
+        driver.advance(19);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(fired.get(), None);
+        driver.advance(1);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(fired.take(), Some((20, 20)));
+
+        assert_eq!(Alarm::set_absolute(15, Ticks(10)), Ok(25));
+        assert_eq!(Alarm::cancel(), Ok(()));
+        assert_eq!(Alarm::cancel(), Err(ErrorCode::Already));
+        driver.advance(10);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(fired.get(), None);
+
+        Alarm::unregister_listener();
+        assert_eq!(Alarm::set_relative(Ticks(0)), Ok(30));
+        driver.advance(0);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(fired.get(), None);
+    });
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    let mut delay = Delay::<fake::Syscalls>::new();
+    delay.delay_ms(20u32);
+    assert_eq!(driver.now(), 20);
+    delay.delay_ms(5u16);
+    delay.delay_ms(3u8);
+    assert_eq!(driver.now(), 28);
+    // Microsecond delays round up to a whole tick.
+    delay.delay_us(1500u32);
+    assert_eq!(driver.now(), 30);
+    delay.delay_us(1u8);
+    assert_eq!(driver.now(), 31);
+}
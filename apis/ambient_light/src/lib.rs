@@ -0,0 +1,70 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The ambient light sensor driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ambient_light::AmbientLight;
+///
+/// let intensity = AmbientLight::read().unwrap();
+/// ```
+pub struct AmbientLight<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> AmbientLight<S, C> {
+    /// Run a check against the ambient light capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Measures the ambient light intensity, blocking until the sensor
+    /// returns it.
+    pub fn read() -> Result<Lux, ErrorCode> {
+        let reading = Cell::new(Option::<(u32,)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &reading)?;
+
+            S::command(DRIVER_NUM, command::READ_INTENSITY, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((value,)) = reading.get() {
+                    return Ok(Lux(value));
+                }
+            }
+        })
+    }
+}
+
+/// A light intensity, in lux.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Lux(pub u32);
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60002;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ_INTENSITY: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
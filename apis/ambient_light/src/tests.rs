@@ -0,0 +1,56 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type AmbientLight = super::AmbientLight<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!AmbientLight::driver_check());
+    assert_eq!(AmbientLight::read(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AmbientLight::new();
+    kernel.add_driver(&driver);
+
+    assert!(AmbientLight::driver_check());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AmbientLight::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(320);
+    assert_eq!(AmbientLight::read(), Ok(Lux(320)));
+    assert!(!driver.is_busy());
+
+    driver.set_error_sync(ErrorCode::Fail);
+    assert_eq!(AmbientLight::read(), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn failed_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AmbientLight::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READING,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ_INTENSITY,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(AmbientLight::read(), Err(ErrorCode::Busy));
+}
@@ -0,0 +1,319 @@
+//! Advertising data (AD) structures, as defined in the Bluetooth Core
+//! Specification Supplement, Part A, Section 1.
+
+use libtock_platform::ErrorCode;
+
+/// The maximum length of the advertising data in a legacy advertising PDU.
+pub const MAX_ADVERTISING_DATA_LEN: usize = 31;
+
+/// Bits of the `AdStructure::Flags` value.
+pub mod flags {
+    pub const LE_LIMITED_DISCOVERABLE: u8 = 0x01;
+    pub const LE_GENERAL_DISCOVERABLE: u8 = 0x02;
+    pub const BR_EDR_NOT_SUPPORTED: u8 = 0x04;
+}
+
+/// A single AD structure.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdStructure<'a> {
+    Flags(u8),
+    /// The complete list of 16-bit service UUIDs.
+    ServiceUuids16(Uuids16<'a>),
+    /// The complete list of 128-bit service UUIDs, each in little-endian byte
+    /// order (the order they are transmitted in).
+    ServiceUuids128(&'a [[u8; 16]]),
+    ShortenedLocalName(&'a str),
+    CompleteLocalName(&'a str),
+    /// The transmit power, in dBm.
+    TxPowerLevel(i8),
+    ManufacturerData {
+        company_id: u16,
+        data: &'a [u8],
+    },
+    /// An AD structure of a type not listed above, or one that could not be
+    /// parsed as its type.
+    Other {
+        ad_type: u8,
+        data: &'a [u8],
+    },
+}
+
+impl<'a> AdStructure<'a> {
+    fn ad_type(&self) -> u8 {
+        match self {
+            AdStructure::Flags(_) => ad_type::FLAGS,
+            AdStructure::ServiceUuids16(_) => ad_type::COMPLETE_UUIDS16,
+            AdStructure::ServiceUuids128(_) => ad_type::COMPLETE_UUIDS128,
+            AdStructure::ShortenedLocalName(_) => ad_type::SHORTENED_LOCAL_NAME,
+            AdStructure::CompleteLocalName(_) => ad_type::COMPLETE_LOCAL_NAME,
+            AdStructure::TxPowerLevel(_) => ad_type::TX_POWER_LEVEL,
+            AdStructure::ManufacturerData { .. } => ad_type::MANUFACTURER_DATA,
+            AdStructure::Other { ad_type, .. } => *ad_type,
+        }
+    }
+
+    // Returns the length of the structure's data, excluding the length and
+    // type bytes.
+    fn data_len(&self) -> usize {
+        match self {
+            AdStructure::Flags(_) | AdStructure::TxPowerLevel(_) => 1,
+            AdStructure::ServiceUuids16(uuids) => 2 * uuids.len(),
+            AdStructure::ServiceUuids128(uuids) => 16 * uuids.len(),
+            AdStructure::ShortenedLocalName(name) | AdStructure::CompleteLocalName(name) => {
+                name.len()
+            }
+            AdStructure::ManufacturerData { data, .. } => 2 + data.len(),
+            AdStructure::Other { data, .. } => data.len(),
+        }
+    }
+
+    // Writes the structure's data into `out`, which must be data_len() bytes
+    // long.
+    fn write_data(&self, out: &mut [u8]) {
+        match self {
+            AdStructure::Flags(flags) => out[0] = *flags,
+            AdStructure::TxPowerLevel(power) => out[0] = *power as u8,
+            AdStructure::ServiceUuids16(uuids) => {
+                for (out, uuid) in out.chunks_exact_mut(2).zip(uuids.iter()) {
+                    out.copy_from_slice(&uuid.to_le_bytes());
+                }
+            }
+            AdStructure::ServiceUuids128(uuids) => {
+                for (out, uuid) in out.chunks_exact_mut(16).zip(uuids.iter()) {
+                    out.copy_from_slice(uuid);
+                }
+            }
+            AdStructure::ShortenedLocalName(name) | AdStructure::CompleteLocalName(name) => {
+                out.copy_from_slice(name.as_bytes())
+            }
+            AdStructure::ManufacturerData { company_id, data } => {
+                out[..2].copy_from_slice(&company_id.to_le_bytes());
+                out[2..].copy_from_slice(data);
+            }
+            AdStructure::Other { data, .. } => out.copy_from_slice(data),
+        }
+    }
+
+    fn parse(ad_type: u8, data: &'a [u8]) -> AdStructure<'a> {
+        let parsed = match (ad_type, data.len()) {
+            (ad_type::FLAGS, 1) => Some(AdStructure::Flags(data[0])),
+            (ad_type::TX_POWER_LEVEL, 1) => Some(AdStructure::TxPowerLevel(data[0] as i8)),
+            (ad_type::COMPLETE_UUIDS16, len) if len % 2 == 0 => Some(AdStructure::ServiceUuids16(
+                Uuids16(Uuids16Repr::Encoded(data)),
+            )),
+            (ad_type::COMPLETE_UUIDS128, len) if len % 16 == 0 => {
+                // Safety: [u8; 16] has the same alignment as u8, and data
+                // contains exactly len / 16 arrays.
+                let uuids = unsafe {
+                    core::slice::from_raw_parts(data.as_ptr() as *const [u8; 16], len / 16)
+                };
+                Some(AdStructure::ServiceUuids128(uuids))
+            }
+            (ad_type::SHORTENED_LOCAL_NAME, _) => core::str::from_utf8(data)
+                .ok()
+                .map(AdStructure::ShortenedLocalName),
+            (ad_type::COMPLETE_LOCAL_NAME, _) => core::str::from_utf8(data)
+                .ok()
+                .map(AdStructure::CompleteLocalName),
+            (ad_type::MANUFACTURER_DATA, len) if len >= 2 => Some(AdStructure::ManufacturerData {
+                company_id: u16::from_le_bytes([data[0], data[1]]),
+                data: &data[2..],
+            }),
+            _ => None,
+        };
+        parsed.unwrap_or(AdStructure::Other { ad_type, data })
+    }
+}
+
+/// A list of 16-bit UUIDs, either provided by the application or parsed from
+/// advertising data.
+#[derive(Clone, Copy, Debug)]
+pub struct Uuids16<'a>(Uuids16Repr<'a>);
+
+#[derive(Clone, Copy, Debug)]
+enum Uuids16Repr<'a> {
+    Values(&'a [u16]),
+    // Little-endian encoded UUIDs.
+    Encoded(&'a [u8]),
+}
+
+impl<'a> Uuids16<'a> {
+    pub fn new(uuids: &'a [u16]) -> Uuids16<'a> {
+        Uuids16(Uuids16Repr::Values(uuids))
+    }
+
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Uuids16Repr::Values(values) => values.len(),
+            Uuids16Repr::Encoded(bytes) => bytes.len() / 2,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u16> {
+        match self.0 {
+            Uuids16Repr::Values(values) => values.get(index).copied(),
+            Uuids16Repr::Encoded(bytes) => bytes
+                .get(2 * index..2 * index + 2)
+                .map(|uuid| u16::from_le_bytes([uuid[0], uuid[1]])),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u16> + 'a {
+        let uuids = *self;
+        (0..uuids.len()).filter_map(move |index| uuids.get(index))
+    }
+}
+
+impl<'a> PartialEq for Uuids16<'a> {
+    fn eq(&self, other: &Uuids16) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a> Eq for Uuids16<'a> {}
+
+/// A buffer of advertising data, built from AD structures.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ble::{flags, AdStructure, AdvertisingData};
+///
+/// let mut data = AdvertisingData::new();
+/// data.push(AdStructure::Flags(flags::LE_GENERAL_DISCOVERABLE)).unwrap();
+/// data.push(AdStructure::CompleteLocalName("tock")).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AdvertisingData {
+    bytes: [u8; MAX_ADVERTISING_DATA_LEN],
+    len: usize,
+}
+
+impl AdvertisingData {
+    pub const fn new() -> AdvertisingData {
+        AdvertisingData {
+            bytes: [0; MAX_ADVERTISING_DATA_LEN],
+            len: 0,
+        }
+    }
+
+    /// Appends an AD structure. Returns `ErrorCode::Size` if the structure does
+    /// not fit in the remaining space.
+    pub fn push(&mut self, structure: AdStructure) -> Result<(), ErrorCode> {
+        let data_len = structure.data_len();
+        let end = self.len + 2 + data_len;
+        if end > MAX_ADVERTISING_DATA_LEN {
+            return Err(ErrorCode::Size);
+        }
+        // The length byte counts the type byte and the data.
+        self.bytes[self.len] = (data_len + 1) as u8;
+        self.bytes[self.len + 1] = structure.ad_type();
+        structure.write_data(&mut self.bytes[self.len + 2..end]);
+        self.len = end;
+        Ok(())
+    }
+
+    /// Removes all AD structures.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    pub fn ad_structures(&self) -> AdStructures {
+        AdStructures::new(self.as_bytes())
+    }
+}
+
+/// An iterator over the AD structures in advertising data. Iteration stops at
+/// the end of the data, at a zero-length structure (which marks the start of
+/// padding), or at a structure that extends past the end of the data.
+#[derive(Clone, Debug)]
+pub struct AdStructures<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> AdStructures<'a> {
+    pub fn new(bytes: &'a [u8]) -> AdStructures<'a> {
+        AdStructures { bytes }
+    }
+}
+
+impl<'a> Iterator for AdStructures<'a> {
+    type Item = AdStructure<'a>;
+
+    fn next(&mut self) -> Option<AdStructure<'a>> {
+        let (&len, rest) = self.bytes.split_first()?;
+        let len = len as usize;
+        if len == 0 || len > rest.len() {
+            self.bytes = &[];
+            return None;
+        }
+        let (structure, rest) = rest.split_at(len);
+        self.bytes = rest;
+        Some(AdStructure::parse(structure[0], &structure[1..]))
+    }
+}
+
+/// An advertisement received during a scan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AdvertisingReport<'a> {
+    /// The type of the advertising PDU (e.g. 0x00 for ADV_IND).
+    pub pdu_type: u8,
+    /// The advertiser's address, in little-endian byte order.
+    pub address: [u8; 6],
+    /// The advertising data.
+    pub data: &'a [u8],
+}
+
+impl<'a> AdvertisingReport<'a> {
+    /// Parses an advertising PDU, as written into the scan buffer by the
+    /// kernel. Returns `None` if the PDU is malformed.
+    pub fn parse(pdu: &'a [u8]) -> Option<AdvertisingReport<'a>> {
+        // The PDU consists of a two-byte header (the PDU type and the payload
+        // length), followed by the payload, which starts with the advertiser's
+        // address.
+        let payload_len = *pdu.get(1)? as usize;
+        let payload = pdu.get(2..2 + payload_len)?;
+        if payload.len() < 6 {
+            return None;
+        }
+        let mut address = [0; 6];
+        address.copy_from_slice(&payload[..6]);
+        Some(AdvertisingReport {
+            pdu_type: pdu[0] & 0x0f,
+            address,
+            data: &payload[6..],
+        })
+    }
+
+    pub fn ad_structures(&self) -> AdStructures<'a> {
+        AdStructures::new(self.data)
+    }
+
+    /// Returns the advertiser's complete or shortened local name, if it
+    /// advertises one.
+    pub fn local_name(&self) -> Option<&'a str> {
+        self.ad_structures().find_map(|structure| match structure {
+            AdStructure::CompleteLocalName(name) | AdStructure::ShortenedLocalName(name) => {
+                Some(name)
+            }
+            _ => None,
+        })
+    }
+}
+
+mod ad_type {
+    pub const FLAGS: u8 = 0x01;
+    pub const COMPLETE_UUIDS16: u8 = 0x03;
+    pub const COMPLETE_UUIDS128: u8 = 0x07;
+    pub const SHORTENED_LOCAL_NAME: u8 = 0x08;
+    pub const COMPLETE_LOCAL_NAME: u8 = 0x09;
+    pub const TX_POWER_LEVEL: u8 = 0x0a;
+    pub const MANUFACTURER_DATA: u8 = 0xff;
+}
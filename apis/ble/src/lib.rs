@@ -0,0 +1,159 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::ops::ControlFlow;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod ad;
+
+pub use ad::{
+    flags, AdStructure, AdStructures, AdvertisingData, AdvertisingReport, Uuids16,
+    MAX_ADVERTISING_DATA_LEN,
+};
+
+/// The BLE advertising driver, which can broadcast advertisements and perform
+/// passive scans.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ble::{flags, AdStructure, AdvertisingData, Ble};
+/// use libtock_platform::share;
+///
+/// let mut data = AdvertisingData::new();
+/// data.push(AdStructure::Flags(flags::BR_EDR_NOT_SUPPORTED)).unwrap();
+/// data.push(AdStructure::CompleteLocalName("tock")).unwrap();
+/// share::scope(|allow_ro| {
+///     Ble::start_advertising(&data, 100, allow_ro).unwrap();
+///     // ...
+///     Ble::stop_advertising().unwrap();
+/// });
+/// ```
+pub struct Ble<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Ble<S, C>
+{
+    /// Starts broadcasting non-connectable advertisements containing `data`,
+    /// one every `interval_ms` milliseconds. The kernel reads `data` for each
+    /// advertisement, so it stays shared until `allow_ro` is dropped;
+    /// advertising should be stopped with `stop_advertising` before then.
+    pub fn start_advertising<'share>(
+        data: &'share AdvertisingData,
+        interval_ms: u32,
+        allow_ro: Handle<AllowRo<'share, S, DRIVER_NUM, { allow_ro::ADVERTISING_DATA }>>,
+    ) -> Result<(), ErrorCode> {
+        S::allow_ro::<C, DRIVER_NUM, { allow_ro::ADVERTISING_DATA }>(allow_ro, data.as_bytes())?;
+        S::command(
+            DRIVER_NUM,
+            command::START_ADVERTISING,
+            ADV_NONCONN_IND,
+            interval_ms,
+        )
+        .to_result()
+    }
+
+    /// Stops advertising.
+    pub fn stop_advertising() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
+
+    /// Runs a passive scan, calling `listener` with each advertisement that is
+    /// received. The scan stops when `listener` returns `ControlFlow::Break`.
+    /// Malformed advertisements are skipped.
+    ///
+    /// Advertisements received while `listener` runs are dropped, so `listener`
+    /// should return quickly.
+    pub fn scan<F: FnMut(AdvertisingReport) -> ControlFlow<()>>(
+        mut listener: F,
+    ) -> Result<(), ErrorCode> {
+        let mut buffer = [0; SCAN_BUFFER_LEN];
+        let received = Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::SCAN }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SCAN }>(subscribe, &received)?;
+
+            let mut started = false;
+            let result = loop {
+                let len =
+                    share::scope::<AllowRw<_, DRIVER_NUM, { allow_rw::SCAN }>, _, _>(|allow_rw| {
+                        S::allow_rw::<C, DRIVER_NUM, { allow_rw::SCAN }>(allow_rw, &mut buffer)?;
+
+                        if !started {
+                            S::command(DRIVER_NUM, command::PASSIVE_SCAN, 0, 0).to_result()?;
+                            started = true;
+                        }
+
+                        loop {
+                            S::yield_wait();
+                            if let Some((status, len)) = received.take() {
+                                return match ErrorCode::try_from(status) {
+                                    Ok(error) => Err(error),
+                                    Err(_) => Ok(len as usize),
+                                };
+                            }
+                        }
+                    });
+                let len = match len {
+                    Ok(len) => len.min(SCAN_BUFFER_LEN),
+                    Err(error) => break Err(error),
+                };
+                if let Some(report) = AdvertisingReport::parse(&buffer[..len]) {
+                    if listener(report).is_break() {
+                        break Ok(());
+                    }
+                }
+            };
+
+            if started {
+                // Stopping can only fail if the scan has already stopped.
+                let _ = S::command(DRIVER_NUM, command::STOP, 0, 0);
+            }
+            result
+        })
+    }
+}
+
+// The length of the largest legacy advertising PDU: a two-byte header, the
+// six-byte advertiser address, and the advertising data.
+const SCAN_BUFFER_LEN: usize = 2 + 6 + MAX_ADVERTISING_DATA_LEN;
+
+// The PDU type of non-connectable, non-scannable undirected advertisements.
+const ADV_NONCONN_IND: u32 = 0x02;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30000;
+
+// Command IDs. Unlike most drivers, the BLE driver does not have a driver
+// check command: command 0 starts advertising.
+mod command {
+    pub const START_ADVERTISING: u32 = 0;
+    pub const STOP: u32 = 1;
+    pub const PASSIVE_SCAN: u32 = 5;
+}
+
+mod subscribe {
+    pub const SCAN: u32 = 0;
+}
+
+mod allow_ro {
+    pub const ADVERTISING_DATA: u32 = 0;
+}
+
+mod allow_rw {
+    pub const SCAN: u32 = 0;
+}
@@ -0,0 +1,177 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Ble = super::Ble<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ble::stop_advertising(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn encode_ad_structures() {
+    let mut data = AdvertisingData::new();
+    data.push(AdStructure::Flags(
+        flags::LE_GENERAL_DISCOVERABLE | flags::BR_EDR_NOT_SUPPORTED,
+    ))
+    .unwrap();
+    data.push(AdStructure::ServiceUuids16(Uuids16::new(&[0x180f, 0x181a])))
+        .unwrap();
+    data.push(AdStructure::CompleteLocalName("tock")).unwrap();
+    data.push(AdStructure::ManufacturerData {
+        company_id: 0x0059,
+        data: &[0xab],
+    })
+    .unwrap();
+    assert_eq!(
+        data.as_bytes(),
+        [
+            2, 0x01, 0x06, // Flags
+            5, 0x03, 0x0f, 0x18, 0x1a, 0x18, // 16-bit service UUIDs
+            5, 0x09, b't', b'o', b'c', b'k', // Complete local name
+            4, 0xff, 0x59, 0x00, 0xab, // Manufacturer data
+        ]
+    );
+
+    // The structures round-trip through the parser.
+    assert!(data.ad_structures().eq([
+        AdStructure::Flags(0x06),
+        AdStructure::ServiceUuids16(Uuids16::new(&[0x180f, 0x181a])),
+        AdStructure::CompleteLocalName("tock"),
+        AdStructure::ManufacturerData {
+            company_id: 0x0059,
+            data: &[0xab],
+        },
+    ]));
+
+    // Structures that do not fit in the remaining 11 bytes are rejected.
+    assert_eq!(
+        data.push(AdStructure::ServiceUuids128(&[[0; 16]])),
+        Err(ErrorCode::Size)
+    );
+    data.push(AdStructure::ShortenedLocalName("123456789"))
+        .unwrap();
+    assert_eq!(data.as_bytes().len(), MAX_ADVERTISING_DATA_LEN);
+    data.clear();
+    assert_eq!(data.as_bytes(), []);
+}
+
+#[test]
+fn parse_ad_structures() {
+    let uuid = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    let mut bytes = [0; 31];
+    bytes[..2].copy_from_slice(&[17, 0x07]);
+    bytes[2..18].copy_from_slice(&uuid);
+    bytes[18..].copy_from_slice(&[
+        2, 0x0a, 0xfc, // TX power: -4 dBm
+        3, 0x09, 0xff, 0xfe, // Invalid UTF-8 name
+        3, 0x01, 0x06, 0x00, // Flags with the wrong length
+        0, 0,
+    ]);
+    assert!(AdStructures::new(&bytes).eq([
+        AdStructure::ServiceUuids128(&[uuid]),
+        AdStructure::TxPowerLevel(-4),
+        AdStructure::Other {
+            ad_type: 0x09,
+            data: &[0xff, 0xfe],
+        },
+        AdStructure::Other {
+            ad_type: 0x01,
+            data: &[0x06, 0x00],
+        },
+    ]));
+
+    // Parsing stops at padding and at structures that run past the end.
+    assert_eq!(AdStructures::new(&[0, 2, 0x01, 0x06]).count(), 0);
+    assert_eq!(AdStructures::new(&[3, 0x09, b'a']).count(), 0);
+}
+
+#[test]
+fn advertise() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    let mut data = AdvertisingData::new();
+    data.push(AdStructure::CompleteLocalName("beacon")).unwrap();
+    share::scope(|allow_ro| {
+        Ble::start_advertising(&data, 100, allow_ro).unwrap();
+        assert_eq!(driver.advertising_interval(), Some(100));
+        assert_eq!(driver.advertising_data().as_deref(), Some(data.as_bytes()));
+        Ble::stop_advertising().unwrap();
+    });
+    assert_eq!(driver.advertising_interval(), None);
+}
+
+#[test]
+fn scan() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+    driver.add_scan_report(0x00, [1, 2, 3, 4, 5, 6], &[5, 0x09, b'n', b'o', b'd', b'e']);
+    // A report without any advertising data.
+    driver.add_scan_report(0x02, [0; 6], &[]);
+    driver.add_scan_report(0x42, [6; 6], &[2, 0x01, 0x06]);
+    driver.add_scan_report(0x00, [7; 6], &[]);
+
+    let mut reports = 0;
+    Ble::scan(|report| {
+        reports += 1;
+        match reports {
+            1 => {
+                assert_eq!(report.pdu_type, 0x00);
+                assert_eq!(report.address, [1, 2, 3, 4, 5, 6]);
+                assert_eq!(report.local_name(), Some("node"));
+            }
+            2 => {
+                assert_eq!(report.pdu_type, 0x02);
+                assert_eq!(report.local_name(), None);
+            }
+            _ => {
+                // The PDU header's upper bits are not part of the PDU type.
+                assert_eq!(report.pdu_type, 0x02);
+                assert!(report.ad_structures().eq([AdStructure::Flags(0x06)]));
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+    assert_eq!(reports, 3);
+    assert!(!driver.is_scanning());
+    assert_eq!(driver.pending_scan_reports(), 1);
+}
+
+#[test]
+fn failed_scan() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::SCAN,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::SCAN,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::PASSIVE_SCAN,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(
+        Ble::scan(|_| ControlFlow::Continue(())),
+        Err(ErrorCode::Busy)
+    );
+}
@@ -0,0 +1,37 @@
+use crate::{ButtonState, Buttons};
+use core::marker::PhantomData;
+use embedded_hal::digital::v2;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// A single button, which implements `embedded_hal::digital::v2::InputPin`.
+/// The pin reads high while the button is pressed.
+pub struct Button<S: Syscalls> {
+    button: u32,
+    _buttons: PhantomData<Buttons<S>>,
+}
+
+impl<S: Syscalls> Button<S> {
+    /// Returns the button with the given index. Fails with
+    /// `ErrorCode::Invalid` if there is no such button.
+    pub fn new(button: u32) -> Result<Self, ErrorCode> {
+        if button >= Buttons::<S>::count()? {
+            return Err(ErrorCode::Invalid);
+        }
+        Ok(Button {
+            button,
+            _buttons: PhantomData,
+        })
+    }
+}
+
+impl<S: Syscalls> v2::InputPin for Button<S> {
+    type Error = ErrorCode;
+
+    fn is_high(&self) -> Result<bool, ErrorCode> {
+        Ok(Buttons::<S>::read(self.button)? == ButtonState::Pressed)
+    }
+
+    fn is_low(&self) -> Result<bool, ErrorCode> {
+        Ok(Buttons::<S>::read(self.button)? == ButtonState::Released)
+    }
+}
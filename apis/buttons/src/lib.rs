@@ -4,6 +4,11 @@ use libtock_platform::{
     share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::Button;
+
 /// The Buttons driver
 ///
 /// # Example
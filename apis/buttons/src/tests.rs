@@ -114,3 +114,24 @@ fn subscribe() {
     });
     assert!(!pressed_interrupt_count.get());
 }
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::digital::v2::InputPin;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert!(core::matches!(
+        super::Button::<fake::Syscalls>::new(2),
+        Err(ErrorCode::Invalid)
+    ));
+    let button = super::Button::<fake::Syscalls>::new(1).unwrap();
+    assert_eq!(button.is_high(), Ok(false));
+    assert_eq!(button.is_low(), Ok(true));
+    assert_eq!(driver.set_pressed(1, true), Ok(()));
+    assert_eq!(button.is_high(), Ok(true));
+    assert_eq!(button.is_low(), Ok(false));
+}
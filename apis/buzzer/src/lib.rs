@@ -0,0 +1,133 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::time::Duration;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The buzzer driver, which plays tones on a piezo buzzer.
+///
+/// # Example
+/// ```ignore
+/// use core::time::Duration;
+/// use libtock2::buzzer::{note, Buzzer, Note};
+///
+/// // Plays an A for a quarter of a second.
+/// Buzzer::tone(note::A4, Duration::from_millis(250)).unwrap();
+///
+/// // Plays the start of a C major scale.
+/// let beat = Duration::from_millis(200);
+/// Buzzer::play(&[
+///     Note::new(note::C4, beat),
+///     Note::new(note::D4, beat),
+///     Note::new(note::E4, beat),
+/// ])
+/// .unwrap();
+/// ```
+pub struct Buzzer<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Buzzer<S, C> {
+    /// Run a check against the buzzer capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Plays a tone at `frequency_hz` for `duration`, and waits for it to
+    /// finish. The duration is rounded down to whole milliseconds. Fails with
+    /// `ErrorCode::Busy` if another process is using the buzzer.
+    pub fn tone(frequency_hz: u32, duration: Duration) -> Result<(), ErrorCode> {
+        let duration_ms = duration.as_millis().min(u32::MAX as u128) as u32;
+        let done = Cell::new(Option::<()>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::DONE }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::TONE, frequency_hz, duration_ms).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some(()) = done.get() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    /// Plays each note of `melody` in turn, stopping at the first error.
+    pub fn play(melody: &[Note]) -> Result<(), ErrorCode> {
+        for note in melody {
+            Self::tone(note.frequency_hz, note.duration)?;
+        }
+        Ok(())
+    }
+}
+
+/// A note of a melody played by `Buzzer::play`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Note {
+    pub frequency_hz: u32,
+    pub duration: Duration,
+}
+
+impl Note {
+    pub const fn new(frequency_hz: u32, duration: Duration) -> Note {
+        Note {
+            frequency_hz,
+            duration,
+        }
+    }
+}
+
+/// The frequencies of the notes in the fourth and fifth octaves, rounded to
+/// the nearest Hertz. Sharps are named with an `S` suffix.
+pub mod note {
+    pub const C4: u32 = 262;
+    pub const CS4: u32 = 277;
+    pub const D4: u32 = 294;
+    pub const DS4: u32 = 311;
+    pub const E4: u32 = 330;
+    pub const F4: u32 = 349;
+    pub const FS4: u32 = 370;
+    pub const G4: u32 = 392;
+    pub const GS4: u32 = 415;
+    pub const A4: u32 = 440;
+    pub const AS4: u32 = 466;
+    pub const B4: u32 = 494;
+    pub const C5: u32 = 523;
+    pub const CS5: u32 = 554;
+    pub const D5: u32 = 587;
+    pub const DS5: u32 = 622;
+    pub const E5: u32 = 659;
+    pub const F5: u32 = 698;
+    pub const FS5: u32 = 740;
+    pub const G5: u32 = 784;
+    pub const GS5: u32 = 831;
+    pub const A5: u32 = 880;
+    pub const AS5: u32 = 932;
+    pub const B5: u32 = 988;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90000;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const TONE: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
@@ -0,0 +1,101 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Buzzer = super::Buzzer<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Buzzer::driver_check());
+    assert_eq!(
+        Buzzer::tone(note::A4, Duration::from_millis(100)),
+        Err(ErrorCode::NoMem)
+    );
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+
+    assert!(Buzzer::driver_check());
+}
+
+#[test]
+fn tone() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(
+        Buzzer::tone(note::A4, Duration::from_micros(250_900)),
+        Ok(())
+    );
+    assert!(!driver.is_playing());
+    assert_eq!(
+        driver.take_tones(),
+        [fake::Tone {
+            frequency_hz: 440,
+            duration_ms: 250
+        }]
+    );
+    assert_eq!(
+        Buzzer::tone(0, Duration::from_millis(100)),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn play() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+
+    let beat = Duration::from_millis(200);
+    let melody = [
+        Note::new(note::E5, beat),
+        Note::new(note::DS5, beat),
+        Note::new(note::E5, beat * 2),
+    ];
+    assert_eq!(Buzzer::play(&melody), Ok(()));
+    let tones = driver.take_tones();
+    assert!(tones
+        .iter()
+        .map(|tone| (tone.frequency_hz, tone.duration_ms))
+        .eq([(659, 200), (622, 200), (659, 400)]));
+
+    // Playing stops at the first note that fails.
+    let melody = [
+        Note::new(note::C4, beat),
+        Note::new(0, beat),
+        Note::new(note::C4, beat),
+    ];
+    assert_eq!(Buzzer::play(&melody), Err(ErrorCode::Invalid));
+    assert_eq!(driver.take_tones().len(), 1);
+}
+
+#[test]
+fn failed_tone() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::TONE,
+        argument0: 440,
+        argument1: 100,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(
+        Buzzer::tone(440, Duration::from_millis(100)),
+        Err(ErrorCode::Busy)
+    );
+}
@@ -1,16 +1,21 @@
 #![no_std]
 
+use core::cell::Cell;
 use core::fmt;
 use core::marker::PhantomData;
+#[cfg(feature = "libtock_alarm")]
+use libtock_alarm::{Alarm, AlarmListener, Convert};
 use libtock_platform as platform;
 use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
 use libtock_platform::share;
 use libtock_platform::subscribe::Subscribe;
 use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
 
 /// The console driver.
 ///
-/// It allows libraries to pass strings to the kernel's console driver.
+/// It allows libraries to pass strings to the kernel's console driver, and to
+/// read bytes received by the console.
 ///
 /// # Example
 /// ```ignore
@@ -22,10 +27,16 @@ use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
 /// ```
 pub struct Console<
     S: Syscalls,
-    C: platform::allow_ro::Config + platform::subscribe::Config = DefaultConfig,
+    C: platform::allow_ro::Config
+        + platform::allow_rw::Config
+        + platform::subscribe::Config = DefaultConfig,
 >(S, C);
 
-impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> Console<S, C> {
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Console<S, C>
+{
     /// Run a check against the console capsule to ensure it is present.
     ///
     /// Returns `true` if the driver was present. This does not necessarily mean
@@ -66,6 +77,109 @@ impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> C
         })
     }
 
+    /// Reads bytes into `buf`, blocking until `buf` is full. Returns the number
+    /// of bytes read.
+    pub fn read(buf: &mut [u8]) -> Result<usize, ErrorCode> {
+        let read = Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let len = buf.len() as u32;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buf)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READ }>(subscribe, &read)?;
+
+            S::command(DRIVER_NUM, command::READ, len, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status, count)) = read.get() {
+                    return read_result(status, count);
+                }
+            }
+        })
+    }
+
+    /// Reads bytes into `buf`, blocking until either `buf` is full or
+    /// `timeout` has elapsed. If the timeout elapses, the read is aborted and
+    /// the bytes received so far are kept. Returns the number of bytes read.
+    ///
+    /// The timeout uses the process's single alarm. Any alarm set with
+    /// `Alarm::set_relative` or `Alarm::set_absolute` is replaced and then
+    /// cancelled, so it never fires, and any listener registered with
+    /// `Alarm::register_listener` is unregistered when this returns. Callers
+    /// that use the alarm must set it again afterwards.
+    ///
+    /// Only available with the `libtock_alarm` feature, so that console users
+    /// who don't need a timeout don't depend on the alarm driver.
+    #[cfg(feature = "libtock_alarm")]
+    pub fn read_with_timeout<T: Convert>(buf: &mut [u8], timeout: T) -> Result<usize, ErrorCode> {
+        let read = Cell::new(Option::<(u32, u32)>::None);
+        let expired = Cell::new(false);
+        let listener = AlarmListener(|_, _| expired.set(true));
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let len = buf.len() as u32;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buf)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READ }>(subscribe, &read)?;
+
+            share::scope(|alarm_subscribe| {
+                Alarm::<S, C>::register_listener(&listener, alarm_subscribe)?;
+                Alarm::<S, C>::set_relative(timeout)?;
+
+                if let Err(error) =
+                    S::command(DRIVER_NUM, command::READ, len, 0).to_result::<(), _>()
+                {
+                    let _ = Alarm::<S, C>::cancel();
+                    return Err(error);
+                }
+
+                let mut aborted = false;
+                loop {
+                    S::yield_wait();
+                    if let Some((status, count)) = read.get() {
+                        if !expired.get() {
+                            let _ = Alarm::<S, C>::cancel();
+                        }
+                        return read_result(status, count);
+                    }
+                    // Once the timeout expires, abort the read. The kernel
+                    // then delivers the READ upcall with the number of bytes
+                    // received so far.
+                    if expired.get() && !aborted {
+                        aborted = true;
+                        S::command(DRIVER_NUM, command::ABORT, 0, 0)
+                            .to_result::<(), ErrorCode>()?;
+                    }
+                }
+            })
+        })
+    }
+
+    /// Returns a `LineReader`, which reads input one line at a time.
+    pub fn line_reader() -> LineReader<S, C> {
+        LineReader {
+            after_cr: false,
+            console: PhantomData,
+        }
+    }
+
     pub fn writer() -> ConsoleWriter<S> {
         ConsoleWriter {
             syscalls: Default::default(),
@@ -73,6 +187,56 @@ impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> C
     }
 }
 
+// Interprets the arguments of the READ upcall. A read that was aborted still
+// returns the bytes it received.
+fn read_result(status: u32, count: u32) -> Result<usize, ErrorCode> {
+    match ErrorCode::try_from(status) {
+        Err(_) | Ok(ErrorCode::Cancel) => Ok(count as usize),
+        Ok(error) => Err(error),
+    }
+}
+
+/// Reads console input one line at a time. Lines may end with `\n`, `\r`
+/// (terminals commonly send the latter when Enter is pressed) or `\r\n`.
+pub struct LineReader<
+    S: Syscalls,
+    C: platform::allow_ro::Config
+        + platform::allow_rw::Config
+        + platform::subscribe::Config = DefaultConfig,
+> {
+    // Whether the last byte read was a `\r`, in which case a `\n` that follows
+    // it belongs to the same line terminator.
+    after_cr: bool,
+    console: PhantomData<Console<S, C>>,
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > LineReader<S, C>
+{
+    /// Reads a line of input into `buf`, one byte at a time. Stops at the end
+    /// of the line or when `buf` is full. The line terminator is not stored in
+    /// `buf`. Returns the length of the line.
+    pub fn read_line(&mut self, buf: &mut [u8]) -> Result<usize, ErrorCode> {
+        let mut len = 0;
+        while len < buf.len() {
+            let mut byte = [0];
+            Console::<S, C>::read(&mut byte)?;
+            let after_cr = core::mem::replace(&mut self.after_cr, byte[0] == b'\r');
+            match byte[0] {
+                // The rest of a `\r\n` that ended the previous line.
+                b'\n' if after_cr => continue,
+                b'\n' | b'\r' => break,
+                _ => {}
+            }
+            buf[len] = byte[0];
+            len += 1;
+        }
+        Ok(len)
+    }
+}
+
 pub struct ConsoleWriter<S: Syscalls> {
     syscalls: PhantomData<S>,
 }
@@ -93,17 +257,15 @@ mod tests;
 const DRIVER_NUM: u32 = 1;
 
 // Command IDs
-#[allow(unused)]
 mod command {
     pub const DRIVER_CHECK: u32 = 0;
     pub const WRITE: u32 = 1;
     pub const READ: u32 = 2;
+    #[cfg(feature = "libtock_alarm")]
     pub const ABORT: u32 = 3;
 }
 
-#[allow(unused)]
 mod subscribe {
-    use libtock_platform::subscribe;
     pub const WRITE: u32 = 1;
     pub const READ: u32 = 2;
 }
@@ -111,3 +273,7 @@ mod subscribe {
 mod allow_ro {
     pub const WRITE: u32 = 1;
 }
+
+mod allow_rw {
+    pub const READ: u32 = 1;
+}
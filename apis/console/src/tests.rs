@@ -69,3 +69,114 @@ fn failed_print() {
     // The fake driver still receives the command even if a fake error is injected.
     assert_eq!(driver.take_bytes(), b"abcde");
 }
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    driver.add_input(b"abcdef");
+    let mut buf = [0; 4];
+    assert_eq!(Console::read(&mut buf), Ok(4));
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(driver.pending_input(), b"ef");
+}
+
+#[test]
+fn read_line() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    driver.add_input(b"foo\rlonger line\n");
+    let mut reader = Console::line_reader();
+    let mut buf = [0; 8];
+    assert_eq!(reader.read_line(&mut buf), Ok(3));
+    assert_eq!(&buf[..3], b"foo");
+    // A line that does not fit in the buffer is split.
+    assert_eq!(reader.read_line(&mut buf), Ok(8));
+    assert_eq!(&buf, b"longer l");
+    assert_eq!(reader.read_line(&mut buf), Ok(3));
+    assert_eq!(&buf[..3], b"ine");
+    assert_eq!(driver.pending_input(), b"");
+}
+
+#[test]
+fn read_line_crlf() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    // `\r\n` ends a single line, but empty lines are still returned.
+    driver.add_input(b"foo\r\n\r\nbar\n\nbaz\r\r");
+    let mut reader = Console::line_reader();
+    let mut buf = [0; 8];
+    assert_eq!(reader.read_line(&mut buf), Ok(3));
+    assert_eq!(&buf[..3], b"foo");
+    assert_eq!(reader.read_line(&mut buf), Ok(0));
+    assert_eq!(reader.read_line(&mut buf), Ok(3));
+    assert_eq!(&buf[..3], b"bar");
+    assert_eq!(reader.read_line(&mut buf), Ok(0));
+    assert_eq!(reader.read_line(&mut buf), Ok(3));
+    assert_eq!(&buf[..3], b"baz");
+    assert_eq!(reader.read_line(&mut buf), Ok(0));
+    assert_eq!(driver.pending_input(), b"");
+}
+
+#[cfg(feature = "libtock_alarm")]
+#[test]
+fn read_with_timeout() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+    kernel.add_driver(&alarm);
+
+    // The read completes before the timeout, which is cancelled.
+    driver.add_input(b"abc");
+    let mut buf = [0; 3];
+    assert_eq!(
+        Console::read_with_timeout(&mut buf, libtock_alarm::Milliseconds(10)),
+        Ok(3)
+    );
+    assert_eq!(&buf, b"abc");
+    assert_eq!(alarm.expiration(), None);
+    assert_eq!(alarm.now(), 0);
+
+    // The timeout expires before the read completes.
+    driver.add_input(b"de");
+    assert_eq!(
+        Console::read_with_timeout(&mut buf, libtock_alarm::Milliseconds(10)),
+        Ok(2)
+    );
+    assert_eq!(&buf[..2], b"de");
+    assert_eq!(alarm.now(), 10);
+}
+
+#[test]
+fn failed_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::READ,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READ,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ,
+        argument0: 2,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    driver.add_input(b"ab");
+    assert_eq!(Console::read(&mut [0; 2]), Err(ErrorCode::Busy));
+}
@@ -0,0 +1,35 @@
+use crate::{GpioState, InputPin, OutputPin, Pull};
+use embedded_hal::digital::v2;
+use libtock_platform::{ErrorCode, Syscalls};
+
+impl<S: Syscalls> v2::OutputPin for OutputPin<'_, S> {
+    type Error = ErrorCode;
+
+    fn set_low(&mut self) -> Result<(), ErrorCode> {
+        self.clear()
+    }
+
+    fn set_high(&mut self) -> Result<(), ErrorCode> {
+        self.set()
+    }
+}
+
+impl<S: Syscalls> v2::ToggleableOutputPin for OutputPin<'_, S> {
+    type Error = ErrorCode;
+
+    fn toggle(&mut self) -> Result<(), ErrorCode> {
+        OutputPin::toggle(self)
+    }
+}
+
+impl<S: Syscalls, P: Pull> v2::InputPin for InputPin<'_, S, P> {
+    type Error = ErrorCode;
+
+    fn is_high(&self) -> Result<bool, ErrorCode> {
+        Ok(self.read()? == GpioState::High)
+    }
+
+    fn is_low(&self) -> Result<bool, ErrorCode> {
+        Ok(self.read()? == GpioState::Low)
+    }
+}
@@ -1,11 +1,19 @@
 #![no_std]
 
+use core::cell::Cell;
+use core::future::Future;
 use core::marker::PhantomData;
+use core::pin::Pin as FuturePin;
+use core::task::{Context, Poll, Waker};
 
+use libtock_executor::block_on;
 use libtock_platform::{
     share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
+#[cfg(feature = "embedded-hal")]
+mod hal;
+
 /// The Gpio driver
 ///
 /// # Example
@@ -110,6 +118,106 @@ impl<F: Fn(u32, GpioState)> Upcall<OneId<DRIVER_NUM, 0>> for GpioInterruptListen
     }
 }
 
+/// Dispatches the gpio interrupt upcall to per-pin waiters, so several pins can
+/// be waited on at once without each waiter filtering the pin index itself.
+/// `GpioInterrupts` supports pins 0 through `GpioInterrupts::MAX_PINS - 1`.
+///
+/// ```ignore
+/// let interrupts = GpioInterrupts::new();
+/// share::scope::<InterruptHandle<_>, _, _>(|subscribe| {
+///     interrupts.subscribe(subscribe)?;
+///     let first = button_a.wait_for_edge_async(PinInterruptEdge::Falling, &interrupts)?;
+///     let second = button_b.wait_for_edge_async(PinInterruptEdge::Falling, &interrupts)?;
+///     block_on::<TockSyscalls, _>(select(first, second));
+///     Ok(())
+/// })
+/// ```
+pub struct GpioInterrupts {
+    // Bit n is set if pin n has interrupted since it was last waited on.
+    pending: Cell<u32>,
+    // Bit n holds the value pin n had at its most recent interrupt.
+    values: Cell<u32>,
+    wakers: [Cell<Option<Waker>>; GpioInterrupts::MAX_PINS],
+}
+
+impl GpioInterrupts {
+    /// The number of pins that interrupts can be waited on for.
+    pub const MAX_PINS: usize = 32;
+
+    pub const fn new() -> GpioInterrupts {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const NO_WAKER: Cell<Option<Waker>> = Cell::new(None);
+        GpioInterrupts {
+            pending: Cell::new(0),
+            values: Cell::new(0),
+            wakers: [NO_WAKER; GpioInterrupts::MAX_PINS],
+        }
+    }
+
+    /// Subscribes to the gpio interrupt upcall. This replaces any listener
+    /// registered with `Gpio::register_listener`.
+    pub fn subscribe<'share, S: Syscalls>(
+        &'share self,
+        subscribe: Handle<InterruptHandle<'share, S>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, self)
+    }
+
+    // Forgets any interrupt pin has received, so that its next wait completes
+    // on the next interrupt.
+    fn clear(&self, pin: u32) {
+        self.pending.set(self.pending.get() & !(1 << pin));
+    }
+}
+
+impl Default for GpioInterrupts {
+    fn default() -> GpioInterrupts {
+        GpioInterrupts::new()
+    }
+}
+
+impl Upcall<OneId<DRIVER_NUM, 0>> for GpioInterrupts {
+    fn upcall(&self, gpio_index: u32, value: u32, _arg2: u32) {
+        if gpio_index as usize >= GpioInterrupts::MAX_PINS {
+            return;
+        }
+        let bit = 1 << gpio_index;
+        self.pending.set(self.pending.get() | bit);
+        match value {
+            0 => self.values.set(self.values.get() & !bit),
+            _ => self.values.set(self.values.get() | bit),
+        }
+        if let Some(waker) = self.wakers[gpio_index as usize].take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The handle `GpioInterrupts::subscribe` needs to subscribe to the gpio
+/// interrupt upcall.
+pub type InterruptHandle<'share, S> = Subscribe<'share, S, DRIVER_NUM, 0>;
+
+/// A future that completes with a pin's state once the pin interrupts.
+/// Returned by `InputPin::wait_for_edge_async`.
+pub struct EdgeFuture<'share> {
+    interrupts: &'share GpioInterrupts,
+    pin: u32,
+}
+
+impl<'share> Future for EdgeFuture<'share> {
+    type Output = GpioState;
+
+    fn poll(self: FuturePin<&mut Self>, context: &mut Context) -> Poll<GpioState> {
+        let bit = 1 << self.pin;
+        if self.interrupts.pending.get() & bit == 0 {
+            self.interrupts.wakers[self.pin as usize].set(Some(context.waker().clone()));
+            return Poll::Pending;
+        }
+        self.interrupts.clear(self.pin);
+        Poll::Ready((self.interrupts.values.get() & bit).into())
+    }
+}
+
 impl From<u32> for GpioState {
     fn from(original: u32) -> GpioState {
         match original {
@@ -172,6 +280,41 @@ impl<'a, S: Syscalls, P: Pull> InputPin<'a, S, P> {
     pub fn disable_interrupts(&self) -> Result<(), ErrorCode> {
         Gpio::<S>::disable_interrupts(self.pin.pin_number)
     }
+
+    /// Blocks until the pin sees `edge`, and returns its state after the edge.
+    /// `interrupts` must be subscribed, and may be shared with waits on other
+    /// pins: interrupts on those pins are recorded while this blocks.
+    /// Interrupts are disabled on the pin again before this returns.
+    pub fn wait_for_edge(
+        &self,
+        edge: PinInterruptEdge,
+        interrupts: &GpioInterrupts,
+    ) -> Result<GpioState, ErrorCode> {
+        let future = self.wait_for_edge_async(edge, interrupts)?;
+        let state = block_on::<S, _>(future);
+        self.disable_interrupts()?;
+        Ok(state)
+    }
+
+    /// Enables interrupts on the pin and returns a future that completes with
+    /// the pin's state once it sees `edge`. `interrupts` must be subscribed for
+    /// the future to complete. Interrupts stay enabled after the future
+    /// completes; use `disable_interrupts` to turn them off. Fails with
+    /// `ErrorCode::Invalid` if the pin number is too large for
+    /// `GpioInterrupts`.
+    pub fn wait_for_edge_async<'share>(
+        &self,
+        edge: PinInterruptEdge,
+        interrupts: &'share GpioInterrupts,
+    ) -> Result<EdgeFuture<'share>, ErrorCode> {
+        let pin = self.pin.pin_number;
+        if pin as usize >= GpioInterrupts::MAX_PINS {
+            return Err(ErrorCode::Invalid);
+        }
+        interrupts.clear(pin);
+        self.enable_interrupts(edge)?;
+        Ok(EdgeFuture { interrupts, pin })
+    }
 }
 
 impl<S: Syscalls> Drop for OutputPin<'_, S> {
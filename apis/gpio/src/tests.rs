@@ -6,7 +6,10 @@ use libtock_unittest::{
     upcall,
 };
 
-use crate::{GpioInterruptListener, GpioState, PullDown, PullNone, PullUp, DRIVER_NUM};
+use crate::{
+    GpioInterruptListener, GpioInterrupts, GpioState, InterruptHandle, PullDown, PullNone, PullUp,
+    DRIVER_NUM,
+};
 
 type Gpio = super::Gpio<fake::Syscalls>;
 
@@ -295,3 +298,165 @@ fn subscribe() {
     assert_eq!(upcall::schedule(DRIVER_NUM, 0, (0, 0, 0)), Ok(()));
     assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
 }
+
+#[test]
+fn wait_for_edge() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin = Gpio::get_pin(2).unwrap();
+    let input_pin = pin.make_input::<PullNone>().unwrap();
+
+    let interrupts = GpioInterrupts::new();
+    share::scope::<InterruptHandle<fake::Syscalls>, _, _>(|subscribe| {
+        assert_eq!(interrupts.subscribe(subscribe), Ok(()));
+
+        driver.set_value_sync(2, true);
+        assert_eq!(
+            input_pin.wait_for_edge(crate::PinInterruptEdge::Rising, &interrupts),
+            Ok(GpioState::High)
+        );
+        assert_eq!(driver.get_gpio_state(2).unwrap().interrupt_enabled, None);
+
+        driver.set_value_sync(2, false);
+        assert_eq!(
+            input_pin.wait_for_edge(crate::PinInterruptEdge::Either, &interrupts),
+            Ok(GpioState::Low)
+        );
+    });
+}
+
+// Tests that a blocking wait on one pin does not lose an interrupt on a pin
+// that is waited on asynchronously through the same GpioInterrupts.
+#[test]
+fn wait_for_edge_blocking_and_async() {
+    use libtock_executor::block_on;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin_2 = Gpio::get_pin(2).unwrap();
+    let pin_3 = Gpio::get_pin(3).unwrap();
+    let input_2 = pin_2.make_input::<PullNone>().unwrap();
+    let input_3 = pin_3.make_input::<PullNone>().unwrap();
+
+    let interrupts = GpioInterrupts::new();
+    share::scope::<InterruptHandle<fake::Syscalls>, _, _>(|subscribe| {
+        assert_eq!(interrupts.subscribe(subscribe), Ok(()));
+
+        let edge_3 = input_3
+            .wait_for_edge_async(crate::PinInterruptEdge::Rising, &interrupts)
+            .unwrap();
+        assert_eq!(driver.set_value(3, true), Ok(()));
+        driver.set_value_sync(2, true);
+        assert_eq!(
+            input_2.wait_for_edge(crate::PinInterruptEdge::Rising, &interrupts),
+            Ok(GpioState::High)
+        );
+        assert_eq!(driver.get_gpio_state(2).unwrap().interrupt_enabled, None);
+        assert_eq!(
+            driver.get_gpio_state(3).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Rising)
+        );
+        assert_eq!(block_on::<fake::Syscalls, _>(edge_3), GpioState::High);
+    });
+}
+
+// Tests that GpioInterrupts routes each interrupt to the pin it belongs to.
+#[test]
+fn wait_for_edge_async() {
+    use libtock_executor::{block_on, join, select, Either};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<40>::new();
+    kernel.add_driver(&driver);
+
+    let pin_3 = Gpio::get_pin(3).unwrap();
+    let pin_5 = Gpio::get_pin(5).unwrap();
+    let pin_35 = Gpio::get_pin(35).unwrap();
+    let input_3 = pin_3.make_input::<PullNone>().unwrap();
+    let input_5 = pin_5.make_input::<PullUp>().unwrap();
+    let input_35 = pin_35.make_input::<PullNone>().unwrap();
+
+    let interrupts = GpioInterrupts::new();
+    share::scope::<InterruptHandle<fake::Syscalls>, _, _>(|subscribe| {
+        assert_eq!(interrupts.subscribe(subscribe), Ok(()));
+
+        assert!(core::matches!(
+            input_35.wait_for_edge_async(crate::PinInterruptEdge::Either, &interrupts),
+            Err(ErrorCode::Invalid)
+        ));
+
+        let edge_3 = input_3
+            .wait_for_edge_async(crate::PinInterruptEdge::Rising, &interrupts)
+            .unwrap();
+        let edge_5 = input_5
+            .wait_for_edge_async(crate::PinInterruptEdge::Falling, &interrupts)
+            .unwrap();
+        assert_eq!(
+            driver.get_gpio_state(3).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Rising)
+        );
+        assert_eq!(driver.set_value(5, true), Ok(()));
+        assert_eq!(driver.set_value(5, false), Ok(()));
+        assert_eq!(
+            block_on::<fake::Syscalls, _>(select(edge_3, edge_5)),
+            Either::Second(GpioState::Low)
+        );
+
+        // An interrupt on another pin does not complete the wait.
+        let edge_3 = input_3
+            .wait_for_edge_async(crate::PinInterruptEdge::Rising, &interrupts)
+            .unwrap();
+        let edge_5 = input_5
+            .wait_for_edge_async(crate::PinInterruptEdge::Falling, &interrupts)
+            .unwrap();
+        assert_eq!(driver.set_value(5, true), Ok(()));
+        assert_eq!(driver.set_value(5, false), Ok(()));
+        assert_eq!(driver.set_value(3, true), Ok(()));
+        assert_eq!(
+            block_on::<fake::Syscalls, _>(join(edge_3, edge_5)),
+            (GpioState::High, GpioState::Low)
+        );
+
+        // Interrupts received before the wait started are ignored.
+        assert_eq!(driver.set_value(3, false), Ok(()));
+        assert_eq!(driver.set_value(3, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        let edge_3 = input_3
+            .wait_for_edge_async(crate::PinInterruptEdge::Either, &interrupts)
+            .unwrap();
+        assert_eq!(driver.set_value(3, false), Ok(()));
+        assert_eq!(block_on::<fake::Syscalls, _>(edge_3), GpioState::Low);
+    });
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::digital::v2::{InputPin, OutputPin, ToggleableOutputPin};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let mut pin = Gpio::get_pin(0).unwrap();
+    let mut output_pin = pin.make_output().unwrap();
+    assert_eq!(output_pin.set_high(), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output_pin.set_low(), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(ToggleableOutputPin::toggle(&mut output_pin), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+
+    let pin = Gpio::get_pin(1).unwrap();
+    let input_pin = pin.make_input::<PullDown>().unwrap();
+    assert_eq!(driver.set_value(1, true), Ok(()));
+    assert_eq!(input_pin.is_high(), Ok(true));
+    assert_eq!(input_pin.is_low(), Ok(false));
+    assert_eq!(driver.set_value(1, false), Ok(()));
+    assert_eq!(input_pin.is_high(), Ok(false));
+    assert_eq!(input_pin.is_low(), Ok(true));
+}
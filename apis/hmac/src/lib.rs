@@ -0,0 +1,165 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The HMAC driver, which computes message authentication codes using the
+/// hardware HMAC engine.
+///
+/// The algorithm and key are selected with `set_algorithm`. A MAC is then
+/// computed incrementally: data is added with `update`, and `finish` writes the
+/// MAC of everything added since the last `finish` (or `set_algorithm`).
+///
+/// # Example
+/// ```ignore
+/// use libtock2::hmac::{Hmac, HmacAlgorithm};
+///
+/// Hmac::set_algorithm(HmacAlgorithm::HmacSha256, b"secret key").unwrap();
+/// Hmac::update(b"message").unwrap();
+/// let mut mac = [0; 32];
+/// Hmac::finish(&mut mac).unwrap();
+/// ```
+pub struct Hmac<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Hmac<S, C>
+{
+    /// Run a check against the hmac capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Selects the MAC algorithm and key, discarding any data added with
+    /// `update`. The kernel copies `key`, so it does not need to stay shared.
+    pub fn set_algorithm(algorithm: HmacAlgorithm, key: &[u8]) -> Result<(), ErrorCode> {
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>, _, _>(|allow_ro| {
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::KEY }>(allow_ro, key)?;
+            S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as u32, 0).to_result()
+        })
+    }
+
+    /// Adds `data` to the MAC being computed.
+    pub fn update(data: &[u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::UPDATE, 0, 0).to_result()?;
+
+            wait_for_status::<S>(&done)
+        })
+    }
+
+    /// Writes the MAC of the data added since the last `finish` into `mac`,
+    /// and starts a new MAC with the same key. Fails with `ErrorCode::Size` if
+    /// `mac` is shorter than the algorithm's MAC length.
+    pub fn finish(mac: &mut [u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::MAC }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::MAC }>(allow_rw, mac)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result()?;
+
+            wait_for_status::<S>(&done)
+        })
+    }
+}
+
+/// The MAC algorithms supported by the HMAC driver.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HmacAlgorithm {
+    HmacSha256 = 0,
+    HmacSha384 = 1,
+    HmacSha512 = 2,
+}
+
+impl HmacAlgorithm {
+    /// Returns the length of the algorithm's MAC, in bytes.
+    pub fn mac_len(self) -> usize {
+        match self {
+            HmacAlgorithm::HmacSha256 => 32,
+            HmacAlgorithm::HmacSha384 => 48,
+            HmacAlgorithm::HmacSha512 => 64,
+        }
+    }
+}
+
+// Waits for the DONE upcall and returns the status it carries.
+fn wait_for_status<S: Syscalls>(done: &Cell<Option<(u32,)>>) -> Result<(), ErrorCode> {
+    loop {
+        S::yield_wait();
+        if let Some((status,)) = done.get() {
+            return match ErrorCode::try_from(status) {
+                Ok(error) => Err(error),
+                Err(_) => Ok(()),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const UPDATE: u32 = 3;
+    pub const FINISH: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const DATA: u32 = 1;
+}
+
+mod allow_rw {
+    pub const MAC: u32 = 0;
+}
@@ -0,0 +1,103 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Hmac = super::Hmac<fake::Syscalls>;
+
+// Known-answer vectors from RFC 4231 (test cases 1 and 2).
+const MAC_HI_THERE: [u8; 32] = [
+    0xb0, 0x34, 0x4c, 0x61, 0xd8, 0xdb, 0x38, 0x53, 0x5c, 0xa8, 0xaf, 0xce, 0xaf, 0x0b, 0xf1, 0x2b,
+    0x88, 0x1d, 0xc2, 0x00, 0xc9, 0x83, 0x3d, 0xa7, 0x26, 0xe9, 0x37, 0x6c, 0x2e, 0x32, 0xcf, 0xf7,
+];
+const MAC_JEFE: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
+const MAC_JEFE_SHA512: [u8; 64] = [
+    0x16, 0x4b, 0x7a, 0x7b, 0xfc, 0xf8, 0x19, 0xe2, 0xe3, 0x95, 0xfb, 0xe7, 0x3b, 0x56, 0xe0, 0xa3,
+    0x87, 0xbd, 0x64, 0x22, 0x2e, 0x83, 0x1f, 0xd6, 0x10, 0x27, 0x0c, 0xd7, 0xea, 0x25, 0x05, 0x54,
+    0x97, 0x58, 0xbf, 0x75, 0xc0, 0x5a, 0x99, 0x4a, 0x6d, 0x03, 0x4f, 0x65, 0xf8, 0xf0, 0xe6, 0xfd,
+    0xca, 0xea, 0xb1, 0xa3, 0x4d, 0x4a, 0x6b, 0x4b, 0x63, 0x6e, 0x07, 0x0a, 0x38, 0xbc, 0xe7, 0x37,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Hmac::driver_check());
+    assert_eq!(
+        Hmac::set_algorithm(HmacAlgorithm::HmacSha256, b"key"),
+        Err(ErrorCode::NoDevice)
+    );
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    assert!(Hmac::driver_check());
+}
+
+#[test]
+fn hmac_sha256() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut mac = [0; 32];
+    Hmac::set_algorithm(HmacAlgorithm::HmacSha256, &[0x0b; 20]).unwrap();
+    Hmac::update(b"Hi There").unwrap();
+    assert_eq!(Hmac::finish(&mut mac), Ok(()));
+    assert_eq!(mac, MAC_HI_THERE);
+
+    Hmac::set_algorithm(HmacAlgorithm::HmacSha256, b"Jefe").unwrap();
+    Hmac::update(b"what do ya want ").unwrap();
+    Hmac::update(b"for nothing?").unwrap();
+    assert_eq!(Hmac::finish(&mut mac), Ok(()));
+    assert_eq!(mac, MAC_JEFE);
+
+    // finish restarts the MAC with the same key.
+    Hmac::update(b"what do ya want for nothing?").unwrap();
+    assert_eq!(Hmac::finish(&mut mac[..31]), Err(ErrorCode::Size));
+    assert_eq!(Hmac::finish(&mut mac), Ok(()));
+    assert_eq!(mac, MAC_JEFE);
+}
+
+#[test]
+fn hmac_sha512() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut mac = [0; 64];
+    Hmac::set_algorithm(HmacAlgorithm::HmacSha512, b"Jefe").unwrap();
+    Hmac::update(b"what do ya want for nothing?").unwrap();
+    assert_eq!(HmacAlgorithm::HmacSha512.mac_len(), mac.len());
+    assert_eq!(Hmac::finish(&mut mac), Ok(()));
+    assert_eq!(mac, MAC_JEFE_SHA512);
+}
+
+#[test]
+fn failed_set_algorithm() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::KEY,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::SET_ALGORITHM,
+        argument0: HmacAlgorithm::HmacSha384 as u32,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::NoSupport)),
+    });
+
+    assert_eq!(
+        Hmac::set_algorithm(HmacAlgorithm::HmacSha384, b"key"),
+        Err(ErrorCode::NoSupport)
+    );
+}
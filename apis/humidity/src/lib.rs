@@ -0,0 +1,77 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The humidity sensor driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::humidity::Humidity;
+///
+/// let humidity = Humidity::read().unwrap();
+/// ```
+pub struct Humidity<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Humidity<S, C> {
+    /// Run a check against the humidity capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Takes a relative humidity reading, blocking until the sensor returns
+    /// it.
+    pub fn read() -> Result<CentiPercent, ErrorCode> {
+        let reading = Cell::new(Option::<(u32,)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &reading)?;
+
+            S::command(DRIVER_NUM, command::READ_HUMIDITY, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((value,)) = reading.get() {
+                    return Ok(CentiPercent(value));
+                }
+            }
+        })
+    }
+}
+
+/// A relative humidity, in hundredths of a percent.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CentiPercent(pub u32);
+
+impl CentiPercent {
+    /// Returns the relative humidity in whole percent, rounded down.
+    pub fn whole_percent(self) -> u32 {
+        self.0 / 100
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ_HUMIDITY: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
@@ -0,0 +1,77 @@
+use crate::I2cMaster;
+use core::marker::PhantomData;
+use embedded_hal::blocking::i2c;
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The I2C bus, which implements the `embedded_hal::blocking::i2c` traits
+/// using 7-bit addresses.
+pub struct I2cBus<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+> {
+    _i2c_master: PhantomData<I2cMaster<S, C>>,
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > I2cBus<S, C>
+{
+    pub fn new() -> Self {
+        I2cBus {
+            _i2c_master: PhantomData,
+        }
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Default for I2cBus<S, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > i2c::Write for I2cBus<S, C>
+{
+    type Error = ErrorCode;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), ErrorCode> {
+        I2cMaster::<S, C>::write(address, bytes)
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > i2c::Read for I2cBus<S, C>
+{
+    type Error = ErrorCode;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        I2cMaster::<S, C>::read(address, buffer)
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > i2c::WriteRead for I2cBus<S, C>
+{
+    type Error = ErrorCode;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), ErrorCode> {
+        I2cMaster::<S, C>::write_read(address, bytes, buffer)
+    }
+}
@@ -0,0 +1,144 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::I2cBus;
+
+/// The I2C master driver, which gives the process raw access to an I2C bus.
+///
+/// If the addressed device does not acknowledge a transaction, the
+/// transaction fails with `ErrorCode::NoAck`.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::i2c_master::I2cMaster;
+///
+/// // Read the WHO_AM_I register of the device at address 0x1e.
+/// let mut id = [0];
+/// I2cMaster::write_read(0x1e, &[0x0f], &mut id).unwrap();
+/// ```
+pub struct I2cMaster<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > I2cMaster<S, C>
+{
+    /// Run a check against the I2C master capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Writes `data` to the device at `address`.
+    pub fn write(address: u8, data: &[u8]) -> Result<(), ErrorCode> {
+        let len = transfer_len(data)?;
+        Self::transaction(command::WRITE, address, len, data, &mut [])
+    }
+
+    /// Reads `buffer.len()` bytes from the device at `address` into `buffer`.
+    pub fn read(address: u8, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        let len = transfer_len(buffer)?;
+        Self::transaction(command::READ, address, len, &[], buffer)
+    }
+
+    /// Writes `data` to the device at `address`, then reads `buffer.len()`
+    /// bytes from it into `buffer`, without releasing the bus in between
+    /// (i.e. using a repeated start condition).
+    pub fn write_read(address: u8, data: &[u8], buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        let lengths = transfer_len(data)? | transfer_len(buffer)? << 16;
+        Self::transaction(command::WRITE_READ, address, lengths, data, buffer)
+    }
+
+    fn transaction(
+        command_num: u32,
+        address: u8,
+        lengths: u32,
+        data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, data)?;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command_num, address as u32, lengths).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status,)) = done.get() {
+                    return match ErrorCode::try_from(status) {
+                        Ok(error) => Err(error),
+                        Err(_) => Ok(()),
+                    };
+                }
+            }
+        })
+    }
+}
+
+// Returns the length of a transfer buffer, which the kernel limits to 16 bits.
+fn transfer_len(buffer: &[u8]) -> Result<u32, ErrorCode> {
+    match u16::try_from(buffer.len()) {
+        Ok(len) => Ok(len as u32),
+        Err(_) => Err(ErrorCode::Size),
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20003;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const WRITE: u32 = 1;
+    pub const READ: u32 = 2;
+    pub const WRITE_READ: u32 = 3;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
@@ -0,0 +1,180 @@
+extern crate std;
+
+use super::*;
+use core::cell::RefCell;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake::I2cDevice;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+use std::rc::Rc;
+
+type I2cMaster = super::I2cMaster<fake::Syscalls>;
+
+// A device with 8-bit registers, addressed by writing the register number
+// before reading. The register pointer increments after each byte transferred,
+// like on most I2C sensors.
+#[derive(Default)]
+struct RegisterDevice {
+    registers: RefCell<[u8; 16]>,
+    pointer: RefCell<usize>,
+}
+
+impl I2cDevice for RegisterDevice {
+    fn write(&self, data: &[u8]) -> Result<(), ErrorCode> {
+        let (&register, values) = data.split_first().ok_or(ErrorCode::Invalid)?;
+        *self.pointer.borrow_mut() = register as usize;
+        for &value in values {
+            let mut pointer = self.pointer.borrow_mut();
+            self.registers.borrow_mut()[*pointer] = value;
+            *pointer += 1;
+        }
+        Ok(())
+    }
+
+    fn read(&self, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        for byte in buffer {
+            let mut pointer = self.pointer.borrow_mut();
+            *byte = self.registers.borrow()[*pointer];
+            *pointer += 1;
+        }
+        Ok(())
+    }
+}
+
+// A device that acknowledges its address but rejects all data.
+struct NackDevice;
+
+impl I2cDevice for NackDevice {
+    fn write(&self, _data: &[u8]) -> Result<(), ErrorCode> {
+        Err(ErrorCode::NoAck)
+    }
+
+    fn read(&self, _buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        Err(ErrorCode::NoAck)
+    }
+}
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!I2cMaster::driver_check());
+    assert_eq!(I2cMaster::write(0x10, &[1]), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    assert!(I2cMaster::driver_check());
+}
+
+#[test]
+fn transactions() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+    let device = Rc::new(RegisterDevice::default());
+    driver.add_device(0x1e, device.clone());
+
+    I2cMaster::write(0x1e, &[4, 0xaa, 0xbb, 0xcc]).unwrap();
+    assert_eq!(device.registers.borrow()[3..8], [0, 0xaa, 0xbb, 0xcc, 0]);
+
+    let mut buffer = [0; 2];
+    I2cMaster::write(0x1e, &[5]).unwrap();
+    I2cMaster::read(0x1e, &mut buffer).unwrap();
+    assert_eq!(buffer, [0xbb, 0xcc]);
+
+    let mut buffer = [0; 3];
+    I2cMaster::write_read(0x1e, &[3], &mut buffer).unwrap();
+    assert_eq!(buffer, [0, 0xaa, 0xbb]);
+    let last_command = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            libtock_unittest::SyscallLogEntry::Command {
+                command_id,
+                argument0,
+                argument1,
+                ..
+            } => Some((command_id, argument0, argument1)),
+            _ => None,
+        })
+        .last();
+    assert_eq!(last_command, Some((command::WRITE_READ, 0x1e, 1 | 3 << 16)));
+}
+
+#[test]
+fn nack() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+    driver.add_device(0x20, Rc::new(NackDevice));
+
+    // Both a missing device and a device that rejects data produce NoAck.
+    assert_eq!(I2cMaster::write(0x21, &[1]), Err(ErrorCode::NoAck));
+    assert_eq!(I2cMaster::read(0x20, &mut [0]), Err(ErrorCode::NoAck));
+    assert_eq!(
+        I2cMaster::write_read(0x20, &[1], &mut [0]),
+        Err(ErrorCode::NoAck)
+    );
+
+    driver.remove_device(0x20);
+    assert_eq!(I2cMaster::write(0x20, &[1]), Err(ErrorCode::NoAck));
+}
+
+#[test]
+fn failed_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::READ,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::WRITE,
+        argument0: 0x10,
+        argument1: 2,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(I2cMaster::write(0x10, &[1, 2]), Err(ErrorCode::Busy));
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::blocking::i2c::{Read, Write, WriteRead};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+    let device = Rc::new(RegisterDevice::default());
+    driver.add_device(0x1e, device.clone());
+
+    let mut bus = I2cBus::<fake::Syscalls>::new();
+    assert_eq!(bus.write(0x1e, &[2, 0x11, 0x22]), Ok(()));
+    assert_eq!(device.registers.borrow()[2..4], [0x11, 0x22]);
+
+    let mut buffer = [0; 2];
+    assert_eq!(bus.write_read(0x1e, &[2], &mut buffer), Ok(()));
+    assert_eq!(buffer, [0x11, 0x22]);
+    let mut buffer = [0; 1];
+    assert_eq!(bus.read(0x1e, &mut buffer), Ok(()));
+    assert_eq!(buffer, [0]);
+
+    assert_eq!(bus.write(0x1f, &[1]), Err(ErrorCode::NoAck));
+}
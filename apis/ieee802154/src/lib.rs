@@ -0,0 +1,265 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod rx;
+
+pub use rx::{Frame, RxRingBuffer, MAX_FRAME_LEN};
+
+/// The IEEE 802.15.4 radio driver, which sends and receives raw 802.15.4 data
+/// frames.
+///
+/// Configuration changes (PAN ID, addresses, channel and TX power) only take
+/// effect once they are applied with `commit_config`.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ieee802154::{Ieee802154, RxRingBuffer, BROADCAST_ADDRESS};
+///
+/// Ieee802154::set_pan(0xabcd).unwrap();
+/// Ieee802154::set_channel(26).unwrap();
+/// Ieee802154::commit_config().unwrap();
+/// Ieee802154::transmit_frame(BROADCAST_ADDRESS, b"hello", None).unwrap();
+///
+/// let mut ring = RxRingBuffer::<4>::new();
+/// let frame = Ieee802154::receive_frame(&mut ring).unwrap();
+/// ```
+pub struct Ieee802154<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Ieee802154<S, C>
+{
+    /// Run a check against the ieee802154 capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    pub fn set_pan(pan: u16) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_PAN, pan.into(), 0).to_result()
+    }
+
+    pub fn get_pan() -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_PAN, 0, 0)
+            .to_result::<u32, _>()
+            .map(|pan| pan as u16)
+    }
+
+    pub fn set_address_short(address: u16) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_SHORT_ADDRESS, address.into(), 0).to_result()
+    }
+
+    pub fn get_address_short() -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_SHORT_ADDRESS, 0, 0)
+            .to_result::<u32, _>()
+            .map(|address| address as u16)
+    }
+
+    pub fn set_address_long(address: u64) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            command::SET_LONG_ADDRESS,
+            address as u32,
+            (address >> 32) as u32,
+        )
+        .to_result()
+    }
+
+    pub fn get_address_long() -> Result<u64, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_LONG_ADDRESS, 0, 0).to_result()
+    }
+
+    /// Sets the radio channel. IEEE 802.15.4 radios in the 2.4 GHz band use
+    /// channels 11 through 26.
+    pub fn set_channel(channel: u8) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_CHANNEL, channel.into(), 0).to_result()
+    }
+
+    pub fn get_channel() -> Result<u8, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_CHANNEL, 0, 0)
+            .to_result::<u32, _>()
+            .map(|channel| channel as u8)
+    }
+
+    /// Sets the transmit power, in dBm.
+    pub fn set_tx_power(power: i8) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_TX_POWER, power as u32, 0).to_result()
+    }
+
+    /// Returns the transmit power, in dBm.
+    pub fn get_tx_power() -> Result<i8, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_TX_POWER, 0, 0)
+            .to_result::<u32, _>()
+            .map(|power| power as i8)
+    }
+
+    /// Applies the configuration set by the `set_*` functions to the radio.
+    pub fn commit_config() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::COMMIT_CONFIG, 0, 0).to_result()
+    }
+
+    /// Sends a data frame containing `payload` to the device with the given
+    /// short address, which may be `BROADCAST_ADDRESS`. If `security` is
+    /// provided, the kernel secures the frame with the given security level and
+    /// key.
+    ///
+    /// Returns whether the frame was acknowledged. Broadcast frames are never
+    /// acknowledged.
+    pub fn transmit_frame(
+        destination: u16,
+        payload: &[u8],
+        security: Option<Security>,
+    ) -> Result<bool, ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::TX }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::TX }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::TX }>(allow_ro, payload)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::TX }>(subscribe, &done)?;
+
+            S::command(
+                DRIVER_NUM,
+                command::TRANSMIT,
+                destination.into(),
+                security.map_or(0, Security::encode),
+            )
+            .to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status, acked)) = done.get() {
+                    return match ErrorCode::try_from(status) {
+                        Ok(error) => Err(error),
+                        Err(_) => Ok(acked != 0),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Returns the oldest frame in `ring`. If `ring` is empty, shares it with
+    /// the kernel and waits until a frame is received. Frames that are received
+    /// while the process is not waiting in `receive_frame` are dropped by the
+    /// kernel.
+    pub fn receive_frame<const N: usize>(ring: &mut RxRingBuffer<N>) -> Result<&Frame, ErrorCode> {
+        if ring.is_empty() {
+            let received = Cell::new(false);
+            share::scope::<
+                (
+                    AllowRw<_, DRIVER_NUM, { allow_rw::RX }>,
+                    Subscribe<_, DRIVER_NUM, { subscribe::RX }>,
+                ),
+                _,
+                _,
+            >(|handle| {
+                let (allow_rw, subscribe) = handle.split();
+
+                S::allow_rw::<C, DRIVER_NUM, { allow_rw::RX }>(allow_rw, ring.as_mut_bytes())?;
+
+                S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::RX }>(subscribe, &received)?;
+
+                while !received.get() {
+                    S::yield_wait();
+                }
+                Ok(())
+            })?;
+        }
+        // The kernel only signals a reception after writing the frame into the
+        // ring, so the ring cannot be empty here unless the kernel misbehaved.
+        ring.pop().ok_or(ErrorCode::Fail)
+    }
+}
+
+/// The short address that addresses every device on the PAN.
+pub const BROADCAST_ADDRESS: u16 = 0xffff;
+
+/// The security level applied to a transmitted frame: whether its payload is
+/// encrypted, and the length of its message integrity code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SecurityLevel {
+    Mic32 = 1,
+    Mic64 = 2,
+    Mic128 = 3,
+    Enc = 4,
+    EncMic32 = 5,
+    EncMic64 = 6,
+    EncMic128 = 7,
+}
+
+/// The security applied to a transmitted frame. `key_index` selects one of the
+/// keys configured in the kernel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Security {
+    pub level: SecurityLevel,
+    pub key_index: u8,
+}
+
+impl Security {
+    // Encodes the security parameters as the TRANSMIT command's second
+    // argument. 0 (which is not a valid security level) means no security.
+    fn encode(self) -> u32 {
+        self.level as u32 | u32::from(self.key_index) << 8
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SET_SHORT_ADDRESS: u32 = 2;
+    pub const SET_LONG_ADDRESS: u32 = 3;
+    pub const SET_PAN: u32 = 4;
+    pub const SET_CHANNEL: u32 = 5;
+    pub const SET_TX_POWER: u32 = 6;
+    pub const COMMIT_CONFIG: u32 = 7;
+    pub const GET_SHORT_ADDRESS: u32 = 8;
+    pub const GET_LONG_ADDRESS: u32 = 9;
+    pub const GET_PAN: u32 = 10;
+    pub const GET_CHANNEL: u32 = 11;
+    pub const GET_TX_POWER: u32 = 12;
+    pub const TRANSMIT: u32 = 25;
+}
+
+mod subscribe {
+    pub const RX: u32 = 0;
+    pub const TX: u32 = 1;
+}
+
+mod allow_ro {
+    pub const TX: u32 = 0;
+}
+
+mod allow_rw {
+    pub const RX: u32 = 0;
+}
@@ -0,0 +1,106 @@
+/// The largest frame (PSDU) an IEEE 802.15.4 radio can send or receive.
+pub const MAX_FRAME_LEN: usize = 127;
+
+/// A received frame, as written into an `RxRingBuffer` by the kernel.
+///
+/// The kernel strips the frame check sequence and, for secured frames,
+/// authenticates and decrypts the payload before delivering the frame.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Frame {
+    header_len: u8,
+    payload_len: u8,
+    mic_len: u8,
+    body: [u8; MAX_FRAME_LEN],
+}
+
+impl Frame {
+    /// Returns the frame's MAC header.
+    pub fn header(&self) -> &[u8] {
+        self.part(0, self.header_len)
+    }
+
+    /// Returns the frame's (decrypted) payload.
+    pub fn payload(&self) -> &[u8] {
+        self.part(self.header_len.into(), self.payload_len)
+    }
+
+    /// Returns the frame's message integrity code, which is empty for frames
+    /// without authentication.
+    pub fn mic(&self) -> &[u8] {
+        let start = usize::from(self.header_len) + usize::from(self.payload_len);
+        self.part(start, self.mic_len)
+    }
+
+    // Returns len bytes of the body starting at start, truncated to the end of
+    // the body in case the kernel wrote inconsistent lengths.
+    fn part(&self, start: usize, len: u8) -> &[u8] {
+        let start = start.min(MAX_FRAME_LEN);
+        let end = (start + usize::from(len)).min(MAX_FRAME_LEN);
+        &self.body[start..end]
+    }
+}
+
+/// A ring of `N` frame buffers that the kernel writes received frames into.
+///
+/// The kernel writes each frame into the slot at the write index and advances
+/// it; `pop` reads the slot at the read index and advances that. The ring is
+/// empty when both indices are equal, so it holds at most `N - 1` frames. Frames
+/// that arrive while the ring is full are dropped.
+#[repr(C)]
+pub struct RxRingBuffer<const N: usize> {
+    read_index: u8,
+    write_index: u8,
+    frames: [Frame; N],
+}
+
+impl<const N: usize> RxRingBuffer<N> {
+    /// Creates an empty ring buffer. `N` must be between 2 and 256.
+    pub const fn new() -> RxRingBuffer<N> {
+        assert!(N >= 2 && N <= 256, "RxRingBuffer must have 2 to 256 slots");
+        RxRingBuffer {
+            read_index: 0,
+            write_index: 0,
+            frames: [Frame {
+                header_len: 0,
+                payload_len: 0,
+                mic_len: 0,
+                body: [0; MAX_FRAME_LEN],
+            }; N],
+        }
+    }
+
+    /// Returns `true` if the ring contains no received frames.
+    pub fn is_empty(&self) -> bool {
+        self.read_index == self.write_index
+    }
+
+    /// Removes the oldest received frame from the ring and returns it. The
+    /// returned frame stays valid until the ring is shared with the kernel
+    /// again.
+    pub fn pop(&mut self) -> Option<&Frame> {
+        if self.is_empty() {
+            return None;
+        }
+        let index = usize::from(self.read_index) % N;
+        self.read_index = ((index + 1) % N) as u8;
+        Some(&self.frames[index])
+    }
+
+    pub(crate) fn as_mut_bytes(&mut self) -> &mut [u8] {
+        // Safety: RxRingBuffer is repr(C) and consists entirely of u8s, so it
+        // has no padding and every byte pattern is a valid RxRingBuffer.
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self as *mut Self as *mut u8,
+                core::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+impl<const N: usize> Default for RxRingBuffer<N> {
+    fn default() -> RxRingBuffer<N> {
+        RxRingBuffer::new()
+    }
+}
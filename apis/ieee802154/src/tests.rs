@@ -0,0 +1,165 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Ieee802154 = super::Ieee802154<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Ieee802154::driver_check());
+    assert_eq!(Ieee802154::set_pan(1), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert!(Ieee802154::driver_check());
+}
+
+#[test]
+fn config() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ieee802154::set_pan(0xabcd), Ok(()));
+    assert_eq!(Ieee802154::set_address_short(0x1234), Ok(()));
+    assert_eq!(Ieee802154::set_address_long(0x0011223344556677), Ok(()));
+    assert_eq!(Ieee802154::set_channel(11), Ok(()));
+    assert_eq!(Ieee802154::set_channel(10), Err(ErrorCode::Invalid));
+    assert_eq!(Ieee802154::set_tx_power(-17), Ok(()));
+    assert!(!driver.is_config_committed());
+    assert_eq!(Ieee802154::commit_config(), Ok(()));
+    assert!(driver.is_config_committed());
+
+    assert_eq!(Ieee802154::get_pan(), Ok(0xabcd));
+    assert_eq!(Ieee802154::get_address_short(), Ok(0x1234));
+    assert_eq!(Ieee802154::get_address_long(), Ok(0x0011223344556677));
+    assert_eq!(Ieee802154::get_channel(), Ok(11));
+    assert_eq!(Ieee802154::get_tx_power(), Ok(-17));
+}
+
+#[test]
+fn transmit() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(
+        Ieee802154::transmit_frame(0x0002, b"unicast", None),
+        Ok(true)
+    );
+    let security = Security {
+        level: SecurityLevel::EncMic64,
+        key_index: 3,
+    };
+    assert_eq!(
+        Ieee802154::transmit_frame(BROADCAST_ADDRESS, b"broadcast", Some(security)),
+        Ok(false)
+    );
+    assert_eq!(
+        Ieee802154::transmit_frame(0x0002, &[0; MAX_FRAME_LEN], None),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(
+        driver.take_transmitted_frames(),
+        [
+            fake::Ieee802154TxFrame {
+                destination: 0x0002,
+                security: None,
+                payload: b"unicast".to_vec(),
+            },
+            fake::Ieee802154TxFrame {
+                destination: BROADCAST_ADDRESS,
+                security: Some((6, 3)),
+                payload: b"broadcast".to_vec(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn receive() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+    driver.inject_frame(&[0x41, 0x88, 7], b"first");
+    driver.inject_frame(&[0x41, 0x88, 8], b"second");
+
+    let mut ring = RxRingBuffer::<4>::new();
+    let frame = Ieee802154::receive_frame(&mut ring).unwrap();
+    assert_eq!(frame.header(), [0x41, 0x88, 7]);
+    assert_eq!(frame.payload(), b"first");
+    assert_eq!(frame.mic(), []);
+    assert!(ring.is_empty());
+    let frame = Ieee802154::receive_frame(&mut ring).unwrap();
+    assert_eq!(frame.payload(), b"second");
+    assert_eq!(driver.pending_rx_frames(), 0);
+}
+
+#[test]
+fn loopback() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+    driver.set_loopback(true);
+    Ieee802154::set_pan(0xabcd).unwrap();
+    Ieee802154::set_address_short(0x0001).unwrap();
+    Ieee802154::commit_config().unwrap();
+
+    let security = Security {
+        level: SecurityLevel::Mic32,
+        key_index: 1,
+    };
+    Ieee802154::transmit_frame(0x0002, b"ping", Some(security)).unwrap();
+
+    let mut ring = RxRingBuffer::<2>::new();
+    let frame = Ieee802154::receive_frame(&mut ring).unwrap();
+    assert_eq!(
+        frame.header(),
+        [
+            0x49, 0x88, 0, // Frame control, sequence number
+            0xcd, 0xab, 0x02, 0x00, 0x01, 0x00, // PAN and addresses
+            0x09, 0, 0, 0, 0, 1, // Auxiliary security header
+        ]
+    );
+    assert_eq!(frame.payload(), b"ping");
+    assert_eq!(frame.mic(), [0; 4]);
+}
+
+#[test]
+fn failed_transmit() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::TX,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::TX,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::TRANSMIT,
+        argument0: 0x0002,
+        argument1: 0x0105,
+        override_return: Some(command_return::failure(ErrorCode::Off)),
+    });
+
+    let security = Security {
+        level: SecurityLevel::EncMic32,
+        key_index: 1,
+    };
+    assert_eq!(
+        Ieee802154::transmit_frame(0x0002, b"data", Some(security)),
+        Err(ErrorCode::Off)
+    );
+}
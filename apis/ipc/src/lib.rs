@@ -0,0 +1,212 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::{OneId, Subscribe};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+/// The inter-process communication (IPC) driver.
+///
+/// Every process can act as a service, which other processes (its clients)
+/// find by its package name. A client shares a buffer with a service and
+/// notifies it; the service reads and writes the buffer, then notifies the
+/// client back. Processes are identified by IDs assigned by the kernel.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ipc::Ipc;
+///
+/// // Client: send a request to the "org.tockos.sensor" service and wait for
+/// // its response, which the service writes into `buffer`.
+/// let service = Ipc::discover(b"org.tockos.sensor").unwrap();
+/// let mut buffer = [0; 32];
+/// Ipc::call(service, &mut buffer).unwrap();
+/// ```
+pub struct Ipc<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Ipc<S, C>
+{
+    /// Run a check against the IPC capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the ID of the service with the given package name. Fails with
+    /// `ErrorCode::NoDevice` if there is no such service.
+    pub fn discover(package_name: &[u8]) -> Result<u32, ErrorCode> {
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::SEARCH }>, _, _>(|allow_ro| {
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::SEARCH }>(allow_ro, package_name)?;
+
+            S::command(DRIVER_NUM, command::DISCOVER, 0, 0).to_result()
+        })
+    }
+
+    /// Registers this process as a service, with a listener that is called
+    /// each time a client notifies it.
+    ///
+    /// There can be only one listener registered at a time. Each time this
+    /// function is used, it will replace the previously registered listener.
+    pub fn register_service_listener<'share, F: Fn(IpcNotification)>(
+        listener: &'share IpcListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::SERVICE }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SERVICE }>(subscribe, listener)
+    }
+
+    /// Unregisters the service listener, after which client notifications are
+    /// ignored.
+    ///
+    /// This function may be used even if there was no previously registered
+    /// listener.
+    pub fn unregister_service_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::SERVICE)
+    }
+
+    /// Shares `buffer` with the service whose ID is `SERVICE`. The service can
+    /// access the buffer until the share handle is dropped. Use `call` if the
+    /// service's ID is only known at runtime.
+    pub fn share<'share, const SERVICE: u32>(
+        allow_rw: Handle<AllowRw<'share, S, DRIVER_NUM, SERVICE>>,
+        buffer: &'share mut [u8],
+    ) -> Result<(), ErrorCode> {
+        S::allow_rw::<C, DRIVER_NUM, SERVICE>(allow_rw, buffer)
+    }
+
+    /// Notifies the service `service`, which calls its service listener.
+    pub fn notify_service(service: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::NOTIFY_SERVICE, service, 0).to_result()
+    }
+
+    /// Notifies the client `client`, typically in response to a notification
+    /// the client sent.
+    pub fn notify_client(client: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::NOTIFY_CLIENT, client, 0).to_result()
+    }
+
+    /// Shares `buffer` with the service `service`, notifies the service, and
+    /// waits for the service to notify this process back. The service's
+    /// response is left in `buffer`. Supports services with IDs below
+    /// `MAX_SERVICES`; fails with `ErrorCode::Invalid` for other IDs.
+    pub fn call(service: u32, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        // The allow and subscribe numbers used to communicate with a service
+        // depend on its ID, so they are selected here.
+        match service {
+            0 => Self::call_service::<0, 1>(buffer),
+            1 => Self::call_service::<1, 2>(buffer),
+            2 => Self::call_service::<2, 3>(buffer),
+            3 => Self::call_service::<3, 4>(buffer),
+            4 => Self::call_service::<4, 5>(buffer),
+            5 => Self::call_service::<5, 6>(buffer),
+            6 => Self::call_service::<6, 7>(buffer),
+            7 => Self::call_service::<7, 8>(buffer),
+            _ => Err(ErrorCode::Invalid),
+        }
+    }
+
+    // Implements call for the service SERVICE. Notifications from SERVICE
+    // arrive on subscribe number SERVICE + 1, which is passed as UPCALL.
+    fn call_service<const SERVICE: u32, const UPCALL: u32>(
+        buffer: &mut [u8],
+    ) -> Result<(), ErrorCode> {
+        let notified = Cell::new(false);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, SERVICE>,
+                Subscribe<_, DRIVER_NUM, UPCALL>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+
+            S::allow_rw::<C, DRIVER_NUM, SERVICE>(allow_rw, buffer)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, UPCALL>(subscribe, &notified)?;
+
+            Self::notify_service(SERVICE)?;
+
+            while !notified.get() {
+                S::yield_wait();
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The number of services `Ipc::call` can communicate with.
+pub const MAX_SERVICES: u32 = 8;
+
+/// A notification a service receives from a client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IpcNotification {
+    /// The ID of the client, which can be passed to `Ipc::notify_client`.
+    pub client: u32,
+    /// The length of the buffer the client shared with this service.
+    pub buffer_len: u32,
+    /// The address of the buffer the client shared with this service. The
+    /// kernel gives the service access to the buffer until the client stops
+    /// sharing it.
+    pub buffer_address: u32,
+}
+
+/// A wrapper around a closure to be registered and called when a client
+/// notifies this process' service.
+///
+/// ```ignore
+/// let listener = IpcListener(|notification| {
+///     // handle the request, then respond
+///     Ipc::notify_client(notification.client).unwrap();
+/// });
+/// ```
+pub struct IpcListener<F: Fn(IpcNotification)>(pub F);
+
+impl<F: Fn(IpcNotification)> Upcall<OneId<DRIVER_NUM, { subscribe::SERVICE }>> for IpcListener<F> {
+    fn upcall(&self, client: u32, buffer_len: u32, buffer_address: u32) {
+        self.0(IpcNotification {
+            client,
+            buffer_len,
+            buffer_address,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const DISCOVER: u32 = 1;
+    pub const NOTIFY_SERVICE: u32 = 2;
+    pub const NOTIFY_CLIENT: u32 = 3;
+}
+
+// Clients receive notifications from a service on subscribe number (service ID
+// + 1).
+mod subscribe {
+    pub const SERVICE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const SEARCH: u32 = 0;
+}
@@ -0,0 +1,140 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Ipc = super::Ipc<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Ipc::driver_check());
+    assert_eq!(
+        Ipc::discover(b"org.tockos.sensor"),
+        Err(ErrorCode::NoDevice)
+    );
+    assert_eq!(Ipc::notify_client(1), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+
+    assert!(Ipc::driver_check());
+}
+
+#[test]
+fn discover() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+    let logger = driver.add_process("org.tockos.logger");
+    let sensor = driver.add_process("org.tockos.sensor");
+
+    assert_eq!(Ipc::discover(b"org.tockos.sensor"), Ok(sensor.id()));
+    assert_eq!(Ipc::discover(b"org.tockos.logger"), Ok(logger.id()));
+    assert_eq!(Ipc::discover(b"org.tockos"), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn call() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+    let sensor = driver.add_process("org.tockos.sensor");
+    sensor.set_handler(|buffer| {
+        // Responds to a request for a reading by writing it into the buffer.
+        if buffer[0] == b'?' {
+            buffer[..4].copy_from_slice(&2150u32.to_le_bytes());
+        }
+    });
+
+    let service = Ipc::discover(b"org.tockos.sensor").unwrap();
+    let mut buffer = [0; 8];
+    buffer[0] = b'?';
+    assert_eq!(Ipc::call(service, &mut buffer), Ok(()));
+    assert_eq!(buffer[..4], 2150u32.to_le_bytes());
+    assert_eq!(sensor.take_notifications(), 1);
+    // The buffer is no longer shared once call returns.
+    assert_eq!(sensor.shared_buffer(), []);
+
+    assert_eq!(
+        Ipc::call(MAX_SERVICES, &mut buffer),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(Ipc::call(5, &mut buffer), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn share_and_notify() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+    let sensor = driver.add_process("org.tockos.sensor");
+
+    let mut buffer = *b"ready";
+    share::scope(|allow_rw| {
+        Ipc::share::<1>(allow_rw, &mut buffer).unwrap();
+        assert_eq!(Ipc::notify_service(sensor.id()), Ok(()));
+        assert_eq!(sensor.shared_buffer(), b"ready");
+    });
+    assert_eq!(sensor.take_notifications(), 1);
+    assert_eq!(Ipc::notify_service(2), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn service() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+    let client = driver.add_process("org.tockos.client");
+
+    let notifications = core::cell::Cell::new(0);
+    let listener = IpcListener(|notification: IpcNotification| {
+        assert_eq!(notification.client, 1);
+        notifications.set(notifications.get() + 1);
+        Ipc::notify_client(notification.client).unwrap();
+    });
+    share::scope(|subscribe| {
+        Ipc::register_service_listener(&listener, subscribe).unwrap();
+        client.notify_service();
+        fake::Syscalls::yield_wait();
+        assert_eq!(notifications.get(), 1);
+        assert_eq!(client.take_notifications(), 1);
+
+        Ipc::unregister_service_listener();
+        client.notify_service();
+        assert_eq!(
+            fake::Syscalls::yield_no_wait(),
+            libtock_platform::YieldNoWaitReturn::NoUpcall
+        );
+    });
+}
+
+#[test]
+fn failed_call() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new();
+    kernel.add_driver(&driver);
+    let _sensor = driver.add_process("org.tockos.sensor");
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: 1,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: 2,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::NOTIFY_SERVICE,
+        argument0: 1,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Ipc::call(1, &mut [0; 4]), Err(ErrorCode::Busy));
+}
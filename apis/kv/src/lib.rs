@@ -0,0 +1,148 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The key-value storage driver, which gives the process access to persistent
+/// storage shared through the kernel's key-value store.
+///
+/// Operations on a key that does not exist (and `add` on a key that already
+/// exists) fail with `ErrorCode::NoSupport`. Operations fail with
+/// `ErrorCode::NoMem` if the store is full and `ErrorCode::Size` if a key or
+/// value is too large.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::kv::KeyValue;
+///
+/// KeyValue::set(b"boot_count", &[1]).unwrap();
+/// let mut value = [0; 4];
+/// let len = KeyValue::get(b"boot_count", &mut value).unwrap();
+/// ```
+pub struct KeyValue<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > KeyValue<S, C>
+{
+    /// Run a check against the kv capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Reads the value stored under `key` into `value` and returns the length
+    /// of the value. Fails with `ErrorCode::Size` if `value` is too small to
+    /// hold it.
+    pub fn get(key: &[u8], value: &mut [u8]) -> Result<usize, ErrorCode> {
+        Self::operation(command::GET, key, &[], value)
+    }
+
+    /// Stores `value` under `key`, replacing any existing value.
+    pub fn set(key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        Self::operation(command::SET, key, value, &mut []).map(|_| ())
+    }
+
+    /// Stores `value` under `key`, which must not already exist.
+    pub fn add(key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        Self::operation(command::ADD, key, value, &mut []).map(|_| ())
+    }
+
+    /// Replaces the value stored under `key`, which must already exist.
+    pub fn update(key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        Self::operation(command::UPDATE, key, value, &mut []).map(|_| ())
+    }
+
+    /// Deletes `key` and its value.
+    pub fn delete(key: &[u8]) -> Result<(), ErrorCode> {
+        Self::operation(command::DELETE, key, &[], &mut []).map(|_| ())
+    }
+
+    // Performs an operation and returns the length of the value reported by
+    // the kernel.
+    fn operation(
+        command_num: u32,
+        key: &[u8],
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::INPUT }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::OUTPUT }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_input, allow_output, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::INPUT }>(allow_input, input)?;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::OUTPUT }>(allow_output, output)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command_num, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status, len)) = done.get() {
+                    return match ErrorCode::try_from(status) {
+                        Ok(error) => Err(error),
+                        Err(_) => Ok(len as usize),
+                    };
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50003;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const GET: u32 = 1;
+    pub const SET: u32 = 2;
+    pub const DELETE: u32 = 3;
+    pub const ADD: u32 = 4;
+    pub const UPDATE: u32 = 5;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const INPUT: u32 = 1;
+}
+
+mod allow_rw {
+    pub const OUTPUT: u32 = 0;
+}
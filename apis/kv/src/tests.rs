@@ -0,0 +1,124 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type KeyValue = super::KeyValue<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!KeyValue::driver_check());
+    assert_eq!(KeyValue::delete(b"key"), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KeyValue::new();
+    kernel.add_driver(&driver);
+
+    assert!(KeyValue::driver_check());
+}
+
+#[test]
+fn operations() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KeyValue::new();
+    kernel.add_driver(&driver);
+    driver.insert(b"existing", b"value");
+
+    let mut value = [0; 8];
+    assert_eq!(KeyValue::get(b"existing", &mut value), Ok(5));
+    assert_eq!(value[..5], *b"value");
+
+    assert_eq!(KeyValue::add(b"new", b"1"), Ok(()));
+    assert_eq!(KeyValue::update(b"new", b"22"), Ok(()));
+    assert_eq!(driver.value(b"new").as_deref(), Some(&b"22"[..]));
+    assert_eq!(KeyValue::set(b"new", b"333"), Ok(()));
+    assert_eq!(KeyValue::set(b"other", b"4"), Ok(()));
+    assert_eq!(KeyValue::get(b"new", &mut value), Ok(3));
+    assert_eq!(value[..3], *b"333");
+
+    assert_eq!(KeyValue::delete(b"new"), Ok(()));
+    assert_eq!(driver.value(b"new"), None);
+}
+
+#[test]
+fn missing_keys() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KeyValue::new();
+    kernel.add_driver(&driver);
+    driver.insert(b"existing", b"value");
+
+    assert_eq!(
+        KeyValue::get(b"missing", &mut [0; 4]),
+        Err(ErrorCode::NoSupport)
+    );
+    assert_eq!(
+        KeyValue::update(b"missing", b"1"),
+        Err(ErrorCode::NoSupport)
+    );
+    assert_eq!(KeyValue::delete(b"missing"), Err(ErrorCode::NoSupport));
+    assert_eq!(KeyValue::add(b"existing", b"1"), Err(ErrorCode::NoSupport));
+    assert_eq!(driver.value(b"existing").as_deref(), Some(&b"value"[..]));
+}
+
+#[test]
+fn size_limits() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KeyValue::new();
+    kernel.add_driver(&driver);
+    driver.set_max_key_len(4);
+    driver.set_max_value_len(4);
+    driver.set_capacity(11);
+
+    assert_eq!(KeyValue::set(b"long key", b"1"), Err(ErrorCode::Size));
+    assert_eq!(KeyValue::set(b"key", b"long value"), Err(ErrorCode::Size));
+    assert_eq!(KeyValue::set(b"a", b"1234"), Ok(()));
+    assert_eq!(KeyValue::set(b"b", b"1234"), Ok(()));
+    assert_eq!(KeyValue::set(b"c", b"1"), Err(ErrorCode::NoMem));
+    assert_eq!(KeyValue::delete(b"a"), Ok(()));
+    assert_eq!(KeyValue::set(b"c", b"1"), Ok(()));
+
+    // A buffer that is too small for the value produces Size, but is filled
+    // with as much of the value as fits.
+    let mut value = [0; 2];
+    assert_eq!(KeyValue::get(b"b", &mut value), Err(ErrorCode::Size));
+    assert_eq!(value, *b"12");
+}
+
+#[test]
+fn failed_get() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KeyValue::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::KEY,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::INPUT,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::OUTPUT,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::GET,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(KeyValue::get(b"key", &mut [0; 4]), Err(ErrorCode::Busy));
+}
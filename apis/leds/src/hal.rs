@@ -0,0 +1,45 @@
+use crate::Leds;
+use core::marker::PhantomData;
+use embedded_hal::digital::v2;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// A single LED, which implements the `embedded_hal::digital::v2` output pin
+/// traits. Setting the pin high turns the LED on.
+pub struct Led<S: Syscalls> {
+    led: u32,
+    _leds: PhantomData<Leds<S>>,
+}
+
+impl<S: Syscalls> Led<S> {
+    /// Returns the LED with the given index. Fails with `ErrorCode::Invalid` if
+    /// there is no such LED.
+    pub fn new(led: u32) -> Result<Self, ErrorCode> {
+        if led >= Leds::<S>::count()? {
+            return Err(ErrorCode::Invalid);
+        }
+        Ok(Led {
+            led,
+            _leds: PhantomData,
+        })
+    }
+}
+
+impl<S: Syscalls> v2::OutputPin for Led<S> {
+    type Error = ErrorCode;
+
+    fn set_low(&mut self) -> Result<(), ErrorCode> {
+        Leds::<S>::off(self.led)
+    }
+
+    fn set_high(&mut self) -> Result<(), ErrorCode> {
+        Leds::<S>::on(self.led)
+    }
+}
+
+impl<S: Syscalls> v2::ToggleableOutputPin for Led<S> {
+    type Error = ErrorCode;
+
+    fn toggle(&mut self) -> Result<(), ErrorCode> {
+        Leds::<S>::toggle(self.led)
+    }
+}
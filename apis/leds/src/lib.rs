@@ -2,6 +2,11 @@
 
 use libtock_platform::{ErrorCode, Syscalls};
 
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::Led;
+
 /// The LEDs driver
 ///
 /// # Example
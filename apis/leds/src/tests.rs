@@ -84,3 +84,26 @@ fn no_led() {
         assert_eq!(driver.get_led(led), Some(false));
     }
 }
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::digital::v2::{OutputPin, ToggleableOutputPin};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert!(core::matches!(
+        super::Led::<fake::Syscalls>::new(4),
+        Err(ErrorCode::Invalid)
+    ));
+    let mut led = super::Led::<fake::Syscalls>::new(2).unwrap();
+    assert_eq!(led.set_high(), Ok(()));
+    assert_eq!(driver.get_led(2), Some(true));
+    assert_eq!(led.set_low(), Ok(()));
+    assert_eq!(driver.get_led(2), Some(false));
+    assert_eq!(led.toggle(), Ok(()));
+    assert_eq!(driver.get_led(2), Some(true));
+    assert_eq!(driver.get_led(1), Some(false));
+}
@@ -0,0 +1,128 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The nine degrees of freedom driver, which provides access to an
+/// accelerometer, a magnetometer, and a gyroscope.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::ninedof::Ninedof;
+///
+/// let acceleration = Ninedof::read_accelerometer().unwrap();
+/// ```
+pub struct Ninedof<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Ninedof<S, C> {
+    /// Run a check against the ninedof capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Reads the accelerometer, blocking until the sensor returns a sample.
+    /// The acceleration is reported in milli-g.
+    pub fn read_accelerometer() -> Result<NinedofData, ErrorCode> {
+        Self::read(command::READ_ACCELEROMETER)
+    }
+
+    /// Reads the accelerometer and returns the magnitude of the acceleration
+    /// vector, in milli-g.
+    pub fn read_accelerometer_magnitude() -> Result<u32, ErrorCode> {
+        Self::read_accelerometer().map(NinedofData::magnitude)
+    }
+
+    /// Reads the magnetometer, blocking until the sensor returns a sample.
+    pub fn read_magnetometer() -> Result<NinedofData, ErrorCode> {
+        Self::read(command::READ_MAGNETOMETER)
+    }
+
+    /// Reads the gyroscope, blocking until the sensor returns a sample.
+    pub fn read_gyroscope() -> Result<NinedofData, ErrorCode> {
+        Self::read(command::READ_GYROSCOPE)
+    }
+
+    fn read(command_num: u32) -> Result<NinedofData, ErrorCode> {
+        let sample = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &sample)?;
+
+            S::command(DRIVER_NUM, command_num, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((x, y, z)) = sample.get() {
+                    return Ok(NinedofData {
+                        x: x as i32,
+                        y: y as i32,
+                        z: z as i32,
+                    });
+                }
+            }
+        })
+    }
+}
+
+/// A three-axis sample from one of the ninedof sensors. The units depend on
+/// the sensor the sample was read from.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NinedofData {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl NinedofData {
+    /// Returns the length of the (x, y, z) vector, rounded down.
+    pub fn magnitude(self) -> u32 {
+        let square = |v: i32| (v as i64 * v as i64) as u64;
+        isqrt(square(self.x) + square(self.y) + square(self.z))
+    }
+}
+
+// Computes floor(sqrt(n)) using Newton's method. The result always fits in a
+// u32, as n is at most 3 * 2^62.
+fn isqrt(n: u64) -> u32 {
+    if n < 2 {
+        return n as u32;
+    }
+    // The initial guess must be at least sqrt(n) for the iteration to converge
+    // from above.
+    let mut x = 1u64 << ((64 - n.leading_zeros() + 1) / 2);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x as u32;
+        }
+        x = next;
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60004;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ_ACCELEROMETER: u32 = 1;
+    pub const READ_MAGNETOMETER: u32 = 100;
+    pub const READ_GYROSCOPE: u32 = 200;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
@@ -0,0 +1,87 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Ninedof = super::Ninedof<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Ninedof::driver_check());
+    assert_eq!(Ninedof::read_accelerometer(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ninedof::new();
+    kernel.add_driver(&driver);
+
+    assert!(Ninedof::driver_check());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ninedof::new();
+    kernel.add_driver(&driver);
+
+    driver.add_accelerometer_sample((12, -25, 1003));
+    driver.add_accelerometer_sample((0, 0, -980));
+    driver.add_magnetometer_sample((-300, 40, 5));
+    driver.add_gyroscope_sample((1, 2, -3));
+
+    assert_eq!(
+        Ninedof::read_accelerometer(),
+        Ok(NinedofData {
+            x: 12,
+            y: -25,
+            z: 1003
+        })
+    );
+    assert_eq!(
+        Ninedof::read_gyroscope(),
+        Ok(NinedofData { x: 1, y: 2, z: -3 })
+    );
+    assert_eq!(
+        Ninedof::read_magnetometer(),
+        Ok(NinedofData {
+            x: -300,
+            y: 40,
+            z: 5
+        })
+    );
+    assert_eq!(Ninedof::read_accelerometer_magnitude(), Ok(980));
+}
+
+#[test]
+fn magnitude() {
+    let data = |x, y, z| NinedofData { x, y, z };
+    assert_eq!(data(0, 0, 0).magnitude(), 0);
+    assert_eq!(data(3, -4, 0).magnitude(), 5);
+    assert_eq!(data(1, 1, 1).magnitude(), 1);
+    assert_eq!(data(2, 3, 6).magnitude(), 7);
+    assert_eq!(data(10, 10, 10).magnitude(), 17);
+    assert_eq!(data(i32::MIN, i32::MIN, i32::MIN).magnitude(), 3719550786);
+}
+
+#[test]
+fn failed_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ninedof::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READING,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ_GYROSCOPE,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Ninedof::read_gyroscope(), Err(ErrorCode::Busy));
+}
@@ -0,0 +1,133 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The nonvolatile storage driver, which gives the process a region of storage
+/// that persists across reboots.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::nonvolatile_storage::NonvolatileStorage;
+///
+/// let mut calibration = [0; 16];
+/// NonvolatileStorage::read(0, &mut calibration).unwrap();
+/// ```
+pub struct NonvolatileStorage<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > NonvolatileStorage<S, C>
+{
+    /// Run a check against the nonvolatile storage capsule to ensure it is
+    /// present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the number of bytes of storage available to the process.
+    pub fn size() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::SIZE, 0, 0).to_result()
+    }
+
+    /// Reads `buf.len()` bytes starting at `offset` into `buf`.
+    pub fn read(offset: u32, buf: &mut [u8]) -> Result<(), ErrorCode> {
+        let called = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let len = buf.len() as u32;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buf)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READ }>(subscribe, &called)?;
+
+            S::command(DRIVER_NUM, command::READ, offset, len).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_,)) = called.get() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    /// Writes `buf` to storage, starting at `offset`.
+    pub fn write(offset: u32, buf: &[u8]) -> Result<(), ErrorCode> {
+        let called = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::WRITE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, buf)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::WRITE }>(subscribe, &called)?;
+
+            S::command(DRIVER_NUM, command::WRITE, offset, buf.len() as u32).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_,)) = called.get() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SIZE: u32 = 1;
+    pub const READ: u32 = 2;
+    pub const WRITE: u32 = 3;
+}
+
+mod subscribe {
+    pub const READ: u32 = 0;
+    pub const WRITE: u32 = 1;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
@@ -0,0 +1,95 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type NonvolatileStorage = super::NonvolatileStorage<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!NonvolatileStorage::driver_check());
+    assert_eq!(NonvolatileStorage::size(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(64);
+    kernel.add_driver(&driver);
+
+    assert!(NonvolatileStorage::driver_check());
+    assert_eq!(NonvolatileStorage::size(), Ok(64));
+}
+
+#[test]
+fn read_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+    driver.load_image(&[1, 2, 3, 4]);
+
+    let mut buf = [0; 6];
+    NonvolatileStorage::read(2, &mut buf).unwrap();
+    // Storage that was not part of the image reads as erased flash.
+    assert_eq!(buf, [3, 4, 0xff, 0xff, 0xff, 0xff]);
+
+    NonvolatileStorage::write(12, &[9, 8, 7, 6]).unwrap();
+    NonvolatileStorage::read(11, &mut buf[..5]).unwrap();
+    assert_eq!(buf[..5], [0xff, 9, 8, 7, 6]);
+    assert_eq!(driver.contents()[12..], [9, 8, 7, 6]);
+
+    // Accesses past the end of storage are rejected.
+    assert_eq!(
+        NonvolatileStorage::write(13, &[0; 4]),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        NonvolatileStorage::read(u32::MAX, &mut buf),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn write_failure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+
+    driver.set_write_error(Some(ErrorCode::Fail));
+    assert_eq!(NonvolatileStorage::write(0, &[1]), Err(ErrorCode::Fail));
+    assert_eq!(driver.contents()[0], 0xff);
+
+    // Reads still work while writes are failing.
+    let mut buf = [0; 1];
+    assert_eq!(NonvolatileStorage::read(0, &mut buf), Ok(()));
+
+    driver.set_write_error(None);
+    assert_eq!(NonvolatileStorage::write(0, &[1]), Ok(()));
+    assert_eq!(driver.contents()[0], 1);
+}
+
+#[test]
+fn failed_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::WRITE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::WRITE,
+        argument0: 4,
+        argument1: 2,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(NonvolatileStorage::write(4, &[1, 2]), Err(ErrorCode::Busy));
+}
@@ -0,0 +1,87 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The proximity sensor driver.
+///
+/// Proximity is reported on a scale from 0 to 255, where higher values mean
+/// an object is closer to the sensor.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::proximity::Proximity;
+///
+/// let proximity = Proximity::read().unwrap();
+///
+/// // Waits until an object comes close to the sensor.
+/// let proximity = Proximity::read_on_interrupt(0, 200).unwrap();
+/// ```
+pub struct Proximity<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Proximity<S, C> {
+    /// Run a check against the proximity capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Measures the proximity of the nearest object, blocking until the sensor
+    /// returns it.
+    pub fn read() -> Result<u8, ErrorCode> {
+        Self::read_with_command(command::READ, 0, 0)
+    }
+
+    /// Blocks until the proximity falls below `low` or rises above `high`, and
+    /// returns it.
+    pub fn read_on_interrupt(low: u8, high: u8) -> Result<u8, ErrorCode> {
+        Self::read_with_command(command::READ_ON_INTERRUPT, low.into(), high.into())
+    }
+
+    fn read_with_command(
+        command_num: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<u8, ErrorCode> {
+        let reading = Cell::new(Option::<(u32,)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &reading)?;
+
+            S::command(DRIVER_NUM, command_num, argument0, argument1).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((value,)) = reading.get() {
+                    return Ok(value as u8);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60005;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ: u32 = 1;
+    pub const READ_ON_INTERRUPT: u32 = 2;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
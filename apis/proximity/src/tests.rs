@@ -0,0 +1,74 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Proximity = super::Proximity<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Proximity::driver_check());
+    assert_eq!(Proximity::read(), Err(ErrorCode::NoMem));
+    assert_eq!(Proximity::read_on_interrupt(0, 255), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    assert!(Proximity::driver_check());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(120);
+    assert_eq!(Proximity::read(), Ok(120));
+    assert!(!driver.is_busy());
+
+    driver.set_error_sync(ErrorCode::Fail);
+    assert_eq!(Proximity::read(), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn read_on_interrupt() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(230);
+    assert_eq!(Proximity::read_on_interrupt(10, 200), Ok(230));
+    assert_eq!(driver.thresholds(), None);
+
+    driver.set_value_sync(3);
+    assert_eq!(Proximity::read_on_interrupt(10, 200), Ok(3));
+
+    driver.set_error_sync(ErrorCode::Busy);
+    assert_eq!(Proximity::read_on_interrupt(10, 200), Err(ErrorCode::Busy));
+}
+
+#[test]
+fn failed_read_on_interrupt() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READING,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ_ON_INTERRUPT,
+        argument0: 10,
+        argument1: 200,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Proximity::read_on_interrupt(10, 200), Err(ErrorCode::Busy));
+}
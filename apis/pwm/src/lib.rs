@@ -0,0 +1,80 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The PWM driver, which generates pulse-width modulated signals on PWM pins.
+///
+/// Duty cycles are given in hundredths of a percent, so `5000` is a 50% duty
+/// cycle and `MAX_DUTY_CYCLE` keeps the pin high.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::pwm::Pwm;
+///
+/// // Drives pin 0 at 1 kHz, high a quarter of the time.
+/// Pwm::start(0, 1000, 2500).unwrap();
+/// ```
+pub struct Pwm<S: Syscalls>(S);
+
+impl<S: Syscalls> Pwm<S> {
+    /// Run a check against the PWM capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the number of PWM pins.
+    pub fn pin_count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::PIN_COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the highest frequency `pin` can generate, in Hertz.
+    pub fn max_frequency_hz(pin: u32) -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::MAX_FREQUENCY, pin, 0).to_result()
+    }
+
+    /// Starts generating a signal on `pin` at `frequency_hz` with the given
+    /// duty cycle, replacing any signal already on the pin.
+    pub fn start(pin: u32, frequency_hz: u32, duty_cycle: u32) -> Result<(), ErrorCode> {
+        if pin > 0xffff || duty_cycle > MAX_DUTY_CYCLE {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(
+            DRIVER_NUM,
+            command::START,
+            duty_cycle << 16 | pin,
+            frequency_hz,
+        )
+        .to_result()
+    }
+
+    /// Stops the signal on `pin`.
+    pub fn stop(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, pin, 0).to_result()
+    }
+}
+
+/// The duty cycle that keeps a pin high, which is 100%.
+pub const MAX_DUTY_CYCLE: u32 = 10_000;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const START: u32 = 1;
+    pub const STOP: u32 = 2;
+    pub const MAX_FREQUENCY: u32 = 3;
+    pub const PIN_COUNT: u32 = 5;
+}
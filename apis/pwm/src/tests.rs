@@ -0,0 +1,83 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Pwm = super::Pwm<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Pwm::driver_check());
+    assert_eq!(Pwm::pin_count(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::new(2);
+    kernel.add_driver(&driver);
+
+    assert!(Pwm::driver_check());
+    assert_eq!(Pwm::pin_count(), Ok(2));
+}
+
+#[test]
+fn max_frequency() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::new(2);
+    kernel.add_driver(&driver);
+
+    driver.set_max_frequency_hz(62_500);
+    assert_eq!(Pwm::max_frequency_hz(1), Ok(62_500));
+    assert_eq!(Pwm::max_frequency_hz(2), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn start_stop() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::new(2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Pwm::start(1, 1000, 2500), Ok(()));
+    assert_eq!(
+        driver.output(1),
+        Some(fake::PwmOutput {
+            frequency_hz: 1000,
+            duty_cycle: 2500
+        })
+    );
+    assert_eq!(Pwm::start(1, 50, MAX_DUTY_CYCLE), Ok(()));
+    assert_eq!(
+        driver.output(1),
+        Some(fake::PwmOutput {
+            frequency_hz: 50,
+            duty_cycle: MAX_DUTY_CYCLE
+        })
+    );
+    assert_eq!(driver.output(0), None);
+    assert_eq!(Pwm::stop(1), Ok(()));
+    assert_eq!(driver.output(1), None);
+
+    assert_eq!(Pwm::start(2, 1000, 2500), Err(ErrorCode::Invalid));
+    assert_eq!(
+        Pwm::start(0, 1000, MAX_DUTY_CYCLE + 1),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(Pwm::start(0x10000, 1000, 0), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn failed_start() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::new(2);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::START,
+        argument0: 5000 << 16 | 1,
+        argument1: 200,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Pwm::start(1, 200, 5000), Err(ErrorCode::Busy));
+}
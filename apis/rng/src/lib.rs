@@ -0,0 +1,151 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The random number generator driver.
+///
+/// It fills buffers with random bytes from the kernel's entropy source.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::rng::Rng;
+///
+/// let mut key = [0; 16];
+/// Rng::fill(&mut key).unwrap();
+/// ```
+pub struct Rng<
+    S: Syscalls,
+    C: platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<S: Syscalls, C: platform::allow_rw::Config + platform::subscribe::Config> Rng<S, C> {
+    /// Run a check against the rng capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Fills `buf` with random bytes, blocking until the whole buffer has been
+    /// filled. The kernel may return fewer bytes than requested, in which case
+    /// the remainder of the buffer is requested again.
+    pub fn fill(buf: &mut [u8]) -> Result<(), ErrorCode> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match Self::fill_partial(&mut buf[filled..])? {
+                // The kernel should never return 0 bytes, but if it does,
+                // retrying would loop forever.
+                0 => return Err(ErrorCode::Fail),
+                count => filled += count,
+            }
+        }
+        Ok(())
+    }
+
+    /// Requests random bytes to fill `buf` and returns the number of bytes the
+    /// kernel provided, which may be less than `buf.len()`.
+    pub fn fill_partial(buf: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let len = buf.len() as u32;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::BUFFER }>(allow_rw, buf)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::GET_BYTES, len, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((_, count)) = done.get() {
+                    return Ok((count as usize).min(len as usize));
+                }
+            }
+        })
+    }
+
+    /// Returns a random number generator that implements `rand_core::RngCore`
+    /// using this driver.
+    #[cfg(feature = "rand_core")]
+    pub fn source() -> RngSource<S, C> {
+        RngSource {
+            _driver: core::marker::PhantomData,
+        }
+    }
+}
+
+/// An implementation of `rand_core::RngCore` that gets its randomness from the
+/// kernel. `fill_bytes`, `next_u32`, and `next_u64` panic if the kernel
+/// returns an error; use `try_fill_bytes` to handle errors.
+#[cfg(feature = "rand_core")]
+pub struct RngSource<
+    S: Syscalls,
+    C: platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+> {
+    _driver: core::marker::PhantomData<Rng<S, C>>,
+}
+
+#[cfg(feature = "rand_core")]
+impl<S: Syscalls, C: platform::allow_rw::Config + platform::subscribe::Config> rand_core::RngCore
+    for RngSource<S, C>
+{
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Rng::<S, C>::fill(dest).expect("Failed to get random bytes from the kernel")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        Rng::<S, C>::fill(dest).map_err(|error| {
+            // CUSTOM_START + error is nonzero, as CUSTOM_START is nonzero and
+            // error codes are small.
+            let code = rand_core::Error::CUSTOM_START + error as u32;
+            rand_core::Error::from(core::num::NonZeroU32::new(code).unwrap())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const GET_BYTES: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const BUFFER: u32 = 0;
+}
@@ -0,0 +1,130 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Rng = super::Rng<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Rng::driver_check());
+    assert_eq!(Rng::fill(&mut [0; 4]), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    assert!(Rng::driver_check());
+}
+
+#[test]
+fn fill() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    let mut first = [0; 32];
+    let mut second = [0; 32];
+    driver.set_seed(7);
+    Rng::fill(&mut first).unwrap();
+    driver.set_seed(7);
+    Rng::fill(&mut second).unwrap();
+    assert_eq!(first, second);
+    assert_ne!(first, [0; 32]);
+}
+
+#[test]
+fn partial_fills() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    let mut expected = [0; 10];
+    driver.set_seed(3);
+    Rng::fill(&mut expected).unwrap();
+
+    // With at most 3 bytes per request, fill needs 4 requests, but produces the
+    // same bytes.
+    driver.set_seed(3);
+    driver.set_max_fill(3);
+    assert_eq!(Rng::fill_partial(&mut [0; 10]), Ok(3));
+    driver.set_seed(3);
+    let mut buf = [0; 10];
+    kernel.take_syscall_log();
+    Rng::fill(&mut buf).unwrap();
+    assert_eq!(buf, expected);
+    let requests = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            libtock_unittest::SyscallLogEntry::Command {
+                command_id: command::GET_BYTES,
+                argument0,
+                ..
+            } => Some(argument0),
+            _ => None,
+        });
+    assert!(requests.eq([10, 7, 4, 1]));
+
+    // A driver that returns no bytes causes an error rather than a hang.
+    driver.set_max_fill(0);
+    assert_eq!(Rng::fill(&mut buf), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn failed_fill() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::BUFFER,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::GET_BYTES,
+        argument0: 4,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Rng::fill(&mut [0; 4]), Err(ErrorCode::Busy));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn rng_core() {
+    use rand_core::RngCore;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    let mut bytes = [0; 8];
+    driver.set_seed(11);
+    Rng::fill(&mut bytes).unwrap();
+
+    driver.set_seed(11);
+    let mut source = Rng::source();
+    assert_eq!(source.next_u64(), u64::from_le_bytes(bytes));
+    driver.set_seed(11);
+    assert_eq!(
+        source.next_u32(),
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    );
+
+    driver.set_max_fill(0);
+    let error = source.try_fill_bytes(&mut bytes).unwrap_err();
+    assert_eq!(
+        error.code().map(core::num::NonZeroU32::get),
+        Some(rand_core::Error::CUSTOM_START + ErrorCode::Fail as u32)
+    );
+}
@@ -0,0 +1,125 @@
+use crate::{PixelFormat, Screen};
+use core::marker::PhantomData;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::{IntoStorage, Rgb565};
+use embedded_graphics_core::primitives::{PointsIter, Rectangle};
+use embedded_graphics_core::Pixel;
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// An `embedded_graphics_core::draw_target::DrawTarget` that draws on the
+/// screen. `Display` supports screens that use the RGB565 pixel format, and
+/// sends each pixel big-endian, which is the order display controllers expect.
+pub struct Display<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::subscribe::Config = DefaultConfig,
+> {
+    size: Size,
+    _screen: PhantomData<Screen<S, C>>,
+}
+
+impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> Display<S, C> {
+    /// Creates a `Display` that covers the screen at its current resolution.
+    /// Fails with `ErrorCode::NoSupport` if the screen's pixel format is not
+    /// RGB565.
+    pub fn new() -> Result<Self, ErrorCode> {
+        if Screen::<S, C>::get_pixel_format()? != PixelFormat::Rgb565 {
+            return Err(ErrorCode::NoSupport);
+        }
+        let (width, height) = Screen::<S, C>::get_resolution()?;
+        Ok(Display {
+            size: Size::new(width, height),
+            _screen: PhantomData,
+        })
+    }
+
+    // Selects area as the write frame. area must lie within the screen.
+    fn set_write_frame(area: &Rectangle) -> Result<(), ErrorCode> {
+        Screen::<S, C>::set_write_frame(
+            area.top_left.x as u32,
+            area.top_left.y as u32,
+            area.size.width,
+            area.size.height,
+        )
+    }
+
+    // Writes colors to the current write frame, a chunk at a time.
+    fn write_colors<I: Iterator<Item = Rgb565>>(mut colors: I) -> Result<(), ErrorCode> {
+        let mut chunk = [0; CHUNK_PIXELS * 2];
+        loop {
+            let mut len = 0;
+            for (pixel, color) in chunk.chunks_exact_mut(2).zip(&mut colors) {
+                pixel.copy_from_slice(&color.into_storage().to_be_bytes());
+                len += 2;
+            }
+            if len == 0 {
+                return Ok(());
+            }
+            Screen::<S, C>::write(&chunk[..len])?;
+        }
+    }
+}
+
+impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> OriginDimensions
+    for Display<S, C>
+{
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> DrawTarget
+    for Display<S, C>
+{
+    type Color = Rgb565;
+    type Error = ErrorCode;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), ErrorCode>
+    where
+        I: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let bounding_box = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if bounding_box.contains(point) {
+                Self::set_write_frame(&Rectangle::new(point, Size::new(1, 1)))?;
+                Screen::<S, C>::write(&color.into_storage().to_be_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), ErrorCode>
+    where
+        I: IntoIterator<Item = Rgb565>,
+    {
+        // Areas that are partially off-screen can't be written as a single
+        // frame, so they are drawn a pixel at a time.
+        if area.intersection(&self.bounding_box()) != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        Self::set_write_frame(area)?;
+        let pixels = area.size.width as usize * area.size.height as usize;
+        Self::write_colors(colors.into_iter().take(pixels))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> Result<(), ErrorCode> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        Self::set_write_frame(&area)?;
+        let pixels = area.size.width as usize * area.size.height as usize;
+        Self::write_colors(core::iter::repeat(color).take(pixels))
+    }
+}
+
+// The number of pixels written per write system call.
+const CHUNK_PIXELS: usize = 64;
@@ -0,0 +1,267 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+#[cfg(feature = "embedded-graphics-core")]
+mod graphics;
+#[cfg(feature = "embedded-graphics-core")]
+pub use graphics::Display;
+
+/// The screen driver, which draws on a pixel display.
+///
+/// Pixels are drawn by selecting a rectangular write frame with
+/// `set_write_frame`, then writing pixel data to it with `write`. The data is
+/// laid out row by row, in the screen's current pixel format. Writes that
+/// cover less than the whole frame continue where the previous write stopped.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::screen::Screen;
+///
+/// // Paint a 2x2 square in the top left corner red (RGB565).
+/// Screen::set_write_frame(0, 0, 2, 2).unwrap();
+/// Screen::write(&[0xf8, 0x00, 0xf8, 0x00, 0xf8, 0x00, 0xf8, 0x00]).unwrap();
+/// ```
+pub struct Screen<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> Screen<S, C> {
+    /// Run a check against the screen capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the number of resolutions the screen supports.
+    pub fn resolution_modes_count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::RESOLUTION_MODES_COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the `(width, height)` of the supported resolution at `index`.
+    pub fn resolution_mode(index: u32) -> Result<(u32, u32), ErrorCode> {
+        S::command(DRIVER_NUM, command::RESOLUTION_MODE, index, 0).to_result()
+    }
+
+    /// Returns the number of pixel formats the screen supports.
+    pub fn pixel_formats_count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::PIXEL_FORMATS_COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the supported pixel format at `index`.
+    pub fn supported_pixel_format(index: u32) -> Result<PixelFormat, ErrorCode> {
+        let format: u32 =
+            S::command(DRIVER_NUM, command::SUPPORTED_PIXEL_FORMAT, index, 0).to_result()?;
+        PixelFormat::try_from(format)
+    }
+
+    /// Returns the screen's current `(width, height)`, in pixels. The
+    /// resolution accounts for the screen's rotation.
+    pub fn get_resolution() -> Result<(u32, u32), ErrorCode> {
+        Self::command_and_wait(command::GET_RESOLUTION, 0, 0)
+    }
+
+    /// Sets the screen's resolution.
+    pub fn set_resolution(width: u32, height: u32) -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::SET_RESOLUTION, width, height).map(|_| ())
+    }
+
+    /// Returns the screen's current pixel format.
+    pub fn get_pixel_format() -> Result<PixelFormat, ErrorCode> {
+        let (format, _) = Self::command_and_wait(command::GET_PIXEL_FORMAT, 0, 0)?;
+        PixelFormat::try_from(format)
+    }
+
+    /// Sets the screen's pixel format.
+    pub fn set_pixel_format(format: PixelFormat) -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::SET_PIXEL_FORMAT, format as u32, 0).map(|_| ())
+    }
+
+    /// Returns the screen's current rotation.
+    pub fn get_rotation() -> Result<Rotation, ErrorCode> {
+        let (rotation, _) = Self::command_and_wait(command::GET_ROTATION, 0, 0)?;
+        Rotation::try_from(rotation)
+    }
+
+    /// Sets the screen's rotation.
+    pub fn set_rotation(rotation: Rotation) -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::SET_ROTATION, rotation as u32, 0).map(|_| ())
+    }
+
+    /// Selects the rectangle that `write` draws into. Each coordinate and
+    /// dimension must fit in 16 bits.
+    pub fn set_write_frame(x: u32, y: u32, width: u32, height: u32) -> Result<(), ErrorCode> {
+        if x > 0xffff || y > 0xffff || width > 0xffff || height > 0xffff {
+            return Err(ErrorCode::Invalid);
+        }
+        Self::command_and_wait(command::SET_WRITE_FRAME, x << 16 | y, width << 16 | height)
+            .map(|_| ())
+    }
+
+    /// Writes `data` to the current write frame. `data` holds pixels in the
+    /// screen's pixel format, row by row.
+    pub fn write(data: &[u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, data)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::WRITE, data.len() as u32, 0).to_result()?;
+
+            wait_for_done::<S>(&done).map(|_| ())
+        })
+    }
+
+    // Runs a command that completes with a DONE upcall, and returns the two
+    // values the upcall carries after its status.
+    fn command_and_wait(
+        command_num: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::DONE }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command_num, argument0, argument1).to_result()?;
+
+            wait_for_done::<S>(&done)
+        })
+    }
+}
+
+/// The formats a screen can store pixels in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PixelFormat {
+    /// One bit per pixel; pixels that are set are lit.
+    Mono = 0,
+    /// One byte per pixel: 2 bits of red, 3 of green and 3 of blue.
+    Rgb233 = 1,
+    /// Two bytes per pixel: 5 bits of red, 6 of green and 5 of blue.
+    Rgb565 = 2,
+    /// Three bytes per pixel: 8 bits each of red, green and blue.
+    Rgb888 = 3,
+    /// Four bytes per pixel: 8 bits each of alpha, red, green and blue.
+    Argb8888 = 4,
+}
+
+impl PixelFormat {
+    /// Returns the number of bits used to store each pixel.
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Mono => 1,
+            PixelFormat::Rgb233 => 8,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb888 => 24,
+            PixelFormat::Argb8888 => 32,
+        }
+    }
+}
+
+impl TryFrom<u32> for PixelFormat {
+    type Error = ErrorCode;
+
+    fn try_from(value: u32) -> Result<PixelFormat, ErrorCode> {
+        match value {
+            0 => Ok(PixelFormat::Mono),
+            1 => Ok(PixelFormat::Rgb233),
+            2 => Ok(PixelFormat::Rgb565),
+            3 => Ok(PixelFormat::Rgb888),
+            4 => Ok(PixelFormat::Argb8888),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+/// The orientations a screen can be rotated to, clockwise.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rotation {
+    Normal = 0,
+    Rotated90 = 1,
+    Rotated180 = 2,
+    Rotated270 = 3,
+}
+
+impl TryFrom<u32> for Rotation {
+    type Error = ErrorCode;
+
+    fn try_from(value: u32) -> Result<Rotation, ErrorCode> {
+        match value {
+            0 => Ok(Rotation::Normal),
+            1 => Ok(Rotation::Rotated90),
+            2 => Ok(Rotation::Rotated180),
+            3 => Ok(Rotation::Rotated270),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+// Waits for the DONE upcall. Returns an error if the upcall reports a failure,
+// and otherwise returns the two values that follow the status.
+fn wait_for_done<S: Syscalls>(
+    done: &Cell<Option<(u32, u32, u32)>>,
+) -> Result<(u32, u32), ErrorCode> {
+    loop {
+        S::yield_wait();
+        if let Some((status, value0, value1)) = done.get() {
+            return match ErrorCode::try_from(status) {
+                Ok(error) => Err(error),
+                Err(_) => Ok((value0, value1)),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const RESOLUTION_MODES_COUNT: u32 = 11;
+    pub const RESOLUTION_MODE: u32 = 12;
+    pub const PIXEL_FORMATS_COUNT: u32 = 13;
+    pub const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+    pub const GET_ROTATION: u32 = 21;
+    pub const SET_ROTATION: u32 = 22;
+    pub const GET_RESOLUTION: u32 = 23;
+    pub const SET_RESOLUTION: u32 = 24;
+    pub const GET_PIXEL_FORMAT: u32 = 25;
+    pub const SET_PIXEL_FORMAT: u32 = 26;
+    pub const SET_WRITE_FRAME: u32 = 100;
+    pub const WRITE: u32 = 200;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
@@ -0,0 +1,150 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Screen = super::Screen<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Screen::driver_check());
+    assert_eq!(Screen::resolution_modes_count(), Err(ErrorCode::NoDevice));
+    assert_eq!(Screen::get_resolution(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(240, 320);
+    kernel.add_driver(&driver);
+
+    assert!(Screen::driver_check());
+}
+
+#[test]
+fn queries() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(240, 320);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::resolution_modes_count(), Ok(1));
+    assert_eq!(Screen::resolution_mode(0), Ok((240, 320)));
+    assert_eq!(Screen::resolution_mode(1), Err(ErrorCode::Invalid));
+    assert_eq!(Screen::pixel_formats_count(), Ok(2));
+    assert_eq!(Screen::supported_pixel_format(0), Ok(PixelFormat::Rgb565));
+    assert_eq!(Screen::supported_pixel_format(1), Ok(PixelFormat::Rgb888));
+
+    assert_eq!(Screen::get_resolution(), Ok((240, 320)));
+    assert_eq!(Screen::set_resolution(240, 320), Ok(()));
+    assert_eq!(Screen::get_pixel_format(), Ok(PixelFormat::Rgb565));
+    assert_eq!(Screen::set_pixel_format(PixelFormat::Rgb888), Ok(()));
+    assert_eq!(Screen::get_pixel_format(), Ok(PixelFormat::Rgb888));
+    assert_eq!(
+        Screen::set_pixel_format(PixelFormat::Mono),
+        Err(ErrorCode::NoSupport)
+    );
+    assert_eq!(PixelFormat::Rgb888.bits_per_pixel(), 24);
+}
+
+#[test]
+fn rotation() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(240, 320);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::get_rotation(), Ok(Rotation::Normal));
+    assert_eq!(Screen::set_rotation(Rotation::Rotated270), Ok(()));
+    assert_eq!(Screen::get_rotation(), Ok(Rotation::Rotated270));
+    assert_eq!(Screen::get_resolution(), Ok((320, 240)));
+    assert_eq!(Screen::set_rotation(Rotation::Rotated180), Ok(()));
+    assert_eq!(Screen::get_resolution(), Ok((240, 320)));
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(3, 3);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::set_write_frame(1, 0, 2, 2), Ok(()));
+    assert_eq!(driver.write_frame(), (1, 0, 2, 2));
+    // Successive writes continue where the previous one stopped.
+    assert_eq!(Screen::write(&[0xf8, 0x00, 0x07, 0xe0]), Ok(()));
+    assert_eq!(Screen::write(&[0x00, 0x1f]), Ok(()));
+    #[rustfmt::skip]
+    assert_eq!(driver.framebuffer(), [
+        0x00, 0x00, 0xf8, 0x00, 0x07, 0xe0,
+        0x00, 0x00, 0x00, 0x1f, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]);
+
+    assert_eq!(Screen::set_write_frame(2, 2, 2, 1), Err(ErrorCode::Invalid));
+    assert_eq!(
+        Screen::set_write_frame(0x10000, 0, 1, 1),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn failed_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(3, 3);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::WRITE,
+        argument0: 2,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Screen::write(&[0xff, 0xff]), Err(ErrorCode::Busy));
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn display() {
+    use embedded_graphics_core::draw_target::DrawTarget;
+    use embedded_graphics_core::geometry::{OriginDimensions, Point, Size};
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+    use embedded_graphics_core::primitives::Rectangle;
+    use embedded_graphics_core::Pixel;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(4, 3);
+    kernel.add_driver(&driver);
+
+    let mut display = super::Display::<fake::Syscalls>::new().unwrap();
+    assert_eq!(display.size(), Size::new(4, 3));
+
+    display.clear(Rgb565::BLUE).unwrap();
+    display
+        .fill_solid(
+            &Rectangle::new(Point::new(2, 1), Size::new(5, 5)),
+            Rgb565::RED,
+        )
+        .unwrap();
+    display
+        .draw_iter([
+            Pixel(Point::new(0, 2), Rgb565::GREEN),
+            Pixel(Point::new(4, 0), Rgb565::GREEN),
+        ])
+        .unwrap();
+    let (b, r, g) = (0x001f, 0xf800, 0x07e0);
+    let pixels = (0..3).flat_map(|y| (0..4).map(move |x| (x, y)));
+    assert!(pixels
+        .map(|(x, y)| driver.pixel(x, y).unwrap())
+        .eq([b, b, b, b, b, b, r, r, g, b, r, r]));
+
+    Screen::set_pixel_format(PixelFormat::Rgb888).unwrap();
+    assert!(super::Display::<fake::Syscalls>::new().is_err());
+}
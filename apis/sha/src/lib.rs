@@ -0,0 +1,160 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The SHA driver, which computes digests using the hardware SHA engine.
+///
+/// A digest is computed incrementally: data is added with `update`, and
+/// `finish` writes the digest of everything added since the last `finish` (or
+/// `set_algorithm`). The driver computes SHA-256 digests until another
+/// algorithm is selected.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::sha::Sha;
+///
+/// Sha::update(b"hello, ").unwrap();
+/// Sha::update(b"world").unwrap();
+/// let mut digest = [0; 32];
+/// Sha::finish(&mut digest).unwrap();
+/// ```
+pub struct Sha<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Sha<S, C>
+{
+    /// Run a check against the sha capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Selects the digest algorithm, discarding any data added with `update`.
+    pub fn set_algorithm(algorithm: ShaAlgorithm) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as u32, 0).to_result()
+    }
+
+    /// Adds `data` to the digest being computed.
+    pub fn update(data: &[u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::UPDATE, 0, 0).to_result()?;
+
+            wait_for_status::<S>(&done)
+        })
+    }
+
+    /// Writes the digest of the data added since the last `finish` into
+    /// `digest`, and starts a new digest. Fails with `ErrorCode::Size` if
+    /// `digest` is shorter than the algorithm's digest length.
+    pub fn finish(digest: &mut [u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::DIGEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DIGEST }>(allow_rw, digest)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result()?;
+
+            wait_for_status::<S>(&done)
+        })
+    }
+}
+
+/// The digest algorithms supported by the SHA driver.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShaAlgorithm {
+    Sha256 = 0,
+    Sha384 = 1,
+    Sha512 = 2,
+}
+
+impl ShaAlgorithm {
+    /// Returns the length of the algorithm's digest, in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            ShaAlgorithm::Sha256 => 32,
+            ShaAlgorithm::Sha384 => 48,
+            ShaAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+// Waits for the DONE upcall and returns the status it carries.
+fn wait_for_status<S: Syscalls>(done: &Cell<Option<(u32,)>>) -> Result<(), ErrorCode> {
+    loop {
+        S::yield_wait();
+        if let Some((status,)) = done.get() {
+            return match ErrorCode::try_from(status) {
+                Ok(error) => Err(error),
+                Err(_) => Ok(()),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const UPDATE: u32 = 3;
+    pub const FINISH: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const DATA: u32 = 1;
+}
+
+mod allow_rw {
+    pub const DIGEST: u32 = 0;
+}
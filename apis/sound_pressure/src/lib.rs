@@ -0,0 +1,83 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The sound pressure sensor driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::sound_pressure::SoundPressure;
+///
+/// SoundPressure::enable().unwrap();
+/// let pressure = SoundPressure::read().unwrap();
+/// ```
+pub struct SoundPressure<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> SoundPressure<S, C> {
+    /// Run a check against the sound pressure capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Powers the sensor on. The sensor stays on until every process that
+    /// enabled it has disabled it.
+    pub fn enable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ENABLE, 0, 0).to_result()
+    }
+
+    /// Allows the sensor to be powered off.
+    pub fn disable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::DISABLE, 0, 0).to_result()
+    }
+
+    /// Measures the sound pressure, blocking until the sensor returns it.
+    pub fn read() -> Result<Decibels, ErrorCode> {
+        let reading = Cell::new(Option::<(u32,)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &reading)?;
+
+            S::command(DRIVER_NUM, command::READ, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((value,)) = reading.get() {
+                    return Ok(Decibels(value as u8));
+                }
+            }
+        })
+    }
+}
+
+/// A sound pressure level, in decibels.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Decibels(pub u8);
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60006;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ: u32 = 1;
+    pub const ENABLE: u32 = 2;
+    pub const DISABLE: u32 = 3;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
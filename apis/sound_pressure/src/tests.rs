@@ -0,0 +1,69 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type SoundPressure = super::SoundPressure<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!SoundPressure::driver_check());
+    assert_eq!(SoundPressure::enable(), Err(ErrorCode::NoDevice));
+    assert_eq!(SoundPressure::read(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+
+    assert!(SoundPressure::driver_check());
+}
+
+#[test]
+fn enable() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SoundPressure::enable(), Ok(()));
+    assert!(driver.is_enabled());
+    assert_eq!(SoundPressure::disable(), Ok(()));
+    assert!(!driver.is_enabled());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(64);
+    assert_eq!(SoundPressure::read(), Ok(Decibels(64)));
+    assert!(!driver.is_busy());
+
+    driver.set_error_sync(ErrorCode::Fail);
+    assert_eq!(SoundPressure::read(), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn failed_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READING,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(SoundPressure::read(), Err(ErrorCode::Busy));
+}
@@ -0,0 +1,77 @@
+use crate::SpiController;
+use core::marker::PhantomData;
+use embedded_hal::blocking::spi;
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The SPI bus, which implements the `embedded_hal::blocking::spi` traits.
+/// Transfers go to the chip selected with `SpiController::select_chip`, using
+/// the rate, phase and polarity configured through `SpiController`. Each
+/// transfer is a single kernel transaction, so transfers are limited to
+/// `SpiBus::MAX_TRANSFER_LEN` bytes.
+pub struct SpiBus<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+> {
+    _spi_controller: PhantomData<SpiController<S, C>>,
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > SpiBus<S, C>
+{
+    /// The longest transfer `spi::Transfer::transfer` accepts.
+    pub const MAX_TRANSFER_LEN: usize = MAX_TRANSFER_LEN;
+
+    pub fn new() -> Self {
+        SpiBus {
+            _spi_controller: PhantomData,
+        }
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Default for SpiBus<S, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > spi::Transfer<u8> for SpiBus<S, C>
+{
+    type Error = ErrorCode;
+
+    // The kernel cannot write from and read into the same buffer, so the bytes
+    // to write are copied onto the stack. Fails with `ErrorCode::Size` if
+    // `words` is longer than `SpiBus::MAX_TRANSFER_LEN`.
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ErrorCode> {
+        let mut data = [0; MAX_TRANSFER_LEN];
+        let data = data.get_mut(..words.len()).ok_or(ErrorCode::Size)?;
+        data.copy_from_slice(words);
+        SpiController::<S, C>::read_write(data, words)?;
+        Ok(words)
+    }
+}
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > spi::Write<u8> for SpiBus<S, C>
+{
+    type Error = ErrorCode;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), ErrorCode> {
+        SpiController::<S, C>::write(words)
+    }
+}
+
+// The size of the stack buffer `SpiBus::transfer` copies the bytes to write
+// into.
+const MAX_TRANSFER_LEN: usize = 64;
@@ -0,0 +1,181 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+#[cfg(feature = "embedded-hal")]
+mod hal;
+#[cfg(feature = "embedded-hal")]
+pub use hal::SpiBus;
+
+/// The SPI controller driver, which gives the process access to an SPI bus as
+/// the bus controller.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::spi_controller::{ClockPhase, ClockPolarity, SpiController};
+///
+/// SpiController::select_chip(0).unwrap();
+/// SpiController::set_rate(1_000_000).unwrap();
+/// SpiController::set_phase(ClockPhase::SampleLeading).unwrap();
+/// SpiController::set_polarity(ClockPolarity::IdleLow).unwrap();
+/// let mut response = [0; 2];
+/// SpiController::read_write(&[0x9f, 0], &mut response).unwrap();
+/// ```
+pub struct SpiController<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > SpiController<S, C>
+{
+    /// Run a check against the SPI controller capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Selects the chip select line to assert during transfers.
+    pub fn select_chip(chip_select: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_CHIP_SELECT, chip_select, 0).to_result()
+    }
+
+    /// Returns the selected chip select line.
+    pub fn get_chip_select() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_CHIP_SELECT, 0, 0).to_result()
+    }
+
+    /// Sets the bus clock rate, in Hz. The hardware may not support the exact
+    /// rate; use `get_rate` to retrieve the rate that was chosen.
+    pub fn set_rate(rate_hz: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_RATE, rate_hz, 0).to_result()
+    }
+
+    /// Returns the bus clock rate, in Hz.
+    pub fn get_rate() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_RATE, 0, 0).to_result()
+    }
+
+    pub fn set_phase(phase: ClockPhase) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_PHASE, phase as u32, 0).to_result()
+    }
+
+    pub fn get_phase() -> Result<ClockPhase, ErrorCode> {
+        match S::command(DRIVER_NUM, command::GET_PHASE, 0, 0).to_result::<u32, _>()? {
+            0 => Ok(ClockPhase::SampleLeading),
+            1 => Ok(ClockPhase::SampleTrailing),
+            _ => Err(ErrorCode::BadRVal),
+        }
+    }
+
+    pub fn set_polarity(polarity: ClockPolarity) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_POLARITY, polarity as u32, 0).to_result()
+    }
+
+    pub fn get_polarity() -> Result<ClockPolarity, ErrorCode> {
+        match S::command(DRIVER_NUM, command::GET_POLARITY, 0, 0).to_result::<u32, _>()? {
+            0 => Ok(ClockPolarity::IdleLow),
+            1 => Ok(ClockPolarity::IdleHigh),
+            _ => Err(ErrorCode::BadRVal),
+        }
+    }
+
+    /// Writes `data` to the selected chip, discarding the bytes it sends back.
+    pub fn write(data: &[u8]) -> Result<(), ErrorCode> {
+        Self::read_write(data, &mut [])
+    }
+
+    /// Performs a full-duplex transfer: writes `data` to the selected chip
+    /// while reading the bytes it sends back into `buffer`. `buffer` must be
+    /// empty (to discard the bytes) or at least as long as `data`.
+    pub fn read_write(data: &[u8], buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, data)?;
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::READ_WRITE, data.len() as u32, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if done.get().is_some() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+}
+
+/// The clock edge on which data is sampled.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClockPhase {
+    SampleLeading = 0,
+    SampleTrailing = 1,
+}
+
+/// The level of the clock line when the bus is idle.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClockPolarity {
+    IdleLow = 0,
+    IdleHigh = 1,
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20001;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ_WRITE: u32 = 2;
+    pub const SET_CHIP_SELECT: u32 = 3;
+    pub const GET_CHIP_SELECT: u32 = 4;
+    pub const SET_RATE: u32 = 5;
+    pub const GET_RATE: u32 = 6;
+    pub const SET_PHASE: u32 = 7;
+    pub const GET_PHASE: u32 = 8;
+    pub const SET_POLARITY: u32 = 9;
+    pub const GET_POLARITY: u32 = 10;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
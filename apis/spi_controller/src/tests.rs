@@ -0,0 +1,145 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type SpiController = super::SpiController<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!SpiController::driver_check());
+    assert_eq!(SpiController::write(&[1]), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    assert!(SpiController::driver_check());
+}
+
+#[test]
+fn configuration() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SpiController::select_chip(2), Ok(()));
+    assert_eq!(SpiController::get_chip_select(), Ok(2));
+    assert_eq!(SpiController::select_chip(9), Err(ErrorCode::Invalid));
+    assert_eq!(SpiController::set_rate(8_000_000), Ok(()));
+    assert_eq!(SpiController::get_rate(), Ok(8_000_000));
+
+    assert_eq!(SpiController::get_phase(), Ok(ClockPhase::SampleLeading));
+    assert_eq!(SpiController::set_phase(ClockPhase::SampleTrailing), Ok(()));
+    assert_eq!(SpiController::get_phase(), Ok(ClockPhase::SampleTrailing));
+    assert_eq!(SpiController::set_polarity(ClockPolarity::IdleHigh), Ok(()));
+    assert_eq!(SpiController::get_polarity(), Ok(ClockPolarity::IdleHigh));
+    assert_eq!((driver.phase(), driver.polarity()), (1, 1));
+}
+
+#[test]
+fn transfers() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    SpiController::select_chip(1).unwrap();
+    SpiController::write(&[0x06]).unwrap();
+
+    // A JEDEC ID read: the peripheral ignores the bytes after the command and
+    // responds with its ID.
+    driver.add_miso(&[0xff, 0xef, 0x40, 0x18]);
+    let mut response = [0; 4];
+    SpiController::read_write(&[0x9f, 0, 0, 0], &mut response).unwrap();
+    assert_eq!(response, [0xff, 0xef, 0x40, 0x18]);
+
+    // Once the script runs out, the fake echoes.
+    let mut response = [0; 2];
+    SpiController::read_write(&[5, 6], &mut response).unwrap();
+    assert_eq!(response, [5, 6]);
+
+    let frames = driver.take_frames();
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|frame| frame.chip_select == 1));
+    assert_eq!(frames[0].mosi, [0x06]);
+    assert_eq!(frames[1].mosi, [0x9f, 0, 0, 0]);
+    assert_eq!(frames[1].miso, [0xff, 0xef, 0x40, 0x18]);
+
+    // The read buffer must be able to hold the whole transfer.
+    assert_eq!(
+        SpiController::read_write(&[1, 2], &mut [0]),
+        Err(ErrorCode::Size)
+    );
+}
+
+#[test]
+fn failed_read_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::READ,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ_WRITE,
+        argument0: 2,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(
+        SpiController::read_write(&[1, 2], &mut [0; 2]),
+        Err(ErrorCode::Busy)
+    );
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn embedded_hal() {
+    use embedded_hal::blocking::spi::{Transfer, Write};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    let mut bus = SpiBus::<fake::Syscalls>::new();
+    assert_eq!(bus.write(&[0x06]), Ok(()));
+
+    driver.add_miso(&[0xff, 0xef, 0x40, 0x18]);
+    let mut words = [0x9f, 0, 0, 0];
+    assert_eq!(bus.transfer(&mut words), Ok(&[0xff, 0xef, 0x40, 0x18][..]));
+
+    // A long transfer is still a single frame.
+    let mut words = [0; 40];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = i as u8;
+    }
+    let expected = words;
+    assert_eq!(bus.transfer(&mut words), Ok(&expected[..]));
+
+    // Transfers that do not fit in one transaction are rejected.
+    let mut words = [0; SpiBus::<fake::Syscalls>::MAX_TRANSFER_LEN + 1];
+    assert_eq!(bus.transfer(&mut words), Err(ErrorCode::Size));
+
+    let frames = driver.take_frames();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].mosi, [0x06]);
+    assert_eq!(frames[1].mosi, [0x9f, 0, 0, 0]);
+    assert_eq!(frames[2].mosi, expected);
+}
@@ -0,0 +1,76 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The temperature sensor driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::temperature::Temperature;
+///
+/// let temperature = Temperature::read().unwrap();
+/// ```
+pub struct Temperature<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Temperature<S, C> {
+    /// Run a check against the temperature capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Takes a temperature reading, blocking until the sensor returns it.
+    pub fn read() -> Result<CentiCelsius, ErrorCode> {
+        let reading = Cell::new(Option::<(u32,)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::READING }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READING }>(subscribe, &reading)?;
+
+            S::command(DRIVER_NUM, command::READ_TEMPERATURE, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((value,)) = reading.get() {
+                    return Ok(CentiCelsius(value as i32));
+                }
+            }
+        })
+    }
+}
+
+/// A temperature, in hundredths of a degree Celsius.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CentiCelsius(pub i32);
+
+impl CentiCelsius {
+    /// Returns the temperature in whole degrees Celsius, rounded towards zero.
+    pub fn whole_degrees(self) -> i32 {
+        self.0 / 100
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60000;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const READ_TEMPERATURE: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
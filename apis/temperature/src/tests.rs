@@ -0,0 +1,62 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Temperature = super::Temperature<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Temperature::driver_check());
+    assert_eq!(Temperature::read(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    assert!(Temperature::driver_check());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(2375);
+    assert_eq!(Temperature::read(), Ok(CentiCelsius(2375)));
+    assert!(!driver.is_busy());
+
+    // Temperatures below freezing are reported as negative values.
+    driver.set_value_sync(-1250);
+    let temperature = Temperature::read().unwrap();
+    assert_eq!(temperature, CentiCelsius(-1250));
+    assert_eq!(temperature.whole_degrees(), -12);
+
+    driver.set_error_sync(ErrorCode::Fail);
+    assert_eq!(Temperature::read(), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn failed_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::READING,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::READ_TEMPERATURE,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(Temperature::read(), Err(ErrorCode::Busy));
+}
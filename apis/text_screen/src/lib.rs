@@ -0,0 +1,178 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The text screen driver, which controls character displays such as HD44780
+/// LCDs.
+///
+/// Text is written at the cursor, which advances past each character written.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::text_screen::TextScreen;
+///
+/// TextScreen::display_on().unwrap();
+/// TextScreen::clear().unwrap();
+/// TextScreen::set_cursor(0, 1).unwrap();
+/// TextScreen::write(b"Hello").unwrap();
+/// ```
+pub struct TextScreen<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<S: Syscalls, C: platform::allow_ro::Config + platform::subscribe::Config> TextScreen<S, C> {
+    /// Run a check against the text screen capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the screen's `(columns, rows)`.
+    pub fn get_size() -> Result<(u32, u32), ErrorCode> {
+        Self::command_and_wait(command::GET_SIZE, 0, 0)
+    }
+
+    /// Turns the display on.
+    pub fn display_on() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::DISPLAY_ON, 0, 0).map(|_| ())
+    }
+
+    /// Turns the display off. The screen's contents are kept.
+    pub fn display_off() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::DISPLAY_OFF, 0, 0).map(|_| ())
+    }
+
+    /// Makes the cursor blink.
+    pub fn blink_on() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::BLINK_ON, 0, 0).map(|_| ())
+    }
+
+    /// Stops the cursor from blinking.
+    pub fn blink_off() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::BLINK_OFF, 0, 0).map(|_| ())
+    }
+
+    /// Shows the cursor.
+    pub fn show_cursor() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::SHOW_CURSOR, 0, 0).map(|_| ())
+    }
+
+    /// Hides the cursor.
+    pub fn hide_cursor() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::HIDE_CURSOR, 0, 0).map(|_| ())
+    }
+
+    /// Writes `text` at the cursor.
+    pub fn write(text: &[u8]) -> Result<(), ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, text)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::WRITE, text.len() as u32, 0).to_result()?;
+
+            wait_for_done::<S>(&done).map(|_| ())
+        })
+    }
+
+    /// Clears the screen and moves the cursor to the top left corner.
+    pub fn clear() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::CLEAR, 0, 0).map(|_| ())
+    }
+
+    /// Moves the cursor to the top left corner.
+    pub fn home() -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::HOME, 0, 0).map(|_| ())
+    }
+
+    /// Moves the cursor to `column` of `row`, counting from zero.
+    pub fn set_cursor(column: u32, row: u32) -> Result<(), ErrorCode> {
+        Self::command_and_wait(command::SET_CURSOR, column, row).map(|_| ())
+    }
+
+    // Runs a command that completes with a DONE upcall, and returns the two
+    // values the upcall carries after its status.
+    fn command_and_wait(
+        command_num: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let done = Cell::new(Option::<(u32, u32, u32)>::None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::DONE }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command_num, argument0, argument1).to_result()?;
+
+            wait_for_done::<S>(&done)
+        })
+    }
+}
+
+// Waits for the DONE upcall. Returns an error if the upcall reports a failure,
+// and otherwise returns the two values that follow the status.
+fn wait_for_done<S: Syscalls>(
+    done: &Cell<Option<(u32, u32, u32)>>,
+) -> Result<(u32, u32), ErrorCode> {
+    loop {
+        S::yield_wait();
+        if let Some((status, value0, value1)) = done.get() {
+            return match ErrorCode::try_from(status) {
+                Ok(error) => Err(error),
+                Err(_) => Ok((value0, value1)),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const GET_SIZE: u32 = 1;
+    pub const DISPLAY_ON: u32 = 2;
+    pub const DISPLAY_OFF: u32 = 3;
+    pub const BLINK_ON: u32 = 4;
+    pub const BLINK_OFF: u32 = 5;
+    pub const SHOW_CURSOR: u32 = 6;
+    pub const HIDE_CURSOR: u32 = 7;
+    pub const WRITE: u32 = 8;
+    pub const CLEAR: u32 = 9;
+    pub const HOME: u32 = 10;
+    pub const SET_CURSOR: u32 = 11;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
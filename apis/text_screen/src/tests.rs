@@ -0,0 +1,88 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type TextScreen = super::TextScreen<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!TextScreen::driver_check());
+    assert_eq!(TextScreen::display_on(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert!(TextScreen::driver_check());
+    assert_eq!(TextScreen::get_size(), Ok((16, 2)));
+}
+
+#[test]
+fn display_settings() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::display_on(), Ok(()));
+    assert!(driver.is_display_on());
+    assert_eq!(TextScreen::show_cursor(), Ok(()));
+    assert!(driver.is_cursor_visible());
+    assert_eq!(TextScreen::blink_on(), Ok(()));
+    assert!(driver.is_blinking());
+    assert_eq!(TextScreen::blink_off(), Ok(()));
+    assert!(!driver.is_blinking());
+    assert_eq!(TextScreen::hide_cursor(), Ok(()));
+    assert!(!driver.is_cursor_visible());
+    assert_eq!(TextScreen::display_off(), Ok(()));
+    assert!(!driver.is_display_on());
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(8, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::write(b"Hello"), Ok(()));
+    assert_eq!(TextScreen::set_cursor(2, 1), Ok(()));
+    assert_eq!(TextScreen::write(b"Tock"), Ok(()));
+    assert_eq!(driver.text(), "Hello   \n  Tock  \n");
+    assert_eq!(TextScreen::home(), Ok(()));
+    assert_eq!(TextScreen::write(b"J"), Ok(()));
+    assert_eq!(driver.text(), "Jello   \n  Tock  \n");
+    assert_eq!(TextScreen::set_cursor(8, 0), Err(ErrorCode::Invalid));
+
+    assert_eq!(TextScreen::clear(), Ok(()));
+    assert_eq!(driver.text(), "        \n        \n");
+    assert_eq!(driver.cursor(), (0, 0));
+}
+
+#[test]
+fn failed_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(8, 2);
+    kernel.add_driver(&driver);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::DONE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::WRITE,
+        argument0: 2,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+
+    assert_eq!(TextScreen::write(b"Hi"), Err(ErrorCode::Busy));
+}
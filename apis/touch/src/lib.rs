@@ -0,0 +1,303 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::{OneId, Subscribe};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+/// The touch panel driver.
+///
+/// Single-touch events and gestures are delivered to listeners, while events
+/// with several fingers on the panel are read with `read_multi_touch`.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::touch::{Touch, TouchListener};
+///
+/// let listener = TouchListener(|event| {
+///     // make use of event.status, event.x and event.y
+/// });
+///
+/// share::scope(|subscribe| {
+///     if let Ok(()) = Touch::register_listener(&listener, subscribe) {
+///         // yield
+///     }
+/// });
+/// ```
+pub struct Touch<
+    S: Syscalls,
+    C: platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<S: Syscalls, C: platform::allow_rw::Config + platform::subscribe::Config> Touch<S, C> {
+    /// Run a check against the touch capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the number of fingers the panel can track at once.
+    pub fn number_of_touches() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::NUMBER_OF_TOUCHES, 0, 0).to_result()
+    }
+
+    /// Register a single-touch events listener
+    ///
+    /// There can be only one single listener registered at a time.
+    /// Each time this function is used, it will replace the
+    /// previously registered listener.
+    pub fn register_listener<'share, F: Fn(TouchEvent)>(
+        listener: &'share TouchListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::SINGLE_TOUCH }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SINGLE_TOUCH }>(subscribe, listener)
+    }
+
+    /// Unregister the single-touch events listener
+    ///
+    /// This function may be used even if there was no
+    /// previously registered listener.
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::SINGLE_TOUCH)
+    }
+
+    /// Register a gesture listener
+    ///
+    /// There can be only one single gesture listener registered at a time.
+    /// Each time this function is used, it will replace the
+    /// previously registered gesture listener.
+    pub fn register_gesture_listener<'share, F: Fn(Gesture)>(
+        listener: &'share GestureListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::GESTURE }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::GESTURE }>(subscribe, listener)
+    }
+
+    /// Unregister the gesture listener
+    ///
+    /// This function may be used even if there was no
+    /// previously registered gesture listener.
+    pub fn unregister_gesture_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::GESTURE)
+    }
+
+    /// Waits for the next multi-touch event and returns the touches it
+    /// contains, which the kernel writes into `buffer`.
+    ///
+    /// Each touch takes `MULTI_TOUCH_RECORD_LEN` bytes of `buffer`; touches
+    /// that do not fit are lost. Multi-touch events that occur while the
+    /// process is not waiting in `read_multi_touch` are dropped by the kernel.
+    pub fn read_multi_touch(buffer: &mut [u8]) -> Result<MultiTouch<'_>, ErrorCode> {
+        let event: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        let (touches, dropped_events, _) = share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::MULTI_TOUCH }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::MULTI_TOUCH }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::MULTI_TOUCH }>(allow_rw, buffer)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::MULTI_TOUCH }>(subscribe, &event)?;
+
+            loop {
+                S::yield_wait();
+                if let Some(event) = event.get() {
+                    return Ok(event);
+                }
+            }
+        })?;
+        // The kernel does not deliver another multi-touch event until this one
+        // is acknowledged.
+        S::command(DRIVER_NUM, command::ACK_MULTI_TOUCH, 0, 0).to_result::<(), ErrorCode>()?;
+
+        let len = (touches as usize * MULTI_TOUCH_RECORD_LEN).min(buffer.len());
+        Ok(MultiTouch {
+            records: &buffer[..len],
+            dropped_events,
+        })
+    }
+}
+
+/// The number of bytes each touch takes in a multi-touch buffer.
+pub const MULTI_TOUCH_RECORD_LEN: usize = 8;
+
+/// Whether a finger touched, moved on, or left the panel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TouchStatus {
+    Released,
+    Pressed,
+    Moved,
+}
+
+impl TryFrom<u32> for TouchStatus {
+    type Error = ErrorCode;
+
+    fn try_from(value: u32) -> Result<TouchStatus, ErrorCode> {
+        match value {
+            0 => Ok(TouchStatus::Released),
+            1 => Ok(TouchStatus::Pressed),
+            2 => Ok(TouchStatus::Moved),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+/// A touch reported by the panel. `size` and `pressure` are 0 if the panel
+/// does not measure them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TouchEvent {
+    /// Identifies the finger across the events of a multi-touch sequence.
+    /// Always 0 for single-touch events.
+    pub id: u8,
+    pub status: TouchStatus,
+    pub x: u16,
+    pub y: u16,
+    pub size: u16,
+    pub pressure: u16,
+}
+
+impl TouchEvent {
+    // Decodes a multi-touch record, which is laid out as: id, status, x (big
+    // endian), y (big endian), size, pressure.
+    fn from_record(record: &[u8]) -> Option<TouchEvent> {
+        Some(TouchEvent {
+            id: record[0],
+            status: TouchStatus::try_from(u32::from(record[1])).ok()?,
+            x: u16::from_be_bytes([record[2], record[3]]),
+            y: u16::from_be_bytes([record[4], record[5]]),
+            size: record[6].into(),
+            pressure: record[7].into(),
+        })
+    }
+}
+
+/// The touches of a multi-touch event, as returned by `read_multi_touch`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MultiTouch<'b> {
+    records: &'b [u8],
+    dropped_events: u32,
+}
+
+impl<'b> MultiTouch<'b> {
+    /// Returns the touches in this event. Touches with an unknown status are
+    /// skipped.
+    pub fn touches(&self) -> impl Iterator<Item = TouchEvent> + 'b {
+        self.records
+            .chunks_exact(MULTI_TOUCH_RECORD_LEN)
+            .filter_map(TouchEvent::from_record)
+    }
+
+    /// Returns the number of multi-touch events the kernel dropped before this
+    /// one because the process did not read them in time.
+    pub fn dropped_events(&self) -> u32 {
+        self.dropped_events
+    }
+}
+
+/// A gesture recognized by the panel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Gesture {
+    SwipeUp,
+    SwipeDown,
+    SwipeLeft,
+    SwipeRight,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl TryFrom<u32> for Gesture {
+    type Error = ErrorCode;
+
+    fn try_from(value: u32) -> Result<Gesture, ErrorCode> {
+        match value {
+            1 => Ok(Gesture::SwipeUp),
+            2 => Ok(Gesture::SwipeDown),
+            3 => Ok(Gesture::SwipeLeft),
+            4 => Ok(Gesture::SwipeRight),
+            5 => Ok(Gesture::ZoomIn),
+            6 => Ok(Gesture::ZoomOut),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+/// A wrapper around a closure to be registered and called when
+/// a single-touch event occurs. Events with an unknown status are ignored.
+///
+/// ```ignore
+/// let listener = TouchListener(|event| {
+///     // make use of the event
+/// });
+/// ```
+pub struct TouchListener<F: Fn(TouchEvent)>(pub F);
+
+impl<F: Fn(TouchEvent)> Upcall<OneId<DRIVER_NUM, { subscribe::SINGLE_TOUCH }>>
+    for TouchListener<F>
+{
+    fn upcall(&self, status: u32, position: u32, pressure_size: u32) {
+        if let Ok(status) = TouchStatus::try_from(status) {
+            self.0(TouchEvent {
+                id: 0,
+                status,
+                x: (position >> 16) as u16,
+                y: position as u16,
+                size: pressure_size as u16,
+                pressure: (pressure_size >> 16) as u16,
+            })
+        }
+    }
+}
+
+/// A wrapper around a closure to be registered and called when
+/// a gesture is recognized. Unknown gestures are ignored.
+///
+/// ```ignore
+/// let listener = GestureListener(|gesture| {
+///     // make use of the gesture
+/// });
+/// ```
+pub struct GestureListener<F: Fn(Gesture)>(pub F);
+
+impl<F: Fn(Gesture)> Upcall<OneId<DRIVER_NUM, { subscribe::GESTURE }>> for GestureListener<F> {
+    fn upcall(&self, gesture: u32, _arg1: u32, _arg2: u32) {
+        if let Ok(gesture) = Gesture::try_from(gesture) {
+            self.0(gesture)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90002;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const ACK_MULTI_TOUCH: u32 = 10;
+    pub const NUMBER_OF_TOUCHES: u32 = 100;
+}
+
+mod subscribe {
+    pub const SINGLE_TOUCH: u32 = 0;
+    pub const GESTURE: u32 = 1;
+    pub const MULTI_TOUCH: u32 = 2;
+}
+
+mod allow_rw {
+    pub const MULTI_TOUCH: u32 = 2;
+}
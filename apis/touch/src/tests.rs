@@ -0,0 +1,252 @@
+extern crate std;
+
+use super::*;
+use libtock_platform::YieldNoWaitReturn;
+use libtock_unittest::fake::{RecordedTouch, TouchPoint};
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+use std::vec;
+use std::vec::Vec;
+
+type Touch = super::Touch<fake::Syscalls>;
+
+fn point(id: u8, status: u8, x: u16, y: u16) -> TouchPoint {
+    TouchPoint {
+        id,
+        status,
+        x,
+        y,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Touch::driver_check());
+    assert_eq!(Touch::number_of_touches(), Err(ErrorCode::NoDevice));
+    assert_eq!(
+        Touch::read_multi_touch(&mut [0; 8]),
+        Err(ErrorCode::NoDevice)
+    );
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(2);
+    kernel.add_driver(&driver);
+
+    assert!(Touch::driver_check());
+    assert_eq!(Touch::number_of_touches(), Ok(2));
+}
+
+#[test]
+fn single_touch() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(1);
+    kernel.add_driver(&driver);
+    driver.replay([
+        RecordedTouch::Touch(point(0, 1, 10, 20)),
+        RecordedTouch::Touch(TouchPoint {
+            size: 3,
+            pressure: 40,
+            ..point(0, 2, 15, 25)
+        }),
+        // Unknown status, which is ignored.
+        RecordedTouch::Touch(point(0, 3, 0, 0)),
+        RecordedTouch::Touch(point(0, 0, 15, 25)),
+    ]);
+
+    let events = core::cell::RefCell::new(Vec::new());
+    let listener = TouchListener(|event| events.borrow_mut().push(event));
+    share::scope(|subscribe| {
+        assert_eq!(Touch::register_listener(&listener, subscribe), Ok(()));
+        for _ in 0..4 {
+            fake::Syscalls::yield_wait();
+        }
+    });
+    let touch = |status, x, y| TouchEvent {
+        id: 0,
+        status,
+        x,
+        y,
+        size: 0,
+        pressure: 0,
+    };
+    assert_eq!(
+        *events.borrow(),
+        [
+            touch(TouchStatus::Pressed, 10, 20),
+            TouchEvent {
+                size: 3,
+                pressure: 40,
+                ..touch(TouchStatus::Moved, 15, 25)
+            },
+            touch(TouchStatus::Released, 15, 25),
+        ]
+    );
+}
+
+#[test]
+fn unregister_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(1);
+    kernel.add_driver(&driver);
+    driver.replay([
+        RecordedTouch::Touch(point(0, 1, 10, 20)),
+        RecordedTouch::Gesture(1),
+    ]);
+
+    let listener = TouchListener(|_| panic!("Unexpected touch"));
+    let gesture = core::cell::Cell::new(None);
+    let gesture_listener = GestureListener(|g| gesture.set(Some(g)));
+    share::scope::<
+        (
+            Subscribe<_, DRIVER_NUM, { subscribe::SINGLE_TOUCH }>,
+            Subscribe<_, DRIVER_NUM, { subscribe::GESTURE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (touch, gestures) = handle.split();
+        assert_eq!(Touch::register_listener(&listener, touch), Ok(()));
+        assert_eq!(
+            Touch::register_gesture_listener(&gesture_listener, gestures),
+            Ok(())
+        );
+        Touch::unregister_listener();
+        fake::Syscalls::yield_wait();
+    });
+    assert_eq!(gesture.get(), Some(Gesture::SwipeUp));
+    assert_eq!(driver.pending_events(), 0);
+}
+
+#[test]
+fn gestures() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(2);
+    kernel.add_driver(&driver);
+    driver.replay([
+        RecordedTouch::Gesture(3),
+        RecordedTouch::Gesture(0),
+        RecordedTouch::Gesture(5),
+    ]);
+
+    let gestures = core::cell::RefCell::new(Vec::new());
+    let listener = GestureListener(|gesture| gestures.borrow_mut().push(gesture));
+    share::scope(|subscribe| {
+        assert_eq!(
+            Touch::register_gesture_listener(&listener, subscribe),
+            Ok(())
+        );
+        for _ in 0..3 {
+            fake::Syscalls::yield_wait();
+        }
+        Touch::unregister_gesture_listener();
+    });
+    assert_eq!(*gestures.borrow(), [Gesture::SwipeLeft, Gesture::ZoomIn]);
+}
+
+#[test]
+fn multi_touch() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(2);
+    kernel.add_driver(&driver);
+    driver.replay([
+        // Single-touch events are dropped while no listener is registered.
+        RecordedTouch::Touch(point(0, 1, 1, 1)),
+        RecordedTouch::MultiTouch(vec![point(0, 1, 100, 200), point(1, 1, 300, 400)]),
+        RecordedTouch::MultiTouch(vec![
+            TouchPoint {
+                size: 7,
+                pressure: 8,
+                ..point(0, 2, 110, 210)
+            },
+            point(1, 0, 300, 400),
+            // Does not fit in the buffer.
+            point(2, 1, 0, 0),
+        ]),
+    ]);
+
+    let mut buffer = [0; 2 * MULTI_TOUCH_RECORD_LEN];
+    let touches = Touch::read_multi_touch(&mut buffer).unwrap();
+    assert_eq!(touches.dropped_events(), 0);
+    assert!(touches.touches().eq([
+        TouchEvent {
+            id: 0,
+            status: TouchStatus::Pressed,
+            x: 100,
+            y: 200,
+            size: 0,
+            pressure: 0,
+        },
+        TouchEvent {
+            id: 1,
+            status: TouchStatus::Pressed,
+            x: 300,
+            y: 400,
+            size: 0,
+            pressure: 0,
+        },
+    ]));
+
+    let touches = Touch::read_multi_touch(&mut buffer).unwrap();
+    assert!(touches.touches().eq([
+        TouchEvent {
+            id: 0,
+            status: TouchStatus::Moved,
+            x: 110,
+            y: 210,
+            size: 7,
+            pressure: 8,
+        },
+        TouchEvent {
+            id: 1,
+            status: TouchStatus::Released,
+            x: 300,
+            y: 400,
+            size: 0,
+            pressure: 0,
+        },
+    ]));
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+}
+
+#[test]
+fn failed_ack() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new(2);
+    kernel.add_driver(&driver);
+    driver.replay([RecordedTouch::MultiTouch(vec![point(0, 1, 100, 200)])]);
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::MULTI_TOUCH,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::MULTI_TOUCH,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWait { skip_upcall: false });
+    // The buffer is unallowed and the upcall unsubscribed before acknowledging.
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::MULTI_TOUCH,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::MULTI_TOUCH,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::ACK_MULTI_TOUCH,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Fail)),
+    });
+
+    assert_eq!(Touch::read_multi_touch(&mut [0; 8]), Err(ErrorCode::Fail));
+}
@@ -0,0 +1,259 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use libtock_executor::{block_on, UpcallFuture, UpcallListener};
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The UDP driver, which sends and receives UDP packets over the kernel's
+/// network stack (e.g. 6LoWPAN).
+///
+/// The kernel only delivers packets to a process that has bound a port with
+/// `bind`, and uses the bound port as the source port of the packets the
+/// process sends. A process can only bind a single port at a time.
+///
+/// # Example
+/// ```ignore
+/// use libtock2::udp::Udp;
+///
+/// Udp::bind(4000).unwrap();
+/// let mut buffer = [0; 64];
+/// let (len, source) = Udp::recv_from(&mut buffer).unwrap();
+/// Udp::send_to(source.addr, source.port, &buffer[..len]).unwrap();
+/// ```
+pub struct Udp<
+    S: Syscalls,
+    C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config = DefaultConfig,
+>(S, C);
+
+impl<
+        S: Syscalls,
+        C: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config,
+    > Udp<S, C>
+{
+    /// Run a check against the udp capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working, as it may still fail to allocate grant
+    /// memory.
+    #[inline(always)]
+    pub fn driver_check() -> bool {
+        S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).is_success()
+    }
+
+    /// Returns the size of the largest payload the kernel can send.
+    pub fn max_payload_len() -> Result<usize, ErrorCode> {
+        S::command(DRIVER_NUM, command::MAX_PAYLOAD_LEN, 0, 0)
+            .to_result::<u32, _>()
+            .map(|len| len as usize)
+    }
+
+    /// Binds the process to `port` on all of the kernel's interfaces, replacing
+    /// any previous binding. Fails with `ErrorCode::Busy` if another process
+    /// has bound `port`.
+    pub fn bind(port: u16) -> Result<(), ErrorCode> {
+        let endpoint = SocketAddr {
+            addr: UNSPECIFIED_ADDR,
+            port,
+        }
+        .encode();
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::CONFIG }>, _, _>(|allow_ro| {
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::CONFIG }>(allow_ro, &endpoint)?;
+            S::command(DRIVER_NUM, command::BIND, 0, 0).to_result()
+        })
+    }
+
+    /// Sends `payload` to the given IPv6 address and port, and waits until it
+    /// has been transmitted. The process must have bound a port with `bind`.
+    pub fn send_to(addr: [u8; 16], port: u16, payload: &[u8]) -> Result<(), ErrorCode> {
+        let destination = SocketAddr { addr, port }.encode();
+        let done = Cell::new(Option::<(u32,)>::None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::PAYLOAD }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::CONFIG }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::SENT }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_payload, allow_config, subscribe) = handle.split();
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::PAYLOAD }>(allow_payload, payload)?;
+
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::CONFIG }>(allow_config, &destination)?;
+
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SENT }>(subscribe, &done)?;
+
+            S::command(DRIVER_NUM, command::SEND, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status,)) = done.get() {
+                    return match ErrorCode::try_from(status) {
+                        Ok(error) => Err(error),
+                        Err(_) => Ok(()),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Waits for a packet addressed to the bound port, copies its payload into
+    /// `buffer`, and returns the number of bytes copied and the packet's
+    /// source. Payloads larger than `buffer` are truncated.
+    pub fn recv_from(buffer: &mut [u8]) -> Result<(usize, SocketAddr), ErrorCode> {
+        let listener = UpcallListener::new();
+        let mut addresses = PacketAddresses::new();
+        let len = share::scope::<RecvHandles<S>, _, _>(|handle| {
+            let future = Self::recv_from_async(buffer, &mut addresses, &listener, handle)?;
+            Ok(block_on::<S, _>(future))
+        })?;
+        Ok((len, addresses.source()))
+    }
+
+    /// Shares `buffer` and `addresses` with the kernel and returns a future
+    /// that completes with the number of bytes of the next packet received on
+    /// the bound port that were copied into `buffer`. Once `handle`'s scope has
+    /// ended, the packet's payload can be read from `buffer` and its source
+    /// and destination from `addresses`.
+    pub fn recv_from_async<'share>(
+        buffer: &'share mut [u8],
+        addresses: &'share mut PacketAddresses,
+        listener: &'share UpcallListener,
+        handle: Handle<RecvHandles<'share, S>>,
+    ) -> Result<RecvFromFuture<'share>, ErrorCode> {
+        let (allow_payload, allow_addresses, subscribe) = handle.split();
+        let buffer_len = buffer.len();
+        S::allow_rw::<C, DRIVER_NUM, { allow_rw::PAYLOAD }>(allow_payload, buffer)?;
+        S::allow_rw::<C, DRIVER_NUM, { allow_rw::ADDRESSES }>(allow_addresses, &mut addresses.0)?;
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::RECEIVED }>(subscribe, listener)?;
+        Ok(RecvFromFuture {
+            upcall: listener.wait(),
+            buffer_len,
+        })
+    }
+}
+
+/// The handles `Udp::recv_from_async` needs to share its buffers and subscribe
+/// to the receive upcall.
+pub type RecvHandles<'share, S> = (
+    AllowRw<'share, S, DRIVER_NUM, { allow_rw::PAYLOAD }>,
+    AllowRw<'share, S, DRIVER_NUM, { allow_rw::ADDRESSES }>,
+    Subscribe<'share, S, DRIVER_NUM, { subscribe::RECEIVED }>,
+);
+
+/// The future returned by `Udp::recv_from_async`, which completes with the
+/// number of bytes of the received payload that fit in the receive buffer.
+pub struct RecvFromFuture<'share> {
+    upcall: UpcallFuture<'share>,
+    buffer_len: usize,
+}
+
+impl<'share> Future for RecvFromFuture<'share> {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<usize> {
+        let buffer_len = self.buffer_len;
+        // The kernel reports the full length of the payload, which may exceed
+        // the buffer if the payload was truncated.
+        Pin::new(&mut self.upcall)
+            .poll(context)
+            .map(|(len, _, _)| (len as usize).min(buffer_len))
+    }
+}
+
+/// An IPv6 address and UDP port.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SocketAddr {
+    pub addr: [u8; 16],
+    pub port: u16,
+}
+
+impl SocketAddr {
+    // Encodes the address the way the kernel expects it: the IPv6 address
+    // followed by the port in network byte order.
+    fn encode(&self) -> [u8; SOCKET_ADDR_LEN] {
+        let mut bytes = [0; SOCKET_ADDR_LEN];
+        bytes[..16].copy_from_slice(&self.addr);
+        bytes[16..].copy_from_slice(&self.port.to_be_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> SocketAddr {
+        let mut addr = [0; 16];
+        addr.copy_from_slice(&bytes[..16]);
+        SocketAddr {
+            addr,
+            port: u16::from_be_bytes([bytes[16], bytes[17]]),
+        }
+    }
+}
+
+/// The source and destination of a received packet, which the kernel writes
+/// when it delivers the packet.
+pub struct PacketAddresses([u8; 2 * SOCKET_ADDR_LEN]);
+
+impl PacketAddresses {
+    pub const fn new() -> PacketAddresses {
+        PacketAddresses([0; 2 * SOCKET_ADDR_LEN])
+    }
+
+    pub fn source(&self) -> SocketAddr {
+        SocketAddr::decode(&self.0[..SOCKET_ADDR_LEN])
+    }
+
+    pub fn destination(&self) -> SocketAddr {
+        SocketAddr::decode(&self.0[SOCKET_ADDR_LEN..])
+    }
+}
+
+impl Default for PacketAddresses {
+    fn default() -> PacketAddresses {
+        PacketAddresses::new()
+    }
+}
+
+const SOCKET_ADDR_LEN: usize = 18;
+
+// The IPv6 unspecified address (::), which binds to every interface.
+const UNSPECIFIED_ADDR: [u8; 16] = [0; 16];
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30002;
+
+// Command IDs
+mod command {
+    pub const DRIVER_CHECK: u32 = 0;
+    pub const SEND: u32 = 2;
+    pub const BIND: u32 = 3;
+    pub const MAX_PAYLOAD_LEN: u32 = 4;
+}
+
+mod subscribe {
+    pub const RECEIVED: u32 = 0;
+    pub const SENT: u32 = 1;
+}
+
+mod allow_ro {
+    pub const PAYLOAD: u32 = 0;
+    pub const CONFIG: u32 = 1;
+}
+
+mod allow_rw {
+    pub const PAYLOAD: u32 = 0;
+    pub const ADDRESSES: u32 = 1;
+}
@@ -0,0 +1,159 @@
+use super::*;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{command_return, fake, ExpectedSyscall};
+
+type Udp = super::Udp<fake::Syscalls>;
+
+const REMOTE: [u8; 16] = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Udp::driver_check());
+    assert_eq!(Udp::bind(1000), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+
+    assert!(Udp::driver_check());
+}
+
+#[test]
+fn bind() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+    let _other_process = driver.add_socket(driver.address(), 2000);
+
+    assert_eq!(Udp::bind(2000), Err(ErrorCode::Busy));
+    assert_eq!(Udp::bind(1000), Ok(()));
+    assert_eq!(driver.bound_port(), Some(1000));
+}
+
+#[test]
+fn send_and_receive() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+    let remote = driver.add_socket(REMOTE, 5683);
+
+    assert_eq!(
+        Udp::send_to(REMOTE, 5683, b"early"),
+        Err(ErrorCode::Invalid)
+    );
+    Udp::bind(1000).unwrap();
+    assert_eq!(Udp::send_to(REMOTE, 5683, b"request"), Ok(()));
+    assert_eq!(
+        remote.recv_from(),
+        Some((driver.address(), 1000, b"request".to_vec()))
+    );
+
+    remote.send_to(driver.address(), 1000, b"response");
+    let mut buffer = [0; 16];
+    let (len, source) = Udp::recv_from(&mut buffer).unwrap();
+    assert_eq!(buffer[..len], *b"response");
+    assert_eq!(
+        source,
+        SocketAddr {
+            addr: REMOTE,
+            port: 5683
+        }
+    );
+
+    // Payloads that exceed the buffer are truncated.
+    remote.send_to(driver.address(), 1000, b"truncated");
+    assert_eq!(Udp::recv_from(&mut buffer[..5]).unwrap().0, 5);
+    assert_eq!(buffer[..5], *b"trunc");
+
+    // Packets sent to the process' own port are looped back.
+    Udp::send_to(driver.address(), 1000, b"self").unwrap();
+    let (len, source) = Udp::recv_from(&mut buffer).unwrap();
+    assert_eq!(buffer[..len], *b"self");
+    assert_eq!(source.port, 1000);
+}
+
+#[test]
+fn max_payload_len() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+    driver.set_max_payload_len(4);
+
+    Udp::bind(1000).unwrap();
+    assert_eq!(Udp::max_payload_len(), Ok(4));
+    assert_eq!(Udp::send_to(REMOTE, 1, b"12345"), Err(ErrorCode::Size));
+}
+
+#[test]
+fn recv_from_async() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+    let remote = driver.add_socket(REMOTE, 9);
+    Udp::bind(1000).unwrap();
+
+    let mut buffer = [0; 8];
+    let mut addresses = PacketAddresses::new();
+    let listener = UpcallListener::new();
+    let len = share::scope::<RecvHandles<_>, _, _>(|handle| {
+        let future = Udp::recv_from_async(&mut buffer, &mut addresses, &listener, handle).unwrap();
+        remote.send_to(driver.address(), 1000, b"async");
+        block_on::<fake::Syscalls, _>(future)
+    });
+    assert_eq!(len, 5);
+    assert_eq!(buffer[..5], *b"async");
+    assert_eq!(addresses.source().addr, REMOTE);
+    assert_eq!(addresses.source().port, 9);
+    assert_eq!(
+        addresses.destination(),
+        SocketAddr {
+            addr: driver.address(),
+            port: 1000
+        }
+    );
+
+    // The length of a truncated payload is clamped to the buffer.
+    let len = share::scope::<RecvHandles<_>, _, _>(|handle| {
+        let future = Udp::recv_from_async(&mut buffer, &mut addresses, &listener, handle).unwrap();
+        remote.send_to(driver.address(), 1000, b"truncated");
+        block_on::<fake::Syscalls, _>(future)
+    });
+    assert_eq!(len, 8);
+    assert_eq!(buffer, *b"truncate");
+}
+
+#[test]
+fn failed_send() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Udp::new();
+    kernel.add_driver(&driver);
+    Udp::bind(1000).unwrap();
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::PAYLOAD,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::CONFIG,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::SENT,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::SEND,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Off)),
+    });
+
+    assert_eq!(Udp::send_to(REMOTE, 1, b"data"), Err(ErrorCode::Off));
+}
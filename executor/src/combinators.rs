@@ -0,0 +1,112 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Returns a future that runs `a` and `b` concurrently and completes with both
+/// of their outputs once both have completed.
+pub fn join<A: Future, B: Future>(a: A, b: B) -> Join<A, B> {
+    Join {
+        a: MaybeDone::Pending(a),
+        b: MaybeDone::Pending(b),
+    }
+}
+
+/// Returns a future that runs `a` and `b` concurrently and completes with the
+/// output of whichever completes first. The other future is dropped without
+/// being completed. If both are ready at the same time, `a` wins.
+pub fn select<A: Future, B: Future>(a: A, b: B) -> Select<A, B> {
+    Select { a, b }
+}
+
+/// The output of `select`, indicating which future completed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Either<A, B> {
+    First(A),
+    Second(B),
+}
+
+/// The future returned by `join`.
+pub struct Join<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        // Safety: a and b are structurally pinned: they are never moved out of
+        // self while they contain a future.
+        let this = unsafe { self.get_unchecked_mut() };
+        let a_done = unsafe { Pin::new_unchecked(&mut this.a) }.poll(context);
+        let b_done = unsafe { Pin::new_unchecked(&mut this.b) }.poll(context);
+        if !(a_done && b_done) {
+            return Poll::Pending;
+        }
+        Poll::Ready((this.a.take(), this.b.take()))
+    }
+}
+
+/// The future returned by `select`.
+pub struct Select<A: Future, B: Future> {
+    a: A,
+    b: B,
+}
+
+impl<A: Future, B: Future> Future for Select<A, B> {
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        // Safety: a and b are structurally pinned: they are never moved out of
+        // self.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.a) }.poll(context) {
+            return Poll::Ready(Either::First(output));
+        }
+        if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.b) }.poll(context) {
+            return Poll::Ready(Either::Second(output));
+        }
+        Poll::Pending
+    }
+}
+
+// A future that is either still running or has completed and stored its
+// output.
+enum MaybeDone<F: Future> {
+    Pending(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    // Polls the future if it is still running. Returns true if the future has
+    // completed.
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> bool {
+        // Safety: The future is structurally pinned: it is only dropped in
+        // place (by Pin::set), never moved.
+        let output = match unsafe { self.as_mut().get_unchecked_mut() } {
+            MaybeDone::Pending(future) => {
+                match unsafe { Pin::new_unchecked(future) }.poll(context) {
+                    Poll::Ready(output) => output,
+                    Poll::Pending => return false,
+                }
+            }
+            _ => return true,
+        };
+        self.set(MaybeDone::Done(output));
+        true
+    }
+
+    // Takes the output out of a completed future. Panics if the future has
+    // not completed or its output was already taken. The state is checked
+    // before replacing self so that a pinned future is never moved.
+    fn take(&mut self) -> F::Output {
+        if !matches!(self, MaybeDone::Done(_)) {
+            panic!("Join polled after completion");
+        }
+        match core::mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(output) => output,
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! A minimal, single-threaded async executor for Tock processes.
+//!
+//! Tock processes receive events through upcalls, which the kernel only runs
+//! while the process is in Yield. `block_on` runs a future to completion by
+//! alternating between polling the future and calling Yield-Wait, so the
+//! future is re-polled after every upcall. `UpcallListener` turns an upcall
+//! into a future, and `join` and `select` allow a process to wait on several
+//! upcalls at once.
+//!
+//! The futures in this crate never make system calls themselves: upcalls are
+//! subscribed within a `share::scope` as usual, so the scope's soundness
+//! guarantees are unaffected by how (or whether) the futures are polled.
+//!
+//! # Example
+//! ```ignore
+//! use libtock_executor::{block_on, select, Either, UpcallListener};
+//!
+//! let button = UpcallListener::new();
+//! let alarm = UpcallListener::new();
+//! type Subscriptions = (Subscribe<TockSyscalls, BUTTONS, 0>, Subscribe<TockSyscalls, ALARM, 0>);
+//! share::scope::<Subscriptions, _, _>(|handle| {
+//!     let (button_subscribe, alarm_subscribe) = handle.split();
+//!     button.subscribe(button_subscribe).unwrap();
+//!     alarm.subscribe(alarm_subscribe).unwrap();
+//!     // Start the alarm and enable button interrupts here.
+//!     match block_on::<TockSyscalls, _>(select(button.wait(), alarm.wait())) {
+//!         Either::First((button, state, _)) => { /* A button changed */ }
+//!         Either::Second(_) => { /* Timed out */ }
+//!     }
+//! });
+//! ```
+
+#![no_std]
+#![warn(unsafe_op_in_unsafe_fn)]
+
+mod combinators;
+mod upcall_future;
+
+pub use combinators::{join, select, Either, Join, Select};
+pub use upcall_future::{UpcallFuture, UpcallListener};
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use libtock_platform::Syscalls;
+
+/// Runs `future` to completion, calling Yield-Wait whenever it is pending.
+///
+/// Because upcalls only run during Yield, the future is polled again after
+/// every upcall, whether or not the upcall woke the future's waker.
+pub fn block_on<S: Syscalls, F: Future>(future: F) -> F::Output {
+    let mut future = future;
+    // Safety: future is shadowed, so it cannot be moved after being pinned.
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        S::yield_wait();
+    }
+}
+
+// block_on re-polls its future after every Yield, so its waker does not need
+// to do anything.
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    // Safety: None of the vtable's functions access the data pointer, so they
+    // are trivially thread-safe and valid for any pointer.
+    unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+}
+
+#[cfg(test)]
+mod tests;
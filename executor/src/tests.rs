@@ -0,0 +1,140 @@
+use super::*;
+use libtock_alarm::Ticks;
+use libtock_platform::{share, DefaultConfig, Subscribe, Syscalls, Upcall};
+use libtock_unittest::fake;
+
+type Alarm = libtock_alarm::Alarm<fake::Syscalls>;
+type Buttons = libtock_buttons::Buttons<fake::Syscalls>;
+
+// Driver numbers of the alarm and buttons drivers, which the tests wait on.
+const ALARM: u32 = 0;
+const BUTTONS: u32 = 3;
+
+#[test]
+fn ready() {
+    let kernel = fake::Kernel::new();
+    assert_eq!(block_on::<fake::Syscalls, _>(async { 5 }), 5);
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn upcall() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+
+    let listener = UpcallListener::new();
+    share::scope::<Subscribe<fake::Syscalls, ALARM, 0>, _, _>(|subscribe| {
+        listener.subscribe(subscribe).unwrap();
+        Alarm::set_relative(Ticks(10)).unwrap();
+        assert_eq!(block_on::<fake::Syscalls, _>(listener.wait()), (10, 10, 0));
+
+        // An upcall received before the future is polled completes it
+        // immediately.
+        Alarm::set_relative(Ticks(5)).unwrap();
+        alarm.advance(5);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(block_on::<fake::Syscalls, _>(listener.wait()), (15, 15, 0));
+        assert_eq!(listener.take(), None);
+    });
+}
+
+#[test]
+fn select_and_join() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    let buttons = fake::Buttons::<2>::new();
+    kernel.add_driver(&alarm);
+    kernel.add_driver(&buttons);
+
+    let alarm_listener = UpcallListener::new();
+    let button_listener = UpcallListener::new();
+    share::scope::<
+        (
+            Subscribe<fake::Syscalls, ALARM, 0>,
+            Subscribe<fake::Syscalls, BUTTONS, 0>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (alarm_subscribe, button_subscribe) = handle.split();
+        alarm_listener.subscribe(alarm_subscribe).unwrap();
+        button_listener
+            .subscribe_with_config::<DefaultConfig, fake::Syscalls, BUTTONS, 0>(button_subscribe)
+            .unwrap();
+        Buttons::enable_interrupts(1).unwrap();
+        Alarm::set_relative(Ticks(100)).unwrap();
+
+        // The button is pressed before the alarm expires.
+        buttons.set_pressed(1, true).unwrap();
+        assert_eq!(
+            block_on::<fake::Syscalls, _>(select(button_listener.wait(), alarm_listener.wait())),
+            Either::First((1, 1, 0))
+        );
+        assert_eq!(alarm.now(), 0);
+
+        // Nothing happens to the button, so the alarm expires.
+        assert_eq!(
+            block_on::<fake::Syscalls, _>(select(button_listener.wait(), alarm_listener.wait())),
+            Either::Second((100, 100, 0))
+        );
+
+        // join waits for both.
+        Alarm::set_relative(Ticks(100)).unwrap();
+        buttons.set_pressed(1, false).unwrap();
+        assert_eq!(
+            block_on::<fake::Syscalls, _>(join(button_listener.wait(), alarm_listener.wait())),
+            ((1, 0, 0), (200, 200, 0))
+        );
+    });
+}
+
+#[test]
+fn async_fn() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+
+    let listener = UpcallListener::new();
+    share::scope::<Subscribe<fake::Syscalls, ALARM, 0>, _, _>(|subscribe| {
+        listener.subscribe(subscribe).unwrap();
+        let ticks = block_on::<fake::Syscalls, _>(async {
+            let mut expirations = [0; 3];
+            for expiration in &mut expirations {
+                Alarm::set_relative(Ticks(10)).unwrap();
+                *expiration = listener.wait().await.1;
+            }
+            expirations
+        });
+        assert_eq!(ticks, [10, 20, 30]);
+    });
+}
+
+#[test]
+fn wake() {
+    extern crate std;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    struct Flag(AtomicBool);
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(flag.clone());
+    let mut context = Context::from_waker(&waker);
+    let listener = UpcallListener::new();
+    let mut future = listener.wait();
+    assert_eq!(Pin::new(&mut future).poll(&mut context), Poll::Pending);
+    assert!(!flag.0.load(Ordering::Relaxed));
+    listener.upcall(1, 2, 3);
+    assert!(flag.0.load(Ordering::Relaxed));
+    assert_eq!(
+        Pin::new(&mut future).poll(&mut context),
+        Poll::Ready((1, 2, 3))
+    );
+}
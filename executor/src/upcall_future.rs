@@ -0,0 +1,91 @@
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use libtock_platform::share::Handle;
+use libtock_platform::subscribe::{self, AnyId};
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+/// An upcall that can be awaited. `UpcallListener` records the arguments of
+/// the most recent upcall it received, and `wait` returns a future that
+/// completes once an upcall has been received.
+#[derive(Default)]
+pub struct UpcallListener {
+    args: Cell<Option<(u32, u32, u32)>>,
+    waker: Cell<Option<Waker>>,
+}
+
+impl UpcallListener {
+    pub const fn new() -> UpcallListener {
+        UpcallListener {
+            args: Cell::new(None),
+            waker: Cell::new(None),
+        }
+    }
+
+    /// Subscribes this listener to the upcall with the given ID, using the
+    /// default subscribe configuration.
+    pub fn subscribe<'share, S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32>(
+        &'share self,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, SUBSCRIBE_NUM>>,
+    ) -> Result<(), ErrorCode> {
+        self.subscribe_with_config::<DefaultConfig, S, DRIVER_NUM, SUBSCRIBE_NUM>(subscribe)
+    }
+
+    /// Subscribes this listener to the upcall with the given ID, using the
+    /// subscribe configuration `C`. Drivers that take a configuration type
+    /// should pass theirs through.
+    pub fn subscribe_with_config<
+        'share,
+        C: subscribe::Config,
+        S: Syscalls,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+    >(
+        &'share self,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, SUBSCRIBE_NUM>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, SUBSCRIBE_NUM>(subscribe, self)
+    }
+
+    /// Returns a future that completes with the arguments of the next upcall
+    /// (or of an upcall that has been received but not yet awaited).
+    pub fn wait(&self) -> UpcallFuture<'_> {
+        UpcallFuture { listener: self }
+    }
+
+    /// Returns the arguments of an upcall that has been received but not yet
+    /// awaited, if there is one, without waiting.
+    pub fn take(&self) -> Option<(u32, u32, u32)> {
+        self.args.take()
+    }
+}
+
+impl Upcall<AnyId> for UpcallListener {
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        self.args.set(Some((arg0, arg1, arg2)));
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A future that completes when an `UpcallListener` receives an upcall.
+/// Returned by `UpcallListener::wait`.
+pub struct UpcallFuture<'l> {
+    listener: &'l UpcallListener,
+}
+
+impl<'l> Future for UpcallFuture<'l> {
+    type Output = (u32, u32, u32);
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<(u32, u32, u32)> {
+        match self.listener.args.take() {
+            Some(args) => Poll::Ready(args),
+            None => {
+                self.listener.waker.set(Some(context.waker().clone()));
+                Poll::Pending
+            }
+        }
+    }
+}
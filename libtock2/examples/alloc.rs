@@ -0,0 +1,26 @@
+//! An example showing use of the `alloc` crate. Builds a `String` on the heap
+//! and prints it to the console. Requires the `alloc` feature.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use libtock2::console::Console;
+use libtock2::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x200}
+
+fn main() {
+    let squares: Vec<u32> = (1..=10).map(|i| i * i).collect();
+    let mut message = String::new();
+    for square in squares {
+        let _ = write!(message, "{} ", square);
+    }
+    message.push('\n');
+    let _ = Console::write(message.as_bytes());
+}
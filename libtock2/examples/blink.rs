@@ -0,0 +1,21 @@
+//! Blinks all the LEDs, toggling them every 250 milliseconds.
+
+#![no_main]
+#![no_std]
+
+use libtock2::alarm::{Alarm, Milliseconds};
+use libtock2::leds::Leds;
+use libtock2::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x200}
+
+fn main() {
+    let leds_count = Leds::count().unwrap_or(0);
+    loop {
+        for led_index in 0..leds_count {
+            let _ = Leds::toggle(led_index as u32);
+        }
+        let _ = Alarm::sleep_for(Milliseconds(250));
+    }
+}
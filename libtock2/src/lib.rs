@@ -1,3 +1,4 @@
+#![cfg_attr(feature = "alloc", feature(alloc_error_handler))]
 #![forbid(unsafe_code)]
 #![no_std]
 
@@ -6,20 +7,194 @@ extern crate libtock_debug_panic;
 pub use libtock_platform as platform;
 pub use libtock_runtime as runtime;
 
+#[cfg(feature = "alloc")]
+pub mod alloc {
+    use libtock_alloc as alloc;
+    pub type BrkAllocator = alloc::BrkAllocator<super::runtime::TockSyscalls>;
+
+    #[global_allocator]
+    static ALLOCATOR: BrkAllocator = BrkAllocator::new();
+
+    #[alloc_error_handler]
+    fn alloc_error_handler(layout: core::alloc::Layout) -> ! {
+        alloc::handle_alloc_error::<super::runtime::TockSyscalls>(layout)
+    }
+}
+
+pub mod adc {
+    use libtock_adc as adc;
+    pub type Adc = adc::Adc<super::runtime::TockSyscalls>;
+    pub use adc::Samples;
+}
+pub mod aes {
+    use libtock_aes as aes;
+    pub type Aes = aes::Aes<super::runtime::TockSyscalls>;
+    pub use aes::{AesMode, Direction, BLOCK_LEN, KEY_LEN};
+}
+pub mod alarm {
+    use libtock_alarm as alarm;
+    pub type Alarm = alarm::Alarm<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-hal")]
+    pub type Delay = alarm::Delay<super::runtime::TockSyscalls>;
+    pub use alarm::{AlarmListener, Convert, Hz, Milliseconds, Ticks};
+}
+pub mod ambient_light {
+    use libtock_ambient_light as ambient_light;
+    pub type AmbientLight = ambient_light::AmbientLight<super::runtime::TockSyscalls>;
+    pub use ambient_light::Lux;
+}
+pub mod ble {
+    use libtock_ble as ble;
+    pub type Ble = ble::Ble<super::runtime::TockSyscalls>;
+    pub use ble::{
+        flags, AdStructure, AdStructures, AdvertisingData, AdvertisingReport, Uuids16,
+        MAX_ADVERTISING_DATA_LEN,
+    };
+}
 pub mod buttons {
     use libtock_buttons as buttons;
     pub type Buttons = buttons::Buttons<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-hal")]
+    pub type Button = buttons::Button<super::runtime::TockSyscalls>;
+}
+pub mod buzzer {
+    use libtock_buzzer as buzzer;
+    pub type Buzzer = buzzer::Buzzer<super::runtime::TockSyscalls>;
+    pub use buzzer::{note, Note};
 }
 pub mod console {
     use libtock_console as console;
     pub type Console = console::Console<super::runtime::TockSyscalls>;
 }
+pub mod executor {
+    pub use executor::{join, select, Either, Join, Select, UpcallFuture, UpcallListener};
+    use libtock_executor as executor;
+
+    /// Runs `future` to completion, sleeping in Yield-Wait whenever it is
+    /// pending.
+    pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+        executor::block_on::<super::runtime::TockSyscalls, F>(future)
+    }
+}
+pub mod gpio {
+    use libtock_gpio as gpio;
+    pub type Gpio = gpio::Gpio<super::runtime::TockSyscalls>;
+    pub use gpio::{
+        EdgeFuture, GpioInterruptListener, GpioInterrupts, GpioState, PinInterruptEdge, PullDown,
+        PullNone, PullUp,
+    };
+}
+pub mod hmac {
+    use libtock_hmac as hmac;
+    pub type Hmac = hmac::Hmac<super::runtime::TockSyscalls>;
+    pub use hmac::HmacAlgorithm;
+}
+pub mod humidity {
+    use libtock_humidity as humidity;
+    pub type Humidity = humidity::Humidity<super::runtime::TockSyscalls>;
+    pub use humidity::CentiPercent;
+}
+pub mod i2c_master {
+    use libtock_i2c_master as i2c_master;
+    pub type I2cMaster = i2c_master::I2cMaster<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-hal")]
+    pub type I2cBus = i2c_master::I2cBus<super::runtime::TockSyscalls>;
+}
+pub mod ieee802154 {
+    use libtock_ieee802154 as ieee802154;
+    pub type Ieee802154 = ieee802154::Ieee802154<super::runtime::TockSyscalls>;
+    pub use ieee802154::{
+        Frame, RxRingBuffer, Security, SecurityLevel, BROADCAST_ADDRESS, MAX_FRAME_LEN,
+    };
+}
+pub mod ipc {
+    use libtock_ipc as ipc;
+    pub type Ipc = ipc::Ipc<super::runtime::TockSyscalls>;
+    pub use ipc::{IpcListener, IpcNotification, MAX_SERVICES};
+}
+pub mod kv {
+    use libtock_kv as kv;
+    pub type KeyValue = kv::KeyValue<super::runtime::TockSyscalls>;
+}
 pub mod leds {
     use libtock_leds as leds;
     pub type Leds = leds::Leds<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-hal")]
+    pub type Led = leds::Led<super::runtime::TockSyscalls>;
 }
 pub mod low_level_debug {
     use libtock_low_level_debug as lldb;
     pub type LowLevelDebug = lldb::LowLevelDebug<super::runtime::TockSyscalls>;
     pub use lldb::AlertCode;
 }
+pub mod ninedof {
+    use libtock_ninedof as ninedof;
+    pub type Ninedof = ninedof::Ninedof<super::runtime::TockSyscalls>;
+    pub use ninedof::NinedofData;
+}
+pub mod nonvolatile_storage {
+    use libtock_nonvolatile_storage as nonvolatile_storage;
+    pub type NonvolatileStorage =
+        nonvolatile_storage::NonvolatileStorage<super::runtime::TockSyscalls>;
+}
+pub mod proximity {
+    use libtock_proximity as proximity;
+    pub type Proximity = proximity::Proximity<super::runtime::TockSyscalls>;
+}
+pub mod pwm {
+    use libtock_pwm as pwm;
+    pub type Pwm = pwm::Pwm<super::runtime::TockSyscalls>;
+    pub use pwm::MAX_DUTY_CYCLE;
+}
+pub mod rng {
+    use libtock_rng as rng;
+    pub type Rng = rng::Rng<super::runtime::TockSyscalls>;
+    #[cfg(feature = "rand_core")]
+    pub type RngSource = rng::RngSource<super::runtime::TockSyscalls>;
+}
+pub mod screen {
+    use libtock_screen as screen;
+    pub type Screen = screen::Screen<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-graphics-core")]
+    pub type Display = screen::Display<super::runtime::TockSyscalls>;
+    pub use screen::{PixelFormat, Rotation};
+}
+pub mod sha {
+    use libtock_sha as sha;
+    pub type Sha = sha::Sha<super::runtime::TockSyscalls>;
+    pub use sha::ShaAlgorithm;
+}
+pub mod sound_pressure {
+    use libtock_sound_pressure as sound_pressure;
+    pub type SoundPressure = sound_pressure::SoundPressure<super::runtime::TockSyscalls>;
+    pub use sound_pressure::Decibels;
+}
+pub mod spi_controller {
+    use libtock_spi_controller as spi_controller;
+    pub type SpiController = spi_controller::SpiController<super::runtime::TockSyscalls>;
+    #[cfg(feature = "embedded-hal")]
+    pub type SpiBus = spi_controller::SpiBus<super::runtime::TockSyscalls>;
+    pub use spi_controller::{ClockPhase, ClockPolarity};
+}
+pub mod temperature {
+    use libtock_temperature as temperature;
+    pub type Temperature = temperature::Temperature<super::runtime::TockSyscalls>;
+    pub use temperature::CentiCelsius;
+}
+pub mod text_screen {
+    use libtock_text_screen as text_screen;
+    pub type TextScreen = text_screen::TextScreen<super::runtime::TockSyscalls>;
+}
+pub mod touch {
+    use libtock_touch as touch;
+    pub type Touch = touch::Touch<super::runtime::TockSyscalls>;
+    pub use touch::{
+        Gesture, GestureListener, MultiTouch, TouchEvent, TouchListener, TouchStatus,
+        MULTI_TOUCH_RECORD_LEN,
+    };
+}
+pub mod udp {
+    use libtock_udp as udp;
+    pub type Udp = udp::Udp<super::runtime::TockSyscalls>;
+    pub use udp::{PacketAddresses, RecvFromFuture, RecvHandles, SocketAddr};
+}
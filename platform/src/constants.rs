@@ -5,6 +5,21 @@ pub mod exit_id {
     pub const RESTART: u32 = 1;
 }
 
+pub mod memop_id {
+    pub const BRK: u32 = 0;
+    pub const SBRK: u32 = 1;
+    pub const MEMORY_START: u32 = 2;
+    pub const MEMORY_END: u32 = 3;
+    pub const FLASH_START: u32 = 4;
+    pub const FLASH_END: u32 = 5;
+    pub const GRANT_START: u32 = 6;
+    pub const FLASH_REGION_COUNT: u32 = 7;
+    pub const FLASH_REGION_START: u32 = 8;
+    pub const FLASH_REGION_END: u32 = 9;
+    pub const DEBUG_STACK_START: u32 = 10;
+    pub const DEBUG_HEAP_START: u32 = 11;
+}
+
 pub mod syscall_class {
     pub const SUBSCRIBE: usize = 1;
     pub const COMMAND: usize = 2;
@@ -77,7 +77,67 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// `unallow_ro` does nothing.
     fn unallow_ro(driver_num: u32, buffer_num: u32);
 
-    // TODO: Add memop() methods.
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+
+    /// Sets the process break to `address`. Returns `NoMem` if `address` lies
+    /// outside the memory the process is allowed to access.
+    ///
+    /// # Safety
+    /// Lowering the break revokes the process' access to the memory between
+    /// the new and the old break. The caller must ensure that no live object
+    /// is stored in that memory.
+    unsafe fn memop_brk(address: *const u8) -> Result<(), ErrorCode>;
+
+    /// Moves the process break by `increment` bytes and returns the previous
+    /// break. Returns `NoMem` if the new break would lie outside the memory the
+    /// process is allowed to access.
+    ///
+    /// # Safety
+    /// A negative `increment` revokes the process' access to the memory
+    /// between the new and the old break. The caller must ensure that no live
+    /// object is stored in that memory.
+    unsafe fn memop_sbrk(increment: i32) -> Result<*mut u8, ErrorCode>;
+
+    /// Returns the address of the start of the process' RAM region.
+    fn memop_memory_start() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the end of the process' RAM region. Note that
+    /// the end of the RAM region includes the grant region, which the process
+    /// cannot access.
+    fn memop_memory_end() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the start of the process' flash region.
+    fn memop_flash_start() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the end of the process' flash region.
+    fn memop_flash_end() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the lowest byte of the grant region. The process
+    /// break cannot be moved past this address.
+    fn memop_grant_start() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the number of writeable flash regions defined in the process'
+    /// TBF header.
+    fn memop_flash_region_count() -> Result<u32, ErrorCode>;
+
+    /// Returns the address of the start of the writeable flash region with the
+    /// given index.
+    fn memop_flash_region_start(region: u32) -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the end of the writeable flash region with the
+    /// given index.
+    fn memop_flash_region_end(region: u32) -> Result<*const u8, ErrorCode>;
+
+    /// Tells the kernel where the top of the process' stack is. This is only
+    /// used by the kernel to produce better debugging output (e.g. in fault
+    /// dumps).
+    fn memop_debug_stack_start(stack_top: *const u8) -> Result<(), ErrorCode>;
+
+    /// Tells the kernel where the process' heap starts. This is only used by
+    /// the kernel to produce better debugging output (e.g. in fault dumps).
+    fn memop_debug_heap_start(heap_start: *const u8) -> Result<(), ErrorCode>;
 
     // -------------------------------------------------------------------------
     // Exit
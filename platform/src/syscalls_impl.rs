@@ -1,9 +1,9 @@
 //! Implements `Syscalls` for all types that implement `RawSyscalls`.
 
 use crate::{
-    allow_ro, allow_rw, exit_id, exit_on_drop, return_variant, share, subscribe, syscall_class,
-    yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register, ReturnVariant,
-    Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
+    allow_ro, allow_rw, exit_id, exit_on_drop, memop_id, return_variant, share, subscribe,
+    syscall_class, yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register,
+    ReturnVariant, Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
 };
 
 impl<S: RawSyscalls> Syscalls for S {
@@ -352,6 +352,73 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+
+    unsafe fn memop_brk(address: *const u8) -> Result<(), ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop. The caller is responsible for making sure that moving the
+        // break does not revoke access to live objects.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([memop_id::BRK.into(), address.into()])
+        };
+        memop_result(registers).map(|_| ())
+    }
+
+    unsafe fn memop_sbrk(increment: i32) -> Result<*mut u8, ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop. The caller is responsible for making sure that moving the
+        // break does not revoke access to live objects.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([
+                memop_id::SBRK.into(),
+                (increment as isize as usize).into(),
+            ])
+        };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_memory_start() -> Result<*const u8, ErrorCode> {
+        memop_query::<Self>(memop_id::MEMORY_START).map(Into::into)
+    }
+
+    fn memop_memory_end() -> Result<*const u8, ErrorCode> {
+        memop_query::<Self>(memop_id::MEMORY_END).map(Into::into)
+    }
+
+    fn memop_flash_start() -> Result<*const u8, ErrorCode> {
+        memop_query::<Self>(memop_id::FLASH_START).map(Into::into)
+    }
+
+    fn memop_flash_end() -> Result<*const u8, ErrorCode> {
+        memop_query::<Self>(memop_id::FLASH_END).map(Into::into)
+    }
+
+    fn memop_grant_start() -> Result<*const u8, ErrorCode> {
+        memop_query::<Self>(memop_id::GRANT_START).map(Into::into)
+    }
+
+    fn memop_flash_region_count() -> Result<u32, ErrorCode> {
+        memop_query::<Self>(memop_id::FLASH_REGION_COUNT).map(Register::as_u32)
+    }
+
+    fn memop_flash_region_start(region: u32) -> Result<*const u8, ErrorCode> {
+        memop_with_argument::<Self>(memop_id::FLASH_REGION_START, region.into()).map(Into::into)
+    }
+
+    fn memop_flash_region_end(region: u32) -> Result<*const u8, ErrorCode> {
+        memop_with_argument::<Self>(memop_id::FLASH_REGION_END, region.into()).map(Into::into)
+    }
+
+    fn memop_debug_stack_start(stack_top: *const u8) -> Result<(), ErrorCode> {
+        memop_with_argument::<Self>(memop_id::DEBUG_STACK_START, stack_top.into()).map(|_| ())
+    }
+
+    fn memop_debug_heap_start(heap_start: *const u8) -> Result<(), ErrorCode> {
+        memop_with_argument::<Self>(memop_id::DEBUG_HEAP_START, heap_start.into()).map(|_| ())
+    }
+
     // -------------------------------------------------------------------------
     // Exit
     // -------------------------------------------------------------------------
@@ -383,3 +450,45 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 }
+
+// Invokes a Memop operation that does not take an argument. This is not
+// monomorphized over the operation to keep code size small.
+fn memop_query<S: RawSyscalls>(memop_id: u32) -> Result<Register, ErrorCode> {
+    // Safety: syscall1's documentation indicates it can be used to call Memop.
+    // The Memop operations that do not take an argument only return
+    // information about the process, and cannot trigger undefined behavior.
+    let registers = unsafe { S::syscall1::<{ syscall_class::MEMOP }>([memop_id.into()]) };
+    memop_result(registers)
+}
+
+// Invokes a Memop operation that takes an argument but does not move the
+// process break. This is not monomorphized over the operation to keep code size
+// small.
+fn memop_with_argument<S: RawSyscalls>(
+    memop_id: u32,
+    argument0: Register,
+) -> Result<Register, ErrorCode> {
+    // Safety: syscall2's documentation indicates it can be used to call Memop.
+    // This function is not used for brk and sbrk, and the remaining Memop
+    // operations cannot trigger undefined behavior.
+    let registers =
+        unsafe { S::syscall2::<{ syscall_class::MEMOP }>([memop_id.into(), argument0]) };
+    memop_result(registers)
+}
+
+// Converts the registers returned by a Memop call into a Result. The value is
+// returned as a Register rather than a u32 so that pointers returned by the
+// kernel keep their provenance.
+fn memop_result([r0, r1]: [Register; 2]) -> Result<Register, ErrorCode> {
+    let return_variant: ReturnVariant = r0.as_u32().into();
+    // TRD 104 guarantees that Memop returns either Failure, Success, or
+    // Success with U32. Success and Success with U32 are treated identically,
+    // as callers know which variant the operation they invoked returns.
+    if return_variant == return_variant::FAILURE {
+        // Safety: TRD 104 guarantees that if r0 is Failure, then r1 will
+        // contain a valid error code. ErrorCode is designed to be safely
+        // transmuted directly from a kernel error code.
+        return Err(unsafe { core::mem::transmute(r1.as_u32() as u16) });
+    }
+    Ok(r1)
+}
@@ -1,7 +1,7 @@
 //! Runtime components related to process startup.
 
 use crate::TockSyscalls;
-use libtock_platform::Termination;
+use libtock_platform::{Syscalls, Termination};
 
 // Include the correct `start` symbol (the program entry point) for the
 // architecture.
@@ -66,9 +66,23 @@ pub fn handle_main_return<T: Termination>(result: T) -> ! {
 // from start, which is written directly in assembly.
 #[no_mangle]
 extern "C" fn rust_start() -> ! {
-    // TODO: Call memop() to inform the kernel of the stack and heap sizes +
-    // locations. Also, perhaps we should support calling a heap initialization
-    // function?
+    extern "C" {
+        // Defined by libtock_layout.ld. _stack_top is the end of the
+        // .stack_buffer section and _heap_start is the initial process break
+        // (which start has already passed to the kernel using memop's brk
+        // operation). Only their addresses are meaningful.
+        static _stack_top: u8;
+        static _heap_start: u8;
+    }
+
+    // Tell the kernel where the stack and heap are, so that it can display
+    // accurate stack and heap regions when it dumps the process' state (e.g.
+    // after a fault). These calls only affect debugging output, so we ignore
+    // any errors they return.
+    unsafe {
+        let _ = TockSyscalls::memop_debug_stack_start(core::ptr::addr_of!(_stack_top));
+        let _ = TockSyscalls::memop_debug_heap_start(core::ptr::addr_of!(_heap_start));
+    }
 
     extern "Rust" {
         fn libtock_unsafe_main() -> !;
@@ -0,0 +1,102 @@
+//! Tests for the Memop system call implementation in
+//! `libtock_platform::Syscalls`.
+
+use libtock_platform::{memop_id, ErrorCode, Syscalls};
+use libtock_unittest::{fake, ExpectedSyscall, SyscallLogEntry};
+
+#[test]
+fn brk_and_sbrk() {
+    let kernel = fake::Kernel::new();
+    let memory_start = fake::Syscalls::memop_memory_start().unwrap();
+    let grant_start = fake::Syscalls::memop_grant_start().unwrap();
+
+    unsafe {
+        assert_eq!(fake::Syscalls::memop_brk(memory_start.add(32)), Ok(()));
+        assert_eq!(
+            fake::Syscalls::memop_sbrk(16),
+            Ok(memory_start.add(32) as *mut u8)
+        );
+        assert_eq!(
+            fake::Syscalls::memop_sbrk(-8),
+            Ok(memory_start.add(48) as *mut u8)
+        );
+        assert_eq!(kernel.app_break() as *const u8, memory_start.add(40));
+        assert_eq!(
+            fake::Syscalls::memop_brk(grant_start.add(1)),
+            Err(ErrorCode::NoMem)
+        );
+        assert_eq!(fake::Syscalls::memop_sbrk(-41), Err(ErrorCode::NoMem));
+    }
+
+    // The memory below the break is usable by the process.
+    let app_break = unsafe { fake::Syscalls::memop_sbrk(4) }.unwrap();
+    unsafe {
+        app_break.write_bytes(0xAA, 4);
+        assert_eq!(app_break.add(3).read(), 0xAA);
+    }
+}
+
+#[test]
+fn memory_layout() {
+    let kernel = fake::Kernel::new();
+    kernel.add_writeable_flash_region(0x10, 0x20);
+    let memory_start = fake::Syscalls::memop_memory_start().unwrap();
+    let memory_end = fake::Syscalls::memop_memory_end().unwrap();
+    let grant_start = fake::Syscalls::memop_grant_start().unwrap();
+    let flash_start = fake::Syscalls::memop_flash_start().unwrap();
+    let flash_end = fake::Syscalls::memop_flash_end().unwrap();
+    assert!(memory_start < grant_start && grant_start < memory_end);
+    assert!(flash_start < flash_end);
+    assert_eq!(fake::Syscalls::memop_flash_region_count(), Ok(1));
+    assert_eq!(
+        fake::Syscalls::memop_flash_region_start(0),
+        Ok(flash_start.wrapping_add(0x10))
+    );
+    assert_eq!(
+        fake::Syscalls::memop_flash_region_end(0),
+        Ok(flash_start.wrapping_add(0x30))
+    );
+    assert_eq!(
+        fake::Syscalls::memop_flash_region_end(1),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn debug_hints() {
+    let kernel = fake::Kernel::new();
+    assert_eq!(
+        fake::Syscalls::memop_debug_stack_start(0x1000 as *const u8),
+        Ok(())
+    );
+    assert_eq!(
+        fake::Syscalls::memop_debug_heap_start(0x2000 as *const u8),
+        Ok(())
+    );
+    assert_eq!(kernel.debug_stack_start(), Some(0x1000));
+    assert_eq!(kernel.debug_heap_start(), Some(0x2000));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 0x1000,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_HEAP_START,
+                argument0: 0x2000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn failure() {
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::MEMORY_START,
+        argument0: 0,
+        return_error: Some(ErrorCode::Fail),
+    });
+    assert_eq!(fake::Syscalls::memop_memory_start(), Err(ErrorCode::Fail));
+}
@@ -70,7 +70,21 @@ pub enum ExpectedSyscall {
         // invoked and the provided error will be returned instead.
         return_error: Option<libtock_platform::ErrorCode>,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        // Matched values: the memop must give the specified memop_num and
+        // argument0 values. Memop operations that do not take an argument are
+        // matched against an argument0 of 0.
+        memop_num: u32,
+        argument0: usize,
+
+        // If set to Some(_), the memop operation will not be performed and the
+        // provided error will be returned instead.
+        return_error: Option<libtock_platform::ErrorCode>,
+    },
     // TODO: Add Exit.
 }
 
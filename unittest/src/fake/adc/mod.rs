@@ -0,0 +1,213 @@
+//! Fake implementation of the ADC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00005_adc.md
+//!
+//! `Adc` takes its samples from a waveform provided by the test, which it
+//! repeats indefinitely. Single samples and single buffers complete as soon as
+//! they are requested. Continuous buffered sampling fills the two shared
+//! buffers alternately, one buffer each time the process waits for an upcall
+//! (i.e. each time the fake kernel is idle) or the test calls
+//! `fill_next_buffer`. If the buffer to fill is not shared at that point, its
+//! samples are lost, which `missed_buffers` reports.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::RwAllowBuffer;
+
+pub struct Adc {
+    channels: u32,
+    resolution_bits: Cell<u32>,
+    reference_voltage_mv: Cell<Option<u32>>,
+    waveform: RefCell<Vec<u16>>,
+    position: Cell<usize>,
+    // The channel being sampled continuously, if continuous sampling is
+    // running.
+    continuous_channel: Cell<Option<u32>>,
+    // The buffer that continuous sampling will fill next.
+    next_buffer: Cell<u32>,
+    missed_buffers: Cell<usize>,
+    buffers: [Cell<RwAllowBuffer>; 2],
+}
+
+impl Adc {
+    /// Creates a fake ADC with the given number of channels, a 12-bit
+    /// resolution, and a 3.3 V reference voltage. Until `set_waveform` is
+    /// called, every sample is 0.
+    pub fn new(channels: u32) -> std::rc::Rc<Adc> {
+        std::rc::Rc::new(Adc {
+            channels,
+            resolution_bits: Cell::new(12),
+            reference_voltage_mv: Cell::new(Some(3300)),
+            waveform: Default::default(),
+            position: Cell::new(0),
+            continuous_channel: Cell::new(None),
+            next_buffer: Cell::new(0),
+            missed_buffers: Cell::new(0),
+            buffers: Default::default(),
+        })
+    }
+
+    pub fn set_resolution_bits(&self, bits: u32) {
+        self.resolution_bits.set(bits);
+    }
+
+    /// Sets the reference voltage, or makes it unknown if `None`.
+    pub fn set_reference_voltage_mv(&self, millivolts: Option<u32>) {
+        self.reference_voltage_mv.set(millivolts);
+    }
+
+    /// Sets the waveform the ADC samples. Samples are taken from the start of
+    /// the waveform, wrapping around when its end is reached.
+    pub fn set_waveform(&self, waveform: &[u16]) {
+        *self.waveform.borrow_mut() = waveform.into();
+        self.position.set(0);
+    }
+
+    /// Returns true if continuous sampling is running.
+    pub fn is_sampling(&self) -> bool {
+        self.continuous_channel.get().is_some()
+    }
+
+    /// Fills the next buffer of continuous sampling, as if the ADC completed
+    /// it while the process was running rather than waiting for an upcall.
+    /// Does nothing if continuous sampling is not running.
+    pub fn fill_next_buffer(&self) {
+        if let Some(channel) = self.continuous_channel.get() {
+            let buffer_num = self.next_buffer.get();
+            let buffer = self.buffers[buffer_num as usize].take();
+            let shared = !buffer.is_empty();
+            self.buffers[buffer_num as usize].set(buffer);
+            if shared {
+                self.fill_buffer(buffer_num, channel, MODE_CONTINUOUS_BUFFER);
+            } else {
+                // There is nowhere to put the samples, so they are lost and
+                // sampling moves on to the other buffer.
+                self.missed_buffers.set(self.missed_buffers.get() + 1);
+            }
+            self.next_buffer.set(buffer_num ^ 1);
+        }
+    }
+
+    /// Returns the number of times continuous sampling had to fill a buffer
+    /// that the process was not sharing, losing its samples.
+    pub fn missed_buffers(&self) -> usize {
+        self.missed_buffers.get()
+    }
+
+    fn next_sample(&self) -> u16 {
+        let waveform = self.waveform.borrow();
+        if waveform.is_empty() {
+            return 0;
+        }
+        let position = self.position.get() % waveform.len();
+        self.position.set(position + 1);
+        waveform[position]
+    }
+
+    // Fills the given buffer with samples and schedules the buffered sample
+    // upcall, which reports the buffer's address.
+    fn fill_buffer(&self, buffer_num: u32, channel: u32, mode: u32) {
+        let mut buffer = self.buffers[buffer_num as usize].take();
+        let count = buffer.len() / 2;
+        let address = buffer.as_ptr() as usize as u32;
+        for sample in buffer.chunks_exact_mut(2) {
+            sample.copy_from_slice(&self.next_sample().to_le_bytes());
+        }
+        self.buffers[buffer_num as usize].set(buffer);
+        upcall::schedule(
+            DRIVER_NUM,
+            SUBSCRIBE_SAMPLES,
+            (mode, (count as u32) << 8 | channel, address),
+        )
+        .expect("Unable to schedule upcall {}");
+    }
+}
+
+impl crate::fake::SyscallDriver for Adc {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        match self.buffers.get(buffer_num as usize) {
+            Some(slot) => Ok(slot.replace(buffer)),
+            None => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => return crate::command_return::success_u32(self.channels),
+            RESOLUTION_BITS => {
+                return crate::command_return::success_u32(self.resolution_bits.get())
+            }
+            REFERENCE_VOLTAGE => {
+                return match self.reference_voltage_mv.get() {
+                    Some(millivolts) => crate::command_return::success_u32(millivolts),
+                    None => crate::command_return::failure(ErrorCode::NoSupport),
+                }
+            }
+            STOP => self.continuous_channel.set(None),
+            SINGLE_SAMPLE | BUFFERED_SAMPLE | CONTINUOUS_BUFFERED_SAMPLE => {
+                if argument0 >= self.channels {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                if self.is_sampling() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                match command_num {
+                    SINGLE_SAMPLE => upcall::schedule(
+                        DRIVER_NUM,
+                        SUBSCRIBE_SAMPLES,
+                        (MODE_SINGLE_SAMPLE, argument0, self.next_sample() as u32),
+                    )
+                    .expect("Unable to schedule upcall {}"),
+                    BUFFERED_SAMPLE => self.fill_buffer(0, argument0, MODE_SINGLE_BUFFER),
+                    _ => {
+                        self.continuous_channel.set(Some(argument0));
+                        self.next_buffer.set(0);
+                    }
+                }
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+
+    fn idle(&self) {
+        self.fill_next_buffer();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x5;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SINGLE_SAMPLE: u32 = 1;
+const BUFFERED_SAMPLE: u32 = 3;
+const CONTINUOUS_BUFFERED_SAMPLE: u32 = 4;
+const STOP: u32 = 5;
+const RESOLUTION_BITS: u32 = 101;
+const REFERENCE_VOLTAGE: u32 = 102;
+
+const SUBSCRIBE_SAMPLES: u32 = 0;
+
+// Upcall modes, passed as the first upcall argument.
+const MODE_SINGLE_SAMPLE: u32 = 0;
+const MODE_SINGLE_BUFFER: u32 = 2;
+const MODE_CONTINUOUS_BUFFER: u32 = 3;
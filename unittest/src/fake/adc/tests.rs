@@ -0,0 +1,133 @@
+use crate::fake;
+use crate::RwAllowBuffer;
+use libtock_platform::share;
+use libtock_platform::{AllowRw, DefaultConfig, ErrorCode, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let adc = fake::Adc::new(2);
+    assert_eq!(
+        adc.command(fake::adc::DRIVER_CHECK, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert_eq!(
+        adc.command(fake::adc::RESOLUTION_BITS, 0, 0)
+            .get_success_u32(),
+        Some(12)
+    );
+    assert_eq!(
+        adc.command(fake::adc::REFERENCE_VOLTAGE, 0, 0)
+            .get_success_u32(),
+        Some(3300)
+    );
+    adc.set_reference_voltage_mv(None);
+    assert_eq!(
+        adc.command(fake::adc::REFERENCE_VOLTAGE, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(adc.allow_readwrite(1, RwAllowBuffer::default()).is_ok());
+    assert!(adc.allow_readwrite(2, RwAllowBuffer::default()).is_err());
+
+    assert_eq!(
+        adc.command(fake::adc::SINGLE_SAMPLE, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(adc
+        .command(fake::adc::CONTINUOUS_BUFFERED_SAMPLE, 1, 100)
+        .is_success());
+    assert!(adc.is_sampling());
+    assert_eq!(
+        adc.command(fake::adc::BUFFERED_SAMPLE, 0, 100)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    // No buffer is shared, so the samples are lost.
+    adc.fill_next_buffer();
+    assert_eq!(adc.missed_buffers(), 1);
+    assert!(adc.command(fake::adc::STOP, 0, 0).is_success());
+    assert!(!adc.is_sampling());
+}
+
+// Integration test that verifies Adc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use fake::SyscallDriver;
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let adc = fake::Adc::new(2);
+    kernel.add_driver(&adc);
+    adc.set_waveform(&[1, 2, 0x300]);
+
+    let mut first = [0; 4];
+    let mut second = [0; 2];
+    let first_address = first.as_ptr() as usize as u32;
+    let second_address = second.as_ptr() as usize as u32;
+    let upcall = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRw<_, { fake::adc::DRIVER_NUM }, 0>,
+            AllowRw<_, { fake::adc::DRIVER_NUM }, 1>,
+            Subscribe<_, { fake::adc::DRIVER_NUM }, { fake::adc::SUBSCRIBE_SAMPLES }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_first, allow_second, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, { fake::adc::DRIVER_NUM }, 0>(
+            allow_first,
+            &mut first,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, { fake::adc::DRIVER_NUM }, 1>(
+            allow_second,
+            &mut second,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::adc::DRIVER_NUM },
+            { fake::adc::SUBSCRIBE_SAMPLES },
+        >(subscribe, &upcall)
+        .unwrap();
+
+        assert!(
+            fake::Syscalls::command(fake::adc::DRIVER_NUM, fake::adc::SINGLE_SAMPLE, 1, 0)
+                .is_success()
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.take(), Some((fake::adc::MODE_SINGLE_SAMPLE, 1, 1)));
+
+        assert!(fake::Syscalls::command(
+            fake::adc::DRIVER_NUM,
+            fake::adc::CONTINUOUS_BUFFERED_SAMPLE,
+            0,
+            1000
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        adc.idle();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            upcall.take(),
+            Some((fake::adc::MODE_CONTINUOUS_BUFFER, 2 << 8, first_address))
+        );
+        adc.idle();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            upcall.take(),
+            Some((fake::adc::MODE_CONTINUOUS_BUFFER, 1 << 8, second_address))
+        );
+        assert!(fake::Syscalls::command(fake::adc::DRIVER_NUM, fake::adc::STOP, 0, 0).is_success());
+        adc.idle();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+    assert_eq!(first, [2, 0, 0, 3]);
+    assert_eq!(second, [1, 0]);
+    assert_eq!(adc.missed_buffers(), 0);
+}
@@ -0,0 +1,244 @@
+//! Fake implementation of the AES API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40006_aes.md
+//!
+//! `Aes` implements AES-128 in ECB, CBC and CTR mode on top of the `aes`
+//! crate's block cipher, so tests can check the results against known-answer
+//! vectors. Each `crypt` completes immediately, and its upcall is delivered the
+//! next time the process yields.
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Aes {
+    // The mode and direction selected by SET_ALGORITHM.
+    algorithm: Cell<Option<(u32, bool)>>,
+    operation: RefCell<Option<Operation>>,
+    key: Cell<RoAllowBuffer>,
+    iv: Cell<RoAllowBuffer>,
+    source: Cell<RoAllowBuffer>,
+    destination: Cell<RwAllowBuffer>,
+}
+
+impl Aes {
+    pub fn new() -> std::rc::Rc<Aes> {
+        std::rc::Rc::new(Aes {
+            algorithm: Cell::new(None),
+            operation: RefCell::new(None),
+            key: Default::default(),
+            iv: Default::default(),
+            source: Default::default(),
+            destination: Default::default(),
+        })
+    }
+
+    /// Returns `true` if an operation has been set up and not finished.
+    pub fn is_active(&self) -> bool {
+        self.operation.borrow().is_some()
+    }
+
+    fn setup(&self) -> Result<(), ErrorCode> {
+        let (mode, encrypting) = self.algorithm.get().ok_or(ErrorCode::Invalid)?;
+        let key = self.key.take();
+        let iv = self.iv.take();
+        let operation = Operation::new(mode, encrypting, &key, &iv);
+        self.key.set(key);
+        self.iv.set(iv);
+        self.operation.replace(Some(operation?));
+        Ok(())
+    }
+
+    fn crypt(&self) -> Result<(), ErrorCode> {
+        let mut operation = self.operation.borrow_mut();
+        let operation = operation.as_mut().ok_or(ErrorCode::Invalid)?;
+        let source = self.source.take();
+        let mut destination = self.destination.take();
+        let result = if operation.mode != CTR && source.len() % BLOCK_LEN != 0 {
+            Err(ErrorCode::Invalid)
+        } else if destination.len() < source.len() {
+            Err(ErrorCode::Size)
+        } else {
+            destination[..source.len()].copy_from_slice(&source);
+            operation.process(&mut destination[..source.len()]);
+            Ok(())
+        };
+        self.source.set(source);
+        self.destination.set(destination);
+        result
+    }
+}
+
+impl crate::fake::SyscallDriver for Aes {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key.replace(buffer)),
+            ALLOW_IV => Ok(self.iv.replace(buffer)),
+            ALLOW_SOURCE => Ok(self.source.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DESTINATION {
+            Ok(self.destination.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let result = match command_num {
+            DRIVER_CHECK => Ok(()),
+            SET_ALGORITHM => match (argument0, argument1) {
+                (ECB | CBC | CTR, 0 | 1) => {
+                    self.algorithm.set(Some((argument0, argument1 == 1)));
+                    Ok(())
+                }
+                _ => Err(ErrorCode::Invalid),
+            },
+            SETUP => self.setup(),
+            CRYPT => self.crypt().map(|()| {
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+            }),
+            FINISH => match self.operation.take() {
+                Some(_) => Ok(()),
+                None => Err(ErrorCode::Already),
+            },
+            _ => Err(ErrorCode::NoSupport),
+        };
+        match result {
+            Ok(()) => crate::command_return::success(),
+            Err(error) => crate::command_return::failure(error),
+        }
+    }
+}
+
+struct Operation {
+    cipher: Aes128,
+    mode: u32,
+    encrypting: bool,
+    // The CBC chaining value or the CTR counter block.
+    state: [u8; BLOCK_LEN],
+    // The current CTR keystream block, and how many of its bytes have been
+    // used.
+    keystream: [u8; BLOCK_LEN],
+    keystream_used: usize,
+}
+
+impl Operation {
+    fn new(mode: u32, encrypting: bool, key: &[u8], iv: &[u8]) -> Result<Operation, ErrorCode> {
+        if key.len() != BLOCK_LEN || (mode != ECB && iv.len() != BLOCK_LEN) {
+            return Err(ErrorCode::Size);
+        }
+        let mut state = [0; BLOCK_LEN];
+        if mode != ECB {
+            state.copy_from_slice(iv);
+        }
+        Ok(Operation {
+            cipher: Aes128::new(GenericArray::from_slice(key)),
+            mode,
+            encrypting,
+            state,
+            keystream: [0; BLOCK_LEN],
+            keystream_used: BLOCK_LEN,
+        })
+    }
+
+    fn process(&mut self, data: &mut [u8]) {
+        match self.mode {
+            CTR => {
+                // Encryption and decryption are the same operation in CTR mode.
+                for byte in data {
+                    if self.keystream_used == BLOCK_LEN {
+                        self.keystream = self.state;
+                        self.cipher
+                            .encrypt_block(GenericArray::from_mut_slice(&mut self.keystream));
+                        self.state =
+                            (u128::from_be_bytes(self.state).wrapping_add(1)).to_be_bytes();
+                        self.keystream_used = 0;
+                    }
+                    *byte ^= self.keystream[self.keystream_used];
+                    self.keystream_used += 1;
+                }
+            }
+            _ => {
+                for block in data.chunks_exact_mut(BLOCK_LEN) {
+                    self.process_block(block);
+                }
+            }
+        }
+    }
+
+    fn process_block(&mut self, block: &mut [u8]) {
+        let cbc = self.mode == CBC;
+        if self.encrypting {
+            if cbc {
+                block.iter_mut().zip(self.state).for_each(|(b, s)| *b ^= s);
+            }
+            self.cipher
+                .encrypt_block(GenericArray::from_mut_slice(block));
+            if cbc {
+                self.state.copy_from_slice(block);
+            }
+        } else {
+            let mut ciphertext = [0; BLOCK_LEN];
+            ciphertext.copy_from_slice(block);
+            self.cipher
+                .decrypt_block(GenericArray::from_mut_slice(block));
+            if cbc {
+                block.iter_mut().zip(self.state).for_each(|(b, s)| *b ^= s);
+                self.state = ciphertext;
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const SETUP: u32 = 2;
+const CRYPT: u32 = 3;
+const FINISH: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_IV: u32 = 1;
+const ALLOW_SOURCE: u32 = 2;
+const ALLOW_DESTINATION: u32 = 0;
+
+// Modes
+const ECB: u32 = 0;
+const CBC: u32 = 1;
+const CTR: u32 = 2;
+
+const BLOCK_LEN: usize = 16;
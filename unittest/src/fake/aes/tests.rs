@@ -0,0 +1,133 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// The AES-128 CBC example from NIST SP 800-38A, section F.2.1.
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const IV: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+];
+const PLAINTEXT: [u8; 16] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+];
+const CIPHERTEXT: [u8; 16] = [
+    0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46, 0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19, 0x7d,
+];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let aes = fake::Aes::new();
+    assert!(aes.allow_readonly(2, RoAllowBuffer::default()).is_ok());
+    assert!(aes.allow_readonly(3, RoAllowBuffer::default()).is_err());
+    assert!(aes.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(aes.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert!(aes.command(fake::aes::DRIVER_CHECK, 0, 0).is_success());
+    // Setup requires an algorithm, and then a key.
+    assert_eq!(
+        aes.command(fake::aes::SETUP, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        aes.command(fake::aes::SET_ALGORITHM, 3, 1).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(aes
+        .command(fake::aes::SET_ALGORITHM, fake::aes::ECB, 1)
+        .is_success());
+    assert_eq!(
+        aes.command(fake::aes::SETUP, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert!(!aes.is_active());
+    assert_eq!(
+        aes.command(fake::aes::CRYPT, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        aes.command(fake::aes::FINISH, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+}
+
+// Integration test that verifies Aes works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let aes = fake::Aes::new();
+    kernel.add_driver(&aes);
+
+    let mut destination = [0; 16];
+    let done = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::aes::DRIVER_NUM }, { fake::aes::ALLOW_KEY }>,
+            AllowRo<_, { fake::aes::DRIVER_NUM }, { fake::aes::ALLOW_IV }>,
+            AllowRo<_, { fake::aes::DRIVER_NUM }, { fake::aes::ALLOW_SOURCE }>,
+            AllowRw<_, { fake::aes::DRIVER_NUM }, { fake::aes::ALLOW_DESTINATION }>,
+            Subscribe<_, { fake::aes::DRIVER_NUM }, { fake::aes::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_iv, allow_source, allow_destination, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::aes::DRIVER_NUM },
+            { fake::aes::ALLOW_KEY },
+        >(allow_key, &KEY)
+        .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, { fake::aes::DRIVER_NUM }, { fake::aes::ALLOW_IV }>(
+            allow_iv, &IV,
+        )
+        .unwrap();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::aes::DRIVER_NUM },
+            { fake::aes::ALLOW_SOURCE },
+        >(allow_source, &PLAINTEXT)
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::aes::DRIVER_NUM },
+            { fake::aes::ALLOW_DESTINATION },
+        >(allow_destination, &mut destination)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::aes::DRIVER_NUM },
+            { fake::aes::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::aes::DRIVER_NUM,
+            fake::aes::SET_ALGORITHM,
+            fake::aes::CBC,
+            1
+        )
+        .is_success());
+        assert!(
+            fake::Syscalls::command(fake::aes::DRIVER_NUM, fake::aes::SETUP, 0, 0).is_success()
+        );
+        assert!(aes.is_active());
+        assert!(
+            fake::Syscalls::command(fake::aes::DRIVER_NUM, fake::aes::CRYPT, 0, 0).is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+        assert!(
+            fake::Syscalls::command(fake::aes::DRIVER_NUM, fake::aes::FINISH, 0, 0).is_success()
+        );
+    });
+    assert_eq!(destination, CIPHERTEXT);
+}
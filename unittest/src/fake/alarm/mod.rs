@@ -0,0 +1,121 @@
+//! Fake implementation of the Alarm API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00000_alarm.md
+//!
+//! Like the real API, `Alarm` maintains a clock and supports a single pending
+//! alarm, which queues an upcall when it expires. The clock is virtual: it only
+//! moves when the test calls `advance`, or when the process calls Yield-Wait
+//! while no upcall is queued (in which case the clock jumps to the pending
+//! alarm's expiration, as if the process had slept until then).
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+
+pub struct Alarm {
+    frequency_hz: u32,
+    now: Cell<u32>,
+    // The pending alarm, as a (reference, dt) pair. The alarm expires once at
+    // least dt ticks have elapsed since reference.
+    pending: Cell<Option<(u32, u32)>>,
+}
+
+impl Alarm {
+    pub fn new(frequency_hz: u32) -> std::rc::Rc<Alarm> {
+        std::rc::Rc::new(Alarm {
+            frequency_hz,
+            now: Cell::new(0),
+            pending: Cell::new(None),
+        })
+    }
+
+    /// Returns the current value of the virtual clock.
+    pub fn now(&self) -> u32 {
+        self.now.get()
+    }
+
+    /// Returns the expiration time of the pending alarm, if an alarm is set.
+    pub fn expiration(&self) -> Option<u32> {
+        self.pending
+            .get()
+            .map(|(reference, dt)| reference.wrapping_add(dt))
+    }
+
+    /// Advances the virtual clock by `ticks`. If the pending alarm expires in
+    /// that time, its upcall is queued.
+    pub fn advance(&self, ticks: u32) {
+        let now = self.now.get().wrapping_add(ticks);
+        if let Some((reference, dt)) = self.pending.get() {
+            // The elapsed time is measured from before the advance so that
+            // clock wraparound is handled correctly.
+            let elapsed = self.now.get().wrapping_sub(reference);
+            if dt.saturating_sub(elapsed) <= ticks {
+                self.pending.set(None);
+                self.now.set(now);
+                upcall::schedule(
+                    DRIVER_NUM,
+                    SUBSCRIBE_CALLBACK,
+                    (now, reference.wrapping_add(dt), 0),
+                )
+                .expect("Unable to schedule upcall {}");
+                return;
+            }
+        }
+        self.now.set(now);
+    }
+
+    fn set(&self, reference: u32, dt: u32) -> CommandReturn {
+        self.pending.set(Some((reference, dt)));
+        crate::command_return::success_u32(reference.wrapping_add(dt))
+    }
+}
+
+impl crate::fake::SyscallDriver for Alarm {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            FREQUENCY => crate::command_return::success_u32(self.frequency_hz),
+            TIME => crate::command_return::success_u32(self.now.get()),
+            STOP => match self.pending.take() {
+                Some(_) => crate::command_return::success(),
+                None => crate::command_return::failure(ErrorCode::Already),
+            },
+            SET_RELATIVE => self.set(self.now.get(), argument0),
+            SET_ABSOLUTE => self.set(argument0, argument1),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        if let Some((reference, dt)) = self.pending.get() {
+            let elapsed = self.now.get().wrapping_sub(reference);
+            self.advance(dt.saturating_sub(elapsed));
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const FREQUENCY: u32 = 1;
+const TIME: u32 = 2;
+const STOP: u32 = 3;
+const SET_RELATIVE: u32 = 5;
+const SET_ABSOLUTE: u32 = 6;
+
+const SUBSCRIBE_CALLBACK: u32 = 0;
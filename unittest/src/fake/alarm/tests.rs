@@ -0,0 +1,77 @@
+use crate::fake;
+use fake::alarm::*;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let alarm = Alarm::new(1000);
+
+    assert!(alarm.command(DRIVER_CHECK, 1, 2).is_success());
+    assert_eq!(alarm.command(FREQUENCY, 0, 0).get_success_u32(), Some(1000));
+
+    alarm.advance(10);
+    assert_eq!(alarm.now(), 10);
+    assert_eq!(alarm.command(TIME, 0, 0).get_success_u32(), Some(10));
+
+    assert_eq!(
+        alarm.command(STOP, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+    assert_eq!(
+        alarm.command(SET_RELATIVE, 5, 0).get_success_u32(),
+        Some(15)
+    );
+    assert_eq!(alarm.expiration(), Some(15));
+    assert_eq!(
+        alarm.command(SET_ABSOLUTE, 20, 3).get_success_u32(),
+        Some(23)
+    );
+    assert_eq!(alarm.expiration(), Some(23));
+    assert!(alarm.command(STOP, 0, 0).is_success());
+    assert_eq!(alarm.expiration(), None);
+}
+
+// Integration test that verifies Alarm works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let alarm = Alarm::new(1000);
+    kernel.add_driver(&alarm);
+    alarm.advance(u32::MAX - 1);
+
+    let called = core::cell::Cell::new(Option::<(u32, u32)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_CALLBACK>(
+            subscribe, &called,
+        )
+        .unwrap();
+
+        // The alarm should fire once the clock reaches its expiration, even if
+        // the clock wraps around.
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, SET_RELATIVE, 4, 0).get_success_u32(),
+            Some(2)
+        );
+        alarm.advance(3);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        alarm.advance(2);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.take(), Some((3, 2)));
+
+        // An alarm that is already in the past fires immediately.
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_ABSOLUTE, 0, 1).is_success_u32());
+        alarm.advance(0);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.take(), Some((3, 1)));
+
+        // When the process waits, the clock jumps forward to the alarm.
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_RELATIVE, 100, 0).is_success_u32());
+        fake::Syscalls::yield_wait();
+        assert_eq!(called.take(), Some((103, 103)));
+        assert_eq!(alarm.now(), 103);
+    });
+}
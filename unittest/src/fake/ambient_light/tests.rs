@@ -0,0 +1,78 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ambient_light = fake::AmbientLight::new();
+    assert!(ambient_light
+        .command(fake::ambient_light::DRIVER_CHECK, 1, 2)
+        .is_success());
+
+    assert!(ambient_light
+        .command(fake::ambient_light::READ_INTENSITY, 0, 0)
+        .is_success());
+    assert!(ambient_light.is_busy());
+    assert_eq!(
+        ambient_light
+            .command(fake::ambient_light::READ_INTENSITY, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    let ambient_light = fake::AmbientLight::new();
+    ambient_light.set_error_sync(ErrorCode::Fail);
+    assert_eq!(
+        ambient_light
+            .command(fake::ambient_light::READ_INTENSITY, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Fail)
+    );
+    assert!(!ambient_light.is_busy());
+}
+
+// Integration test that verifies AmbientLight works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ambient_light = fake::AmbientLight::new();
+    kernel.add_driver(&ambient_light);
+
+    let reading = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::ambient_light::DRIVER_NUM },
+            { fake::ambient_light::SUBSCRIBE_READING },
+        >(subscribe, &reading)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::ambient_light::DRIVER_NUM,
+            fake::ambient_light::READ_INTENSITY,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        ambient_light.set_value(150);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((150,)));
+
+        ambient_light.set_value_sync(2250);
+        assert!(fake::Syscalls::command(
+            fake::ambient_light::DRIVER_NUM,
+            fake::ambient_light::READ_INTENSITY,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((2250,)));
+    });
+}
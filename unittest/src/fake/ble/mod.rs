@@ -0,0 +1,167 @@
+//! Fake implementation of the BLE advertising API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30000_ble_advertising.md
+//!
+//! `Ble` does not transmit anything; instead, tests inspect the advertising
+//! data shared by the process with `advertising_data`. Scan results are
+//! scripted with `add_scan_report`. While a scan is running, the fake delivers
+//! one queued report each time the process waits for an upcall (i.e. each time
+//! the fake kernel is idle).
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Ble {
+    advertising_interval: Cell<Option<u32>>,
+    scanning: Cell<bool>,
+    scan_reports: RefCell<VecDeque<Vec<u8>>>,
+    advertising_data: Cell<RoAllowBuffer>,
+    scan_buffer: Cell<RwAllowBuffer>,
+}
+
+impl Ble {
+    pub fn new() -> std::rc::Rc<Ble> {
+        std::rc::Rc::new(Ble {
+            advertising_interval: Cell::new(None),
+            scanning: Cell::new(false),
+            scan_reports: Default::default(),
+            advertising_data: Default::default(),
+            scan_buffer: Default::default(),
+        })
+    }
+
+    /// Returns the advertising interval, in milliseconds, if the process is
+    /// advertising.
+    pub fn advertising_interval(&self) -> Option<u32> {
+        self.advertising_interval.get()
+    }
+
+    /// Returns the advertising data currently shared by the process, if it is
+    /// advertising.
+    pub fn advertising_data(&self) -> Option<Vec<u8>> {
+        self.advertising_interval.get()?;
+        let buffer = self.advertising_data.take();
+        let data = buffer.to_vec();
+        self.advertising_data.set(buffer);
+        Some(data)
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scanning.get()
+    }
+
+    /// Queues an advertisement to be received by the next scan. The report is
+    /// delivered as an advertising PDU of the given type, from the advertiser
+    /// with the given (little-endian) address.
+    pub fn add_scan_report(&self, pdu_type: u8, address: [u8; 6], data: &[u8]) {
+        let mut pdu = vec![pdu_type, (address.len() + data.len()) as u8];
+        pdu.extend_from_slice(&address);
+        pdu.extend_from_slice(data);
+        self.scan_reports.borrow_mut().push_back(pdu);
+    }
+
+    /// Returns the number of queued reports that have not been delivered.
+    pub fn pending_scan_reports(&self) -> usize {
+        self.scan_reports.borrow().len()
+    }
+}
+
+impl crate::fake::SyscallDriver for Ble {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_ADVERTISING_DATA {
+            Ok(self.advertising_data.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SCAN {
+            Ok(self.scan_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let busy = self.advertising_interval.get().is_some() || self.scanning.get();
+        match command_num {
+            START_ADVERTISING | PASSIVE_SCAN if busy => {
+                crate::command_return::failure(ErrorCode::Busy)
+            }
+            START_ADVERTISING => match argument0 {
+                ADV_IND | ADV_NONCONN_IND | ADV_SCAN_IND => {
+                    self.advertising_interval.set(Some(argument1));
+                    crate::command_return::success()
+                }
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            PASSIVE_SCAN => {
+                self.scanning.set(true);
+                crate::command_return::success()
+            }
+            STOP if busy => {
+                self.advertising_interval.set(None);
+                self.scanning.set(false);
+                crate::command_return::success()
+            }
+            STOP => crate::command_return::failure(ErrorCode::Already),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        if !self.scanning.get() {
+            return;
+        }
+        if let Some(pdu) = self.scan_reports.borrow_mut().pop_front() {
+            let mut buffer = self.scan_buffer.take();
+            let len = pdu.len().min(buffer.len());
+            buffer[..len].copy_from_slice(&pdu[..len]);
+            self.scan_buffer.set(buffer);
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_SCAN, (0, len as u32, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x30000;
+
+// Command numbers
+const START_ADVERTISING: u32 = 0;
+const STOP: u32 = 1;
+const PASSIVE_SCAN: u32 = 5;
+
+const SUBSCRIBE_SCAN: u32 = 0;
+const ALLOW_ADVERTISING_DATA: u32 = 0;
+const ALLOW_SCAN: u32 = 0;
+
+// Advertising PDU types that may be passed to START_ADVERTISING.
+const ADV_IND: u32 = 0x00;
+const ADV_NONCONN_IND: u32 = 0x02;
+const ADV_SCAN_IND: u32 = 0x06;
@@ -0,0 +1,110 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ble = fake::Ble::new();
+    assert!(ble.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(ble.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(ble.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(ble.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert_eq!(
+        ble.command(fake::ble::START_ADVERTISING, 0x03, 100)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(ble
+        .command(
+            fake::ble::START_ADVERTISING,
+            fake::ble::ADV_NONCONN_IND,
+            100
+        )
+        .is_success());
+    assert_eq!(ble.advertising_interval(), Some(100));
+    assert_eq!(ble.advertising_data(), Some(vec![]));
+    assert_eq!(
+        ble.command(fake::ble::PASSIVE_SCAN, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    assert!(ble.command(fake::ble::STOP, 0, 0).is_success());
+    assert_eq!(ble.advertising_interval(), None);
+    assert_eq!(ble.advertising_data(), None);
+    assert_eq!(
+        ble.command(fake::ble::STOP, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+
+    // Reports are only delivered while scanning.
+    ble.add_scan_report(0, [1; 6], &[]);
+    ble.idle();
+    assert_eq!(ble.pending_scan_reports(), 1);
+}
+
+// Integration test that verifies Ble works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ble = fake::Ble::new();
+    kernel.add_driver(&ble);
+    ble.add_scan_report(0, [1, 2, 3, 4, 5, 6], &[2, 1, 6]);
+
+    let mut buffer = [0; 12];
+    let received = core::cell::Cell::new(Option::<(u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::ble::DRIVER_NUM }, { fake::ble::ALLOW_ADVERTISING_DATA }>,
+            AllowRw<_, { fake::ble::DRIVER_NUM }, { fake::ble::ALLOW_SCAN }>,
+            Subscribe<_, { fake::ble::DRIVER_NUM }, { fake::ble::SUBSCRIBE_SCAN }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::ble::DRIVER_NUM },
+            { fake::ble::ALLOW_ADVERTISING_DATA },
+        >(allow_ro, &[2, 1, 4])
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::ble::DRIVER_NUM },
+            { fake::ble::ALLOW_SCAN },
+        >(allow_rw, &mut buffer)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::ble::DRIVER_NUM },
+            { fake::ble::SUBSCRIBE_SCAN },
+        >(subscribe, &received)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::ble::DRIVER_NUM,
+            fake::ble::START_ADVERTISING,
+            fake::ble::ADV_NONCONN_IND,
+            250
+        )
+        .is_success());
+        assert_eq!(ble.advertising_data(), Some(vec![2, 1, 4]));
+        assert!(fake::Syscalls::command(fake::ble::DRIVER_NUM, fake::ble::STOP, 0, 0).is_success());
+
+        assert!(
+            fake::Syscalls::command(fake::ble::DRIVER_NUM, fake::ble::PASSIVE_SCAN, 0, 0)
+                .is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(received.take(), Some((0, 11)));
+        assert_eq!(ble.pending_scan_reports(), 0);
+    });
+    assert_eq!(buffer, [0, 9, 1, 2, 3, 4, 5, 6, 2, 1, 6, 0]);
+}
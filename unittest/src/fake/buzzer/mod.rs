@@ -0,0 +1,96 @@
+//! Fake implementation of the buzzer API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90000_buzzer.md
+//!
+//! `Buzzer` records the tones the process plays, so tests can check them. A
+//! tone finishes when the process waits for it (with Yield-Wait), at which
+//! point its upcall is queued. Starting a tone while another is playing fails
+//! with `ErrorCode::Busy`.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+
+pub struct Buzzer {
+    playing: Cell<bool>,
+    tones: RefCell<Vec<Tone>>,
+}
+
+/// A tone played on a `Buzzer`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Tone {
+    pub frequency_hz: u32,
+    pub duration_ms: u32,
+}
+
+impl Buzzer {
+    pub fn new() -> std::rc::Rc<Buzzer> {
+        std::rc::Rc::new(Buzzer {
+            playing: Cell::new(false),
+            tones: Default::default(),
+        })
+    }
+
+    /// Returns true if a tone is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing.get()
+    }
+
+    /// Returns the tones played since the last call to `take_tones`, in the
+    /// order they were played.
+    pub fn take_tones(&self) -> Vec<Tone> {
+        self.tones.take()
+    }
+}
+
+impl crate::fake::SyscallDriver for Buzzer {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            TONE => {
+                if self.playing.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                if argument0 == 0 {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.playing.set(true);
+                self.tones.borrow_mut().push(Tone {
+                    frequency_hz: argument0,
+                    duration_ms: argument1,
+                });
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        if self.playing.replace(false) {
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x90000;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const TONE: u32 = 1;
+
+const SUBSCRIBE_DONE: u32 = 0;
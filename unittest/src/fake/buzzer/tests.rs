@@ -0,0 +1,60 @@
+use crate::fake;
+use fake::buzzer::*;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let buzzer = Buzzer::new();
+    assert!(buzzer.command(DRIVER_CHECK, 0, 0).is_success());
+    assert_eq!(
+        buzzer.command(TONE, 0, 100).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(buzzer.command(TONE, 440, 100).is_success());
+    assert!(buzzer.is_playing());
+    assert_eq!(
+        buzzer.command(TONE, 880, 100).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    assert_eq!(
+        buzzer.take_tones(),
+        [Tone {
+            frequency_hz: 440,
+            duration_ms: 100
+        }]
+    );
+    assert_eq!(buzzer.take_tones(), []);
+}
+
+// Integration test that verifies Buzzer works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let buzzer = Buzzer::new();
+    kernel.add_driver(&buzzer);
+
+    let done = core::cell::Cell::new(false);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, TONE, 262, 500).is_success());
+        // The tone is still playing until the process waits for it.
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        fake::Syscalls::yield_wait();
+        assert!(done.get());
+        assert!(!buzzer.is_playing());
+    });
+    assert_eq!(
+        buzzer.take_tones(),
+        [Tone {
+            frequency_hz: 262,
+            duration_ms: 500
+        }]
+    );
+}
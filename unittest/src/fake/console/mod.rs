@@ -4,17 +4,25 @@
 //! Like the real API, `Console` stores each message written to it.
 //! The resulting byte stream can be retrieved via `take_bytes`
 //! for use in unit tests.
+//!
+//! Input is emulated by queueing bytes with `add_input`. Queued bytes are
+//! received by the process' outstanding read (if any), which completes through
+//! the READ upcall once it has received as many bytes as it requested.
 
 use core::cell::Cell;
 use core::cmp;
 use libtock_platform::{CommandReturn, ErrorCode};
 
 use crate::upcall;
-use crate::RoAllowBuffer;
+use crate::{RoAllowBuffer, RwAllowBuffer};
 
 pub struct Console {
     messages: Cell<Vec<u8>>,
     buffer: Cell<RoAllowBuffer>,
+    input: Cell<Vec<u8>>,
+    read_buffer: Cell<RwAllowBuffer>,
+    // The outstanding read, as (requested length, bytes received so far).
+    read: Cell<Option<(usize, usize)>>,
 }
 
 impl Console {
@@ -22,6 +30,9 @@ impl Console {
         std::rc::Rc::new(Console {
             messages: Default::default(),
             buffer: Default::default(),
+            input: Default::default(),
+            read_buffer: Default::default(),
+            read: Cell::new(None),
         })
     }
 
@@ -30,6 +41,49 @@ impl Console {
     pub fn take_bytes(&self) -> Vec<u8> {
         self.messages.take()
     }
+
+    /// Queues bytes to be received by the process, as if they had been typed
+    /// into the console.
+    pub fn add_input(&self, bytes: &[u8]) {
+        let mut input = self.input.take();
+        input.extend_from_slice(bytes);
+        self.input.set(input);
+        self.receive();
+    }
+
+    /// Returns the queued input bytes that have not been received by the
+    /// process yet.
+    pub fn pending_input(&self) -> Vec<u8> {
+        let input = self.input.take();
+        self.input.set(input.clone());
+        input
+    }
+
+    // Moves queued input into the outstanding read's buffer, completing the
+    // read if it has received all the bytes it requested.
+    fn receive(&self) {
+        let (len, received) = match self.read.get() {
+            None => return,
+            Some(read) => read,
+        };
+        let mut input = self.input.take();
+        let mut buffer = self.read_buffer.take();
+        // The process may have replaced the buffer since it started the read,
+        // so don't assume the buffer is long enough.
+        let count =
+            cmp::min(len - received, input.len()).min(buffer.len().saturating_sub(received));
+        buffer[received..received + count].copy_from_slice(&input[..count]);
+        input.drain(..count);
+        self.read_buffer.set(buffer);
+        self.input.set(input);
+        if received + count == len {
+            self.read.set(None);
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_READ, (0, len as u32, 0))
+                .expect("Unable to schedule upcall {}");
+        } else {
+            self.read.set(Some((len, received + count)));
+        }
+    }
 }
 
 impl crate::fake::SyscallDriver for Console {
@@ -37,7 +91,7 @@ impl crate::fake::SyscallDriver for Console {
         DRIVER_NUM
     }
     fn num_upcalls(&self) -> u32 {
-        2
+        3
     }
 
     fn allow_readonly(
@@ -52,6 +106,18 @@ impl crate::fake::SyscallDriver for Console {
         }
     }
 
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
     fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
         match command_num {
             DRIVER_CHECK => {}
@@ -65,6 +131,29 @@ impl crate::fake::SyscallDriver for Console {
                 upcall::schedule(DRIVER_NUM, SUBSCRIBE_WRITE, (size as u32, 0, 0))
                     .expect("Unable to schedule upcall {}");
             }
+            READ => {
+                if self.read.get().is_some() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                let buffer = self.read_buffer.take();
+                let buffer_len = buffer.len();
+                self.read_buffer.set(buffer);
+                if argument0 as usize > buffer_len {
+                    return crate::command_return::failure(ErrorCode::Size);
+                }
+                self.read.set(Some((argument0 as usize, 0)));
+                self.receive();
+            }
+            ABORT => {
+                if let Some((_, received)) = self.read.take() {
+                    upcall::schedule(
+                        DRIVER_NUM,
+                        SUBSCRIBE_READ,
+                        (ErrorCode::Cancel as u32, received as u32, 0),
+                    )
+                    .expect("Unable to schedule upcall {}");
+                }
+            }
             _ => return crate::command_return::failure(ErrorCode::NoSupport),
         }
         crate::command_return::success()
@@ -83,7 +172,9 @@ const DRIVER_NUM: u32 = 1;
 // Command numbers
 const DRIVER_CHECK: u32 = 0;
 const WRITE: u32 = 1;
-//const READ: u32 = 2;
-//const ABORT: u32 = 3;
+const READ: u32 = 2;
+const ABORT: u32 = 3;
 const SUBSCRIBE_WRITE: u32 = 1;
+const SUBSCRIBE_READ: u32 = 2;
 const ALLOW_WRITE: u32 = 1;
+const ALLOW_READ: u32 = 1;
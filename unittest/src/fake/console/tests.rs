@@ -1,7 +1,7 @@
 use crate::fake;
-use crate::RoAllowBuffer;
+use crate::{RoAllowBuffer, RwAllowBuffer};
 use libtock_platform::share;
-use libtock_platform::DefaultConfig;
+use libtock_platform::{AllowRw, DefaultConfig, ErrorCode, Subscribe};
 
 // Tests the command implementation.
 #[test]
@@ -13,6 +13,17 @@ fn command() {
         .is_success());
     assert!(console.allow_readonly(1, RoAllowBuffer::default()).is_ok());
     assert!(console.allow_readonly(2, RoAllowBuffer::default()).is_err());
+    assert!(console.allow_readwrite(1, RwAllowBuffer::default()).is_ok());
+    assert!(console
+        .allow_readwrite(2, RwAllowBuffer::default())
+        .is_err());
+
+    // Reads may not be longer than the read buffer.
+    assert_eq!(
+        console.command(fake::console::READ, 1, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert!(console.command(fake::console::ABORT, 0, 0).is_success());
 }
 
 // Integration test that verifies Console works with fake::Kernel and
@@ -41,4 +52,64 @@ fn kernel_integration() {
     });
     assert_eq!(console.take_bytes(), b"abc");
     assert_eq!(console.take_bytes(), b"");
+
+    let mut buffer = [0; 4];
+    let read = core::cell::Cell::new(Option::<(u32, u32)>::None);
+    console.add_input(b"ab");
+    share::scope::<
+        (
+            AllowRw<_, { fake::console::DRIVER_NUM }, { fake::console::ALLOW_READ }>,
+            Subscribe<_, { fake::console::DRIVER_NUM }, { fake::console::SUBSCRIBE_READ }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::ALLOW_READ },
+        >(allow_rw, &mut buffer)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::SUBSCRIBE_READ },
+        >(subscribe, &read)
+        .unwrap();
+
+        // The read completes once enough input has been queued.
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 3, 0)
+                .is_success()
+        );
+        assert_eq!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 3, 0)
+                .get_failure(),
+            Some(ErrorCode::Busy)
+        );
+        assert_eq!(
+            fake::Syscalls::yield_no_wait(),
+            libtock_platform::YieldNoWaitReturn::NoUpcall
+        );
+        console.add_input(b"cde");
+        fake::Syscalls::yield_wait();
+        assert_eq!(read.take(), Some((0, 3)));
+        assert_eq!(console.pending_input(), b"de");
+
+        // Aborting a read reports the bytes received so far.
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 4, 0)
+                .is_success()
+        );
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::ABORT, 0, 0)
+                .is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(read.take(), Some((ErrorCode::Cancel as u32, 2)));
+    });
+    assert_eq!(&buffer, b"dec\0");
 }
@@ -6,6 +6,8 @@
 //! status of a button.
 //!
 //! It also provides the function `set_pressed` that set the button's state.
+//! `set_value_sync` changes a gpio's value as soon as the process next enables
+//! interrupts on it, which lets tests drive APIs that block until an edge.
 
 use core::cell::Cell;
 use libtock_platform::{CommandReturn, ErrorCode};
@@ -69,6 +71,7 @@ pub struct GpioState {
 
 pub struct Gpio<const NUM_GPIOS: usize> {
     gpios: [Cell<Option<GpioState>>; NUM_GPIOS],
+    value_on_interrupt_enable: [Cell<Option<bool>>; NUM_GPIOS],
 }
 
 impl<const NUM_GPIOS: usize> Gpio<NUM_GPIOS> {
@@ -79,8 +82,11 @@ impl<const NUM_GPIOS: usize> Gpio<NUM_GPIOS> {
             mode: GpioMode::Input(PullMode::PullNone),
             interrupt_enabled: None,
         }));
+        #[allow(clippy::declare_interior_mutable_const)]
+        const NO_VALUE: Cell<Option<bool>> = Cell::new(None);
         std::rc::Rc::new(Gpio {
             gpios: [OFF; NUM_GPIOS],
+            value_on_interrupt_enable: [NO_VALUE; NUM_GPIOS],
         })
     }
 
@@ -124,6 +130,14 @@ impl<const NUM_GPIOS: usize> Gpio<NUM_GPIOS> {
             .and_then(|value| value)
     }
 
+    /// Sets the gpio's value (as `set_value` does) as soon as the process next
+    /// enables interrupts on it.
+    pub fn set_value_sync(&self, pin: u32, value: bool) {
+        if let Some(pending) = self.value_on_interrupt_enable.get(pin as usize) {
+            pending.set(Some(value));
+        }
+    }
+
     pub fn get_gpio_state(&self, button: u32) -> Option<GpioState> {
         self.gpios
             .get(button as usize)
@@ -209,6 +223,11 @@ impl<const NUM_GPIOS: usize> crate::fake::SyscallDriver for Gpio<NUM_GPIOS> {
                                     interrupt_enabled: Some(interrupt_edge),
                                     ..gpio
                                 }));
+                                if let Some(value) =
+                                    self.value_on_interrupt_enable[argument0 as usize].take()
+                                {
+                                    let _ = self.set_value(argument0, value);
+                                }
                                 crate::command_return::success()
                             }
                             Err(error) => crate::command_return::failure(error),
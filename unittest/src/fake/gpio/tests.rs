@@ -401,3 +401,34 @@ fn kernel_integration() {
     assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_DISABLE, 0, 0).is_success());
     assert_eq!(gpio.get_gpio_state(0).unwrap().mode, GpioMode::Disable);
 }
+
+// Tests that set_value_sync changes the value once interrupts are enabled.
+#[test]
+fn set_value_sync() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let gpio = Gpio::<10>::new();
+    kernel.add_driver(&gpio);
+
+    let listener = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &listener),
+            Ok(())
+        );
+
+        gpio.set_value_sync(3, true);
+        assert!(!gpio.get_gpio_state(3).unwrap().value);
+        assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_ENABLE_INTERRUPTS, 3, 1).is_success());
+        assert!(gpio.get_gpio_state(3).unwrap().value);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((3, 1)));
+
+        // The value is only applied once.
+        assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_DISABLE_INTERRUPTS, 3, 0).is_success());
+        assert_eq!(gpio.set_value(3, false), Ok(()));
+        assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_ENABLE_INTERRUPTS, 3, 1).is_success());
+        assert!(!gpio.get_gpio_state(3).unwrap().value);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
@@ -0,0 +1,183 @@
+//! Fake implementation of the HMAC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40003_hmac.md
+//!
+//! `Hmac` computes MACs with the `hmac` and `sha2` crates, so tests can check
+//! the results against known-answer vectors. Until the process selects an
+//! algorithm and key, it computes HMAC-SHA256 MACs with an empty key. Each
+//! operation completes immediately, and its upcall is delivered the next time
+//! the process yields.
+
+use ::hmac::{Hmac as HostHmac, Mac};
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use sha2::{Sha256, Sha384, Sha512};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Hmac {
+    mac: RefCell<MacState>,
+    key: Cell<RoAllowBuffer>,
+    data: Cell<RoAllowBuffer>,
+    output: Cell<RwAllowBuffer>,
+}
+
+impl Hmac {
+    pub fn new() -> std::rc::Rc<Hmac> {
+        std::rc::Rc::new(Hmac {
+            mac: RefCell::new(MacState::new(HMAC_SHA256, &[]).unwrap()),
+            key: Default::default(),
+            data: Default::default(),
+            output: Default::default(),
+        })
+    }
+}
+
+impl crate::fake::SyscallDriver for Hmac {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key.replace(buffer)),
+            ALLOW_DATA => Ok(self.data.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_MAC {
+            Ok(self.output.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            SET_ALGORITHM => {
+                let key = self.key.take();
+                let mac = MacState::new(argument0, &key);
+                self.key.set(key);
+                match mac {
+                    Some(mac) => {
+                        self.mac.replace(mac);
+                        crate::command_return::success()
+                    }
+                    None => crate::command_return::failure(ErrorCode::Invalid),
+                }
+            }
+            UPDATE => {
+                let data = self.data.take();
+                self.mac.borrow_mut().update(&data);
+                self.data.set(data);
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+                crate::command_return::success()
+            }
+            FINISH => {
+                let mut output = self.output.take();
+                let result = self.mac.borrow_mut().finish(&mut output);
+                self.output.set(output);
+                if let Err(error) = result {
+                    return crate::command_return::failure(error);
+                }
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// The MAC being computed, along with its key so it can be restarted.
+struct MacState {
+    key: Vec<u8>,
+    mac: HostMac,
+}
+
+enum HostMac {
+    Sha256(HostHmac<Sha256>),
+    Sha384(HostHmac<Sha384>),
+    Sha512(HostHmac<Sha512>),
+}
+
+impl MacState {
+    // Returns None if algorithm is not a valid algorithm number.
+    fn new(algorithm: u32, key: &[u8]) -> Option<MacState> {
+        // HMAC accepts keys of any length, so new_from_slice cannot fail.
+        let mac = match algorithm {
+            HMAC_SHA256 => HostMac::Sha256(HostHmac::new_from_slice(key).unwrap()),
+            HMAC_SHA384 => HostMac::Sha384(HostHmac::new_from_slice(key).unwrap()),
+            HMAC_SHA512 => HostMac::Sha512(HostHmac::new_from_slice(key).unwrap()),
+            _ => return None,
+        };
+        Some(MacState {
+            key: key.to_vec(),
+            mac,
+        })
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match &mut self.mac {
+            HostMac::Sha256(mac) => mac.update(data),
+            HostMac::Sha384(mac) => mac.update(data),
+            HostMac::Sha512(mac) => mac.update(data),
+        }
+    }
+
+    // Writes the MAC into the start of output and restarts the MAC with the
+    // same key. Fails without restarting if output is too short.
+    fn finish(&mut self, output: &mut [u8]) -> Result<(), ErrorCode> {
+        let (algorithm, mac) = match &self.mac {
+            HostMac::Sha256(mac) => (HMAC_SHA256, mac.clone().finalize().into_bytes().to_vec()),
+            HostMac::Sha384(mac) => (HMAC_SHA384, mac.clone().finalize().into_bytes().to_vec()),
+            HostMac::Sha512(mac) => (HMAC_SHA512, mac.clone().finalize().into_bytes().to_vec()),
+        };
+        output
+            .get_mut(..mac.len())
+            .ok_or(ErrorCode::Size)?
+            .copy_from_slice(&mac);
+        *self = MacState::new(algorithm, &self.key).unwrap();
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const UPDATE: u32 = 3;
+const FINISH: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_DATA: u32 = 1;
+const ALLOW_MAC: u32 = 0;
+
+// Algorithm numbers
+const HMAC_SHA256: u32 = 0;
+const HMAC_SHA384: u32 = 1;
+const HMAC_SHA512: u32 = 2;
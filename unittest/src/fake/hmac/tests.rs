@@ -0,0 +1,108 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// HMAC-SHA256 test case 2 from RFC 4231.
+const KEY: &[u8] = b"Jefe";
+const DATA: &[u8] = b"what do ya want for nothing?";
+const MAC: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let hmac = fake::Hmac::new();
+    assert!(hmac.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(hmac.allow_readonly(1, RoAllowBuffer::default()).is_ok());
+    assert!(hmac.allow_readonly(2, RoAllowBuffer::default()).is_err());
+    assert!(hmac.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(hmac.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert!(hmac.command(fake::hmac::DRIVER_CHECK, 0, 0).is_success());
+    assert!(hmac
+        .command(fake::hmac::SET_ALGORITHM, fake::hmac::HMAC_SHA384, 0)
+        .is_success());
+    assert_eq!(
+        hmac.command(fake::hmac::SET_ALGORITHM, 3, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    // No output buffer is shared.
+    assert_eq!(
+        hmac.command(fake::hmac::FINISH, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+}
+
+// Integration test that verifies Hmac works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let hmac = fake::Hmac::new();
+    kernel.add_driver(&hmac);
+
+    let mut mac = [0; 32];
+    let done = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::hmac::DRIVER_NUM }, { fake::hmac::ALLOW_KEY }>,
+            AllowRo<_, { fake::hmac::DRIVER_NUM }, { fake::hmac::ALLOW_DATA }>,
+            AllowRw<_, { fake::hmac::DRIVER_NUM }, { fake::hmac::ALLOW_MAC }>,
+            Subscribe<_, { fake::hmac::DRIVER_NUM }, { fake::hmac::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_data, allow_mac, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::hmac::DRIVER_NUM },
+            { fake::hmac::ALLOW_KEY },
+        >(allow_key, KEY)
+        .unwrap();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::hmac::DRIVER_NUM },
+            { fake::hmac::ALLOW_DATA },
+        >(allow_data, DATA)
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::hmac::DRIVER_NUM },
+            { fake::hmac::ALLOW_MAC },
+        >(allow_mac, &mut mac)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::hmac::DRIVER_NUM },
+            { fake::hmac::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::hmac::DRIVER_NUM,
+            fake::hmac::SET_ALGORITHM,
+            fake::hmac::HMAC_SHA256,
+            0
+        )
+        .is_success());
+        assert!(
+            fake::Syscalls::command(fake::hmac::DRIVER_NUM, fake::hmac::UPDATE, 0, 0).is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+        assert!(
+            fake::Syscalls::command(fake::hmac::DRIVER_NUM, fake::hmac::FINISH, 0, 0).is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+    });
+    assert_eq!(mac, MAC);
+}
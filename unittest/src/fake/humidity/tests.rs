@@ -0,0 +1,78 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let humidity = fake::Humidity::new();
+    assert!(humidity
+        .command(fake::humidity::DRIVER_CHECK, 1, 2)
+        .is_success());
+
+    assert!(humidity
+        .command(fake::humidity::READ_HUMIDITY, 0, 0)
+        .is_success());
+    assert!(humidity.is_busy());
+    assert_eq!(
+        humidity
+            .command(fake::humidity::READ_HUMIDITY, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    let humidity = fake::Humidity::new();
+    humidity.set_error_sync(ErrorCode::Fail);
+    assert_eq!(
+        humidity
+            .command(fake::humidity::READ_HUMIDITY, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Fail)
+    );
+    assert!(!humidity.is_busy());
+}
+
+// Integration test that verifies Humidity works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let humidity = fake::Humidity::new();
+    kernel.add_driver(&humidity);
+
+    let reading = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::humidity::DRIVER_NUM },
+            { fake::humidity::SUBSCRIBE_READING },
+        >(subscribe, &reading)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::humidity::DRIVER_NUM,
+            fake::humidity::READ_HUMIDITY,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        humidity.set_value(5000);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((5000,)));
+
+        humidity.set_value_sync(6525);
+        assert!(fake::Syscalls::command(
+            fake::humidity::DRIVER_NUM,
+            fake::humidity::READ_HUMIDITY,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((6525,)));
+    });
+}
@@ -0,0 +1,157 @@
+//! Fake implementation of the I2C master API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/20003_i2c_master.md
+//!
+//! `I2cMaster` emulates an I2C bus. Tests attach fake devices to the bus with
+//! `add_device`; each transaction is routed to the device at the transaction's
+//! address. Transactions to an address with no device fail with NOACK, as they
+//! would on a real bus. Transactions complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+/// A device attached to a fake I2C bus.
+pub trait I2cDevice {
+    /// Handles a write transaction containing `data`.
+    fn write(&self, data: &[u8]) -> Result<(), ErrorCode>;
+
+    /// Handles a read transaction, which should fill `buffer`.
+    fn read(&self, buffer: &mut [u8]) -> Result<(), ErrorCode>;
+
+    /// Handles a write transaction followed by a read transaction with a
+    /// repeated start. By default, this performs the write, then the read.
+    fn write_read(&self, data: &[u8], buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        self.write(data)?;
+        self.read(buffer)
+    }
+}
+
+pub struct I2cMaster {
+    devices: RefCell<HashMap<u8, Rc<dyn I2cDevice>>>,
+    write_buffer: Cell<RoAllowBuffer>,
+    read_buffer: Cell<RwAllowBuffer>,
+}
+
+impl I2cMaster {
+    pub fn new() -> Rc<I2cMaster> {
+        Rc::new(I2cMaster {
+            devices: Default::default(),
+            write_buffer: Default::default(),
+            read_buffer: Default::default(),
+        })
+    }
+
+    /// Attaches `device` to the bus at `address`, replacing any device that was
+    /// previously at that address.
+    pub fn add_device<D: I2cDevice + 'static>(&self, address: u8, device: Rc<D>) {
+        self.devices.borrow_mut().insert(address, device);
+    }
+
+    /// Detaches the device at `address`, if any.
+    pub fn remove_device(&self, address: u8) {
+        self.devices.borrow_mut().remove(&address);
+    }
+}
+
+impl crate::fake::SyscallDriver for I2cMaster {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        // Lengths of the data to write and read, respectively.
+        let (write_len, read_len) = match command_num {
+            DRIVER_CHECK => return crate::command_return::success(),
+            WRITE => (argument1 as usize, 0),
+            READ => (0, argument1 as usize),
+            WRITE_READ => ((argument1 & 0xffff) as usize, (argument1 >> 16) as usize),
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        };
+        if argument0 > MAX_ADDRESS {
+            return crate::command_return::failure(ErrorCode::Invalid);
+        }
+        let write_buffer = self.write_buffer.take();
+        let mut read_buffer = self.read_buffer.take();
+        let result = if write_len > write_buffer.len() || read_len > read_buffer.len() {
+            Err(ErrorCode::Size)
+        } else {
+            Ok(())
+        };
+        if result.is_ok() {
+            let device = self.devices.borrow().get(&(argument0 as u8)).cloned();
+            let data = &write_buffer[..write_len];
+            let buffer = &mut read_buffer[..read_len];
+            let status = match (device, command_num) {
+                (None, _) => Err(ErrorCode::NoAck),
+                (Some(device), WRITE) => device.write(data),
+                (Some(device), READ) => device.read(buffer),
+                (Some(device), _) => device.write_read(data, buffer),
+            };
+            let status = match status {
+                Ok(()) => 0,
+                Err(error) => error as u32,
+            };
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (status, 0, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+        self.write_buffer.set(write_buffer);
+        self.read_buffer.set(read_buffer);
+        match result {
+            Ok(()) => crate::command_return::success(),
+            Err(error) => crate::command_return::failure(error),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x20003;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const WRITE: u32 = 1;
+const READ: u32 = 2;
+const WRITE_READ: u32 = 3;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
+
+// The largest 7-bit I2C address.
+const MAX_ADDRESS: u32 = 0x7f;
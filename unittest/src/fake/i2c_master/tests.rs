@@ -0,0 +1,131 @@
+use crate::fake::{self, I2cDevice};
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// A device that records the data written to it and returns a fixed response.
+#[derive(Default)]
+struct EchoDevice {
+    written: RefCell<Vec<u8>>,
+}
+
+impl I2cDevice for EchoDevice {
+    fn write(&self, data: &[u8]) -> Result<(), ErrorCode> {
+        self.written.borrow_mut().extend_from_slice(data);
+        Ok(())
+    }
+
+    fn read(&self, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = i as u8 + 0x10;
+        }
+        Ok(())
+    }
+}
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let i2c_master = fake::I2cMaster::new();
+    assert!(i2c_master
+        .command(fake::i2c_master::DRIVER_CHECK, 1, 2)
+        .is_success());
+    assert!(i2c_master
+        .allow_readonly(0, RoAllowBuffer::default())
+        .is_ok());
+    assert!(i2c_master
+        .allow_readonly(1, RoAllowBuffer::default())
+        .is_err());
+    assert!(i2c_master
+        .allow_readwrite(0, RwAllowBuffer::default())
+        .is_ok());
+    assert!(i2c_master
+        .allow_readwrite(1, RwAllowBuffer::default())
+        .is_err());
+
+    // Transfers may not be longer than the shared buffers.
+    assert_eq!(
+        i2c_master
+            .command(fake::i2c_master::WRITE, 0x10, 1)
+            .get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(
+        i2c_master
+            .command(fake::i2c_master::READ, 0x80, 0)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies I2cMaster works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let i2c_master = fake::I2cMaster::new();
+    kernel.add_driver(&i2c_master);
+    let device = Rc::new(EchoDevice::default());
+    i2c_master.add_device(0x1e, device.clone());
+
+    let mut buffer = [0; 3];
+    let done = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::i2c_master::DRIVER_NUM }, { fake::i2c_master::ALLOW_WRITE }>,
+            AllowRw<_, { fake::i2c_master::DRIVER_NUM }, { fake::i2c_master::ALLOW_READ }>,
+            Subscribe<_, { fake::i2c_master::DRIVER_NUM }, { fake::i2c_master::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::i2c_master::DRIVER_NUM },
+            { fake::i2c_master::ALLOW_WRITE },
+        >(allow_ro, &[1, 2])
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::i2c_master::DRIVER_NUM },
+            { fake::i2c_master::ALLOW_READ },
+        >(allow_rw, &mut buffer)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::i2c_master::DRIVER_NUM },
+            { fake::i2c_master::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::i2c_master::DRIVER_NUM,
+            fake::i2c_master::WRITE_READ,
+            0x1e,
+            1 | 2 << 16
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+
+        // No device is attached at this address.
+        assert!(fake::Syscalls::command(
+            fake::i2c_master::DRIVER_NUM,
+            fake::i2c_master::WRITE,
+            0x1f,
+            2
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((ErrorCode::NoAck as u32,)));
+    });
+    assert_eq!(*device.written.borrow(), [1]);
+    assert_eq!(buffer, [0x10, 0x11, 0]);
+}
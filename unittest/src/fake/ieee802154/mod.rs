@@ -0,0 +1,321 @@
+//! Fake implementation of the IEEE 802.15.4 radio API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30001_ieee802154.md
+//!
+//! `Ieee802154` records every frame the process transmits, which tests inspect
+//! with `take_transmitted_frames`. Received frames are either injected with
+//! `inject_frame` or, if loopback is enabled, are the process' own transmitted
+//! frames. The fake writes one received frame into the process' ring buffer
+//! each time the process waits for an upcall (i.e. each time the fake kernel is
+//! idle) while the ring buffer is shared; like the real driver, it drops frames
+//! that do not fit in a full ring.
+//!
+//! The fake does not encrypt anything: secured frames are delivered with their
+//! payload in plaintext and an all-zero message integrity code.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+/// A frame transmitted by the process.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ieee802154TxFrame {
+    pub destination: u16,
+    /// The security level and key index, if the frame is secured.
+    pub security: Option<(u8, u8)>,
+    pub payload: Vec<u8>,
+}
+
+pub struct Ieee802154 {
+    pan: Cell<u16>,
+    short_address: Cell<u16>,
+    long_address: Cell<u64>,
+    channel: Cell<u8>,
+    tx_power: Cell<i8>,
+    config_committed: Cell<bool>,
+    loopback: Cell<bool>,
+    sequence_number: Cell<u8>,
+    transmitted: RefCell<Vec<Ieee802154TxFrame>>,
+    // Frames waiting to be written into the ring, as (body, header length,
+    // MIC length) tuples.
+    received: RefCell<VecDeque<(Vec<u8>, u8, u8)>>,
+    tx_buffer: Cell<RoAllowBuffer>,
+    rx_buffer: Cell<RwAllowBuffer>,
+}
+
+impl Ieee802154 {
+    pub fn new() -> std::rc::Rc<Ieee802154> {
+        std::rc::Rc::new(Ieee802154 {
+            pan: Cell::new(0xffff),
+            short_address: Cell::new(0xffff),
+            long_address: Cell::new(0),
+            channel: Cell::new(26),
+            tx_power: Cell::new(0),
+            config_committed: Cell::new(true),
+            loopback: Cell::new(false),
+            sequence_number: Cell::new(0),
+            transmitted: Default::default(),
+            received: Default::default(),
+            tx_buffer: Default::default(),
+            rx_buffer: Default::default(),
+        })
+    }
+
+    pub fn pan(&self) -> u16 {
+        self.pan.get()
+    }
+
+    pub fn short_address(&self) -> u16 {
+        self.short_address.get()
+    }
+
+    pub fn long_address(&self) -> u64 {
+        self.long_address.get()
+    }
+
+    pub fn channel(&self) -> u8 {
+        self.channel.get()
+    }
+
+    pub fn tx_power(&self) -> i8 {
+        self.tx_power.get()
+    }
+
+    /// Returns `false` if the configuration has been changed since the process
+    /// last committed it.
+    pub fn is_config_committed(&self) -> bool {
+        self.config_committed.get()
+    }
+
+    /// If `loopback` is `true`, every frame the process transmits is also
+    /// received by the process.
+    pub fn set_loopback(&self, loopback: bool) {
+        self.loopback.set(loopback);
+    }
+
+    /// Queues a frame with the given MAC header and payload to be received by
+    /// the process.
+    pub fn inject_frame(&self, header: &[u8], payload: &[u8]) {
+        assert!(
+            header.len() + payload.len() + FCS_LEN <= MAX_FRAME_LEN,
+            "Injected frame is too long"
+        );
+        let mut body = header.to_vec();
+        body.extend_from_slice(payload);
+        self.received
+            .borrow_mut()
+            .push_back((body, header.len() as u8, 0));
+    }
+
+    /// Returns the number of received frames that have not been written into
+    /// the process' ring buffer yet.
+    pub fn pending_rx_frames(&self) -> usize {
+        self.received.borrow().len()
+    }
+
+    /// Returns the frames transmitted since the last call, in order.
+    pub fn take_transmitted_frames(&self) -> Vec<Ieee802154TxFrame> {
+        self.transmitted.take()
+    }
+
+    fn transmit(&self, destination: u16, security: u32) -> Result<bool, ErrorCode> {
+        let security = match (security & 0xff, (security >> 8) & 0xff) {
+            (0, _) => None,
+            (level @ 1..=7, key_index) => Some((level as u8, key_index as u8)),
+            _ => return Err(ErrorCode::Invalid),
+        };
+        let tx_buffer = self.tx_buffer.take();
+        let payload = tx_buffer.to_vec();
+        self.tx_buffer.set(tx_buffer);
+
+        // Build the MAC header of a data frame with PAN ID compression and short
+        // source and destination addresses.
+        let mut frame_control = FRAME_CONTROL_DATA;
+        if security.is_some() {
+            frame_control |= FRAME_CONTROL_SECURITY;
+        }
+        let sequence_number = self.sequence_number.get();
+        self.sequence_number.set(sequence_number.wrapping_add(1));
+        let mut header = frame_control.to_le_bytes().to_vec();
+        header.push(sequence_number);
+        header.extend_from_slice(&self.pan.get().to_le_bytes());
+        header.extend_from_slice(&destination.to_le_bytes());
+        header.extend_from_slice(&self.short_address.get().to_le_bytes());
+        let mut mic_len = 0;
+        if let Some((level, key_index)) = security {
+            // Auxiliary security header: security control (with key identifier
+            // mode 1), frame counter, key index.
+            header.push(level | 1 << 3);
+            header.extend_from_slice(&u32::from(sequence_number).to_le_bytes());
+            header.push(key_index);
+            mic_len = MIC_LEN[level as usize];
+        }
+        if header.len() + payload.len() + mic_len + FCS_LEN > MAX_FRAME_LEN {
+            return Err(ErrorCode::Size);
+        }
+
+        if self.loopback.get() {
+            let header_len = header.len() as u8;
+            let mut body = header;
+            body.extend_from_slice(&payload);
+            body.resize(body.len() + mic_len, 0);
+            self.received
+                .borrow_mut()
+                .push_back((body, header_len, mic_len as u8));
+        }
+        self.transmitted.borrow_mut().push(Ieee802154TxFrame {
+            destination,
+            security,
+            payload,
+        });
+        Ok(destination != BROADCAST_ADDRESS)
+    }
+
+    fn set_config<T: Copy>(&self, cell: &Cell<T>, value: T) -> CommandReturn {
+        cell.set(value);
+        self.config_committed.set(false);
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Ieee802154 {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        2
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_TX {
+            Ok(self.tx_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_RX {
+            Ok(self.rx_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            SET_SHORT_ADDRESS => self.set_config(&self.short_address, argument0 as u16),
+            SET_LONG_ADDRESS => self.set_config(
+                &self.long_address,
+                u64::from(argument0) | u64::from(argument1) << 32,
+            ),
+            SET_PAN => self.set_config(&self.pan, argument0 as u16),
+            SET_CHANNEL => match argument0 {
+                11..=26 => self.set_config(&self.channel, argument0 as u8),
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            SET_TX_POWER => self.set_config(&self.tx_power, argument0 as i8),
+            COMMIT_CONFIG => {
+                self.config_committed.set(true);
+                crate::command_return::success()
+            }
+            GET_SHORT_ADDRESS => {
+                crate::command_return::success_u32(self.short_address.get().into())
+            }
+            GET_LONG_ADDRESS => crate::command_return::success_u64(self.long_address.get()),
+            GET_PAN => crate::command_return::success_u32(self.pan.get().into()),
+            GET_CHANNEL => crate::command_return::success_u32(self.channel.get().into()),
+            GET_TX_POWER => crate::command_return::success_u32(self.tx_power.get() as u32),
+            TRANSMIT => match self.transmit(argument0 as u16, argument1) {
+                Ok(acked) => {
+                    upcall::schedule(DRIVER_NUM, SUBSCRIBE_TX, (0, acked as u32, 0))
+                        .expect("Unable to schedule upcall {}");
+                    crate::command_return::success()
+                }
+                Err(error) => crate::command_return::failure(error),
+            },
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        let mut buffer = self.rx_buffer.take();
+        let slots = buffer.len().saturating_sub(RING_HEADER_LEN) / SLOT_LEN;
+        if slots >= 2 {
+            if let Some((body, header_len, mic_len)) = self.received.borrow_mut().pop_front() {
+                let read_index = buffer[0] as usize % slots;
+                let write_index = buffer[1] as usize % slots;
+                let next_write_index = (write_index + 1) % slots;
+                // Like the real driver, drop the frame if the ring is full.
+                if next_write_index != read_index {
+                    let start = RING_HEADER_LEN + write_index * SLOT_LEN;
+                    let slot = &mut buffer[start..start + SLOT_LEN];
+                    let payload_len = body.len() - usize::from(header_len) - usize::from(mic_len);
+                    slot[..3].copy_from_slice(&[header_len, payload_len as u8, mic_len]);
+                    slot[3..3 + body.len()].copy_from_slice(&body);
+                    buffer[1] = next_write_index as u8;
+                    upcall::schedule(DRIVER_NUM, SUBSCRIBE_RX, (0, 0, 0))
+                        .expect("Unable to schedule upcall {}");
+                }
+            }
+        }
+        self.rx_buffer.set(buffer);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x30001;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SET_SHORT_ADDRESS: u32 = 2;
+const SET_LONG_ADDRESS: u32 = 3;
+const SET_PAN: u32 = 4;
+const SET_CHANNEL: u32 = 5;
+const SET_TX_POWER: u32 = 6;
+const COMMIT_CONFIG: u32 = 7;
+const GET_SHORT_ADDRESS: u32 = 8;
+const GET_LONG_ADDRESS: u32 = 9;
+const GET_PAN: u32 = 10;
+const GET_CHANNEL: u32 = 11;
+const GET_TX_POWER: u32 = 12;
+const TRANSMIT: u32 = 25;
+
+const SUBSCRIBE_RX: u32 = 0;
+const SUBSCRIBE_TX: u32 = 1;
+const ALLOW_TX: u32 = 0;
+const ALLOW_RX: u32 = 0;
+
+const MAX_FRAME_LEN: usize = 127;
+const FCS_LEN: usize = 2;
+const BROADCAST_ADDRESS: u16 = 0xffff;
+
+// Ring buffer layout: a read index and a write index, followed by the slots.
+// Each slot holds the header, payload and MIC lengths followed by the frame.
+const RING_HEADER_LEN: usize = 2;
+const SLOT_LEN: usize = 3 + MAX_FRAME_LEN;
+
+// Frame control field of a data frame with PAN ID compression and short
+// addresses, and the bit that marks the frame as secured.
+const FRAME_CONTROL_DATA: u16 = 0x8841;
+const FRAME_CONTROL_SECURITY: u16 = 0x0008;
+
+// The length of the message integrity code for each security level.
+const MIC_LEN: [usize; 8] = [0, 4, 8, 16, 0, 4, 8, 16];
@@ -0,0 +1,168 @@
+use crate::fake::{self, Ieee802154TxFrame};
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let radio = fake::Ieee802154::new();
+    assert!(radio.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(radio.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(radio.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(radio.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert!(radio
+        .command(fake::ieee802154::DRIVER_CHECK, 0, 0)
+        .is_success());
+    assert!(radio.is_config_committed());
+    assert!(radio
+        .command(fake::ieee802154::SET_PAN, 0xabcd, 0)
+        .is_success());
+    assert!(radio
+        .command(fake::ieee802154::SET_LONG_ADDRESS, 0x89abcdef, 0x01234567)
+        .is_success());
+    assert!(radio
+        .command(fake::ieee802154::SET_TX_POWER, -4i32 as u32, 0)
+        .is_success());
+    assert_eq!(
+        radio
+            .command(fake::ieee802154::SET_CHANNEL, 27, 0)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(!radio.is_config_committed());
+    assert!(radio
+        .command(fake::ieee802154::COMMIT_CONFIG, 0, 0)
+        .is_success());
+    assert!(radio.is_config_committed());
+    assert_eq!(radio.pan(), 0xabcd);
+    assert_eq!(radio.long_address(), 0x0123456789abcdef);
+    assert_eq!(radio.tx_power(), -4);
+    assert_eq!(
+        radio
+            .command(fake::ieee802154::GET_LONG_ADDRESS, 0, 0)
+            .get_success_u64(),
+        Some(0x0123456789abcdef)
+    );
+    assert_eq!(
+        radio
+            .command(fake::ieee802154::GET_TX_POWER, 0, 0)
+            .get_success_u32(),
+        Some(-4i32 as u32)
+    );
+    assert_eq!(
+        radio
+            .command(fake::ieee802154::GET_CHANNEL, 0, 0)
+            .get_success_u32(),
+        Some(26)
+    );
+
+    assert_eq!(
+        radio
+            .command(fake::ieee802154::TRANSMIT, 0x0001, 8)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(radio.take_transmitted_frames(), []);
+
+    // Frames are only written into the ring while it is shared.
+    radio.inject_frame(&[1, 2], &[3]);
+    radio.idle();
+    assert_eq!(radio.pending_rx_frames(), 1);
+}
+
+// Integration test that verifies Ieee802154 works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let radio = fake::Ieee802154::new();
+    kernel.add_driver(&radio);
+    radio.set_loopback(true);
+
+    // A ring with two slots, which holds a single frame.
+    let mut ring = [0; 2 + 2 * 130];
+    let transmitted = core::cell::Cell::new(Option::<(u32, u32)>::None);
+    let received = core::cell::Cell::new(false);
+    share::scope::<
+        (
+            AllowRo<_, { fake::ieee802154::DRIVER_NUM }, { fake::ieee802154::ALLOW_TX }>,
+            AllowRw<_, { fake::ieee802154::DRIVER_NUM }, { fake::ieee802154::ALLOW_RX }>,
+            Subscribe<_, { fake::ieee802154::DRIVER_NUM }, { fake::ieee802154::SUBSCRIBE_TX }>,
+            Subscribe<_, { fake::ieee802154::DRIVER_NUM }, { fake::ieee802154::SUBSCRIBE_RX }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe_tx, subscribe_rx) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::ieee802154::DRIVER_NUM },
+            { fake::ieee802154::ALLOW_TX },
+        >(allow_ro, b"hi")
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::ieee802154::DRIVER_NUM },
+            { fake::ieee802154::ALLOW_RX },
+        >(allow_rw, &mut ring)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::ieee802154::DRIVER_NUM },
+            { fake::ieee802154::SUBSCRIBE_TX },
+        >(subscribe_tx, &transmitted)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::ieee802154::DRIVER_NUM },
+            { fake::ieee802154::SUBSCRIBE_RX },
+        >(subscribe_rx, &received)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::ieee802154::DRIVER_NUM,
+            fake::ieee802154::TRANSMIT,
+            0x0002,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(transmitted.take(), Some((0, 1)));
+        fake::Syscalls::yield_wait();
+        assert!(received.get());
+
+        // The ring is now full, so the next frame is dropped.
+        radio.inject_frame(&[0; 9], b"dropped");
+        fake::SyscallDriver::idle(&*radio);
+        assert_eq!(
+            fake::Syscalls::yield_no_wait(),
+            libtock_platform::YieldNoWaitReturn::NoUpcall
+        );
+        assert_eq!(radio.pending_rx_frames(), 0);
+    });
+    assert_eq!(
+        radio.take_transmitted_frames(),
+        [Ieee802154TxFrame {
+            destination: 0x0002,
+            security: None,
+            payload: b"hi".to_vec(),
+        }]
+    );
+    assert_eq!(ring[..2], [0, 1]);
+    assert_eq!(
+        ring[2..16],
+        [
+            9, 2, 0, // Lengths
+            0x41, 0x88, 0, 0xff, 0xff, 0x02, 0x00, 0xff, 0xff, // MAC header
+            b'h', b'i',
+        ]
+    );
+}
@@ -0,0 +1,237 @@
+//! Fake implementation of the IPC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/10000_ipc.md
+//!
+//! `Ipc` models a system containing the process, which has ID 0, and any number
+//! of other processes created by the test with `add_process`. Each
+//! `IpcProcess` can act as a service for the process, as a client of the
+//! process' service, or both. A process with a handler (see
+//! `IpcProcess::set_handler`) responds to each notification immediately, so
+//! the process receives the response the next time it yields.
+//!
+//! Upcalls carry the length of the relevant shared buffer but a buffer address
+//! of 0, as host addresses do not fit in an upcall argument.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::rc::{Rc, Weak};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Ipc {
+    processes: Rc<Processes>,
+    search: Cell<RoAllowBuffer>,
+}
+
+/// Another process in the fake system, used by tests to communicate with the
+/// process.
+pub struct IpcProcess {
+    processes: Weak<Processes>,
+    id: u32,
+    package_name: String,
+    notifications: Cell<u32>,
+    handler: RefCell<Option<Handler>>,
+}
+
+type Handler = Box<dyn Fn(&mut [u8])>;
+
+// The state shared between Ipc and its processes.
+struct Processes {
+    list: RefCell<Vec<Rc<IpcProcess>>>,
+    // The buffers the process shared with each service, indexed by ID.
+    shared: Vec<Cell<RwAllowBuffer>>,
+}
+
+impl Ipc {
+    pub fn new() -> Rc<Ipc> {
+        Rc::new(Ipc {
+            processes: Rc::new(Processes {
+                list: Default::default(),
+                shared: (0..MAX_PROCESSES).map(|_| Default::default()).collect(),
+            }),
+            search: Default::default(),
+        })
+    }
+
+    /// Adds a process with the given package name, and returns it. The
+    /// process' IDs are assigned in order, starting at 1. Panics if the system
+    /// already contains 8 processes (including the process under test).
+    pub fn add_process(&self, package_name: &str) -> Rc<IpcProcess> {
+        let mut list = self.processes.list.borrow_mut();
+        let id = list.len() as u32 + 1;
+        assert!(id < MAX_PROCESSES, "Too many IPC processes");
+        let process = Rc::new(IpcProcess {
+            processes: Rc::downgrade(&self.processes),
+            id,
+            package_name: package_name.into(),
+            notifications: Cell::new(0),
+            handler: Default::default(),
+        });
+        list.push(process.clone());
+        process
+    }
+
+    fn process(&self, id: u32) -> Option<Rc<IpcProcess>> {
+        let list = self.processes.list.borrow();
+        list.iter().find(|process| process.id == id).cloned()
+    }
+
+    fn discover(&self) -> CommandReturn {
+        let search = self.search.take();
+        let found = self
+            .processes
+            .list
+            .borrow()
+            .iter()
+            .find(|process| process.package_name.as_bytes() == &*search)
+            .map(|process| process.id);
+        self.search.set(search);
+        match found {
+            Some(id) => crate::command_return::success_u32(id),
+            None => crate::command_return::failure(ErrorCode::NoDevice),
+        }
+    }
+}
+
+impl IpcProcess {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn package_name(&self) -> &str {
+        &self.package_name
+    }
+
+    /// Makes this process' service respond to notifications from the process
+    /// under test: `handler` is called with the buffer the process shared with
+    /// this process (which is empty if it has not shared one), then the process
+    /// is notified.
+    pub fn set_handler<F: Fn(&mut [u8]) + 'static>(&self, handler: F) {
+        self.handler.replace(Some(Box::new(handler)));
+    }
+
+    /// Returns the number of times the process under test has notified this
+    /// process since the last call to `take_notifications`.
+    pub fn take_notifications(&self) -> u32 {
+        self.notifications.replace(0)
+    }
+
+    /// Returns a copy of the buffer the process under test has shared with
+    /// this process.
+    pub fn shared_buffer(&self) -> Vec<u8> {
+        self.with_shared_buffer(|buffer| buffer.to_vec())
+    }
+
+    /// Notifies the process under test, acting as a service the process is a
+    /// client of.
+    pub fn notify_client(&self) {
+        let len = self.with_shared_buffer(|buffer| buffer.len() as u32);
+        upcall::schedule(DRIVER_NUM, self.id + 1, (self.id, len, 0))
+            .expect("Unable to schedule upcall {}");
+    }
+
+    /// Notifies the process under test's service, acting as its client. This
+    /// process does not share a buffer with the service.
+    pub fn notify_service(&self) {
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_SERVICE, (self.id, 0, 0))
+            .expect("Unable to schedule upcall {}");
+    }
+
+    // Handles a notification the process under test sent to this process'
+    // service.
+    fn notified(&self) {
+        self.notifications.set(self.notifications.get() + 1);
+        if let Some(handler) = &*self.handler.borrow() {
+            self.with_shared_buffer(|buffer| handler(buffer));
+            self.notify_client();
+        }
+    }
+
+    fn with_shared_buffer<R, F: FnOnce(&mut [u8]) -> R>(&self, f: F) -> R {
+        let processes = self.processes.upgrade().expect("fake::Ipc was dropped");
+        let shared = &processes.shared[self.id as usize];
+        let mut buffer = shared.take();
+        let result = f(&mut buffer);
+        shared.set(buffer);
+        result
+    }
+}
+
+impl crate::fake::SyscallDriver for Ipc {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        // Subscribe number 0 receives notifications from clients, and
+        // subscribe number N + 1 receives notifications from service N.
+        MAX_PROCESSES + 1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SEARCH {
+            Ok(self.search.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        // The read-write buffer number is the ID of the service the buffer is
+        // shared with.
+        match self.processes.shared.get(buffer_num as usize) {
+            Some(shared) => Ok(shared.replace(buffer)),
+            None => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            DISCOVER => self.discover(),
+            NOTIFY_SERVICE => match self.process(argument0) {
+                Some(process) => {
+                    process.notified();
+                    crate::command_return::success()
+                }
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            NOTIFY_CLIENT => match self.process(argument0) {
+                Some(process) => {
+                    process.notifications.set(process.notifications.get() + 1);
+                    crate::command_return::success()
+                }
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const DISCOVER: u32 = 1;
+const NOTIFY_SERVICE: u32 = 2;
+const NOTIFY_CLIENT: u32 = 3;
+
+const SUBSCRIBE_SERVICE: u32 = 0;
+const ALLOW_SEARCH: u32 = 0;
+
+// The number of processes in the system, including the process under test.
+const MAX_PROCESSES: u32 = 8;
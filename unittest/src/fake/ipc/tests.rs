@@ -0,0 +1,89 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use fake::ipc::*;
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ipc = Ipc::new();
+    let sensor = ipc.add_process("org.tockos.sensor");
+    assert_eq!(sensor.id(), 1);
+    assert_eq!(sensor.package_name(), "org.tockos.sensor");
+    assert!(ipc.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(ipc.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(ipc.allow_readwrite(7, RwAllowBuffer::default()).is_ok());
+    assert!(ipc.allow_readwrite(8, RwAllowBuffer::default()).is_err());
+
+    assert!(ipc.command(DRIVER_CHECK, 0, 0).is_success());
+    // No package name is shared.
+    assert_eq!(
+        ipc.command(DISCOVER, 0, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+    assert!(ipc.command(NOTIFY_SERVICE, 1, 0).is_success());
+    assert!(ipc.command(NOTIFY_CLIENT, 1, 0).is_success());
+    assert_eq!(sensor.take_notifications(), 2);
+    assert_eq!(sensor.take_notifications(), 0);
+    assert_eq!(
+        ipc.command(NOTIFY_SERVICE, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(sensor.shared_buffer(), []);
+}
+
+// Integration test that verifies Ipc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ipc = Ipc::new();
+    kernel.add_driver(&ipc);
+    let _logger = ipc.add_process("org.tockos.logger");
+    let sensor = ipc.add_process("org.tockos.sensor");
+    sensor.set_handler(|buffer| buffer.reverse());
+
+    let mut buffer = [1, 2, 3];
+    let response = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    let request = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_SEARCH>,
+            AllowRw<_, DRIVER_NUM, 2>,
+            Subscribe<_, DRIVER_NUM, 3>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SERVICE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, client, service) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SEARCH>(
+            allow_ro,
+            b"org.tockos.sensor",
+        )
+        .unwrap();
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, DISCOVER, 0, 0).get_success_u32(),
+            Some(2)
+        );
+
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, 2>(allow_rw, &mut buffer).unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 3>(client, &response).unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, NOTIFY_SERVICE, 2, 0).is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(response.take(), Some((2, 3, 0)));
+
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SERVICE>(
+            service, &request,
+        )
+        .unwrap();
+        sensor.notify_service();
+        fake::Syscalls::yield_wait();
+        assert_eq!(request.take(), Some((2, 0, 0)));
+    });
+    assert_eq!(buffer, [3, 2, 1]);
+    assert_eq!(sensor.take_notifications(), 1);
+}
@@ -8,6 +8,11 @@ use crate::{ExpectedSyscall, SyscallLogEntry};
 ///
 /// Note that there can only be one `fake::Kernel` instance per thread, as
 /// `fake::Syscalls` uses a thread-local variable to locate the `fake::Kernel`.
+///
+/// For the Memop system call, `fake::Kernel` emulates a process with 16 KiB of
+/// RAM (the last 1 KiB of which is the grant region) and 4 KiB of flash. Both
+/// regions are backed by real memory, and the process break starts at the
+/// beginning of RAM.
 // Note: The kernel's data is actually stored in
 // crate::kernel_data::KERNEL_DATA. See the kernel_data module comment for an
 // explanation.
@@ -34,6 +39,7 @@ impl Kernel {
                 create_location: std::panic::Location::caller(),
                 drivers: Default::default(),
                 expected_syscalls: Default::default(),
+                memory: crate::process_memory::ProcessMemory::new(),
                 syscall_log: Vec::new(),
                 upcall_queue: Default::default(),
             }))
@@ -93,6 +99,35 @@ impl Kernel {
         });
     }
 
+    /// Adds a writeable flash region to the fake process' flash, as if it were
+    /// declared in the process' TBF header. `offset` is relative to the start
+    /// of the process' flash region.
+    pub fn add_writeable_flash_region(&self, offset: usize, len: usize) {
+        with_kernel_data(|kernel_data| {
+            kernel_data
+                .unwrap()
+                .memory
+                .add_writeable_flash_region(offset, len)
+        });
+    }
+
+    /// Returns the current process break.
+    pub fn app_break(&self) -> *mut u8 {
+        with_kernel_data(|kernel_data| kernel_data.unwrap().memory.app_break())
+    }
+
+    /// Returns the address most recently passed to the Memop operation that
+    /// specifies the start of the stack, or `None` if it has not been called.
+    pub fn debug_stack_start(&self) -> Option<usize> {
+        with_kernel_data(|kernel_data| kernel_data.unwrap().memory.debug_stack_start())
+    }
+
+    /// Returns the address most recently passed to the Memop operation that
+    /// specifies the start of the heap, or `None` if it has not been called.
+    pub fn debug_heap_start(&self) -> Option<usize> {
+        with_kernel_data(|kernel_data| kernel_data.unwrap().memory.debug_heap_start())
+    }
+
     /// Returns the system call log and empties it.
     pub fn take_syscall_log(&self) -> Vec<SyscallLogEntry> {
         with_kernel_data(|kernel_data| std::mem::take(&mut kernel_data.unwrap().syscall_log))
@@ -0,0 +1,204 @@
+//! Fake implementation of the key-value storage API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/50003_key_value.md
+//!
+//! `KeyValue` keeps its store in memory. Like a real store, it has limited
+//! space: keys and values longer than `set_max_key_len`/`set_max_value_len`
+//! are rejected with SIZE, and writes that would make the store exceed
+//! `set_capacity` bytes (counting the lengths of all keys and values) fail with
+//! NOMEM. Operations complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::BTreeMap;
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct KeyValue {
+    store: RefCell<BTreeMap<Vec<u8>, Vec<u8>>>,
+    max_key_len: Cell<usize>,
+    max_value_len: Cell<usize>,
+    capacity: Cell<usize>,
+    key: Cell<RoAllowBuffer>,
+    input: Cell<RoAllowBuffer>,
+    output: Cell<RwAllowBuffer>,
+}
+
+impl KeyValue {
+    pub fn new() -> std::rc::Rc<KeyValue> {
+        std::rc::Rc::new(KeyValue {
+            store: Default::default(),
+            max_key_len: Cell::new(DEFAULT_MAX_KEY_LEN),
+            max_value_len: Cell::new(DEFAULT_MAX_VALUE_LEN),
+            capacity: Cell::new(DEFAULT_CAPACITY),
+            key: Default::default(),
+            input: Default::default(),
+            output: Default::default(),
+        })
+    }
+
+    pub fn set_max_key_len(&self, len: usize) {
+        self.max_key_len.set(len);
+    }
+
+    pub fn set_max_value_len(&self, len: usize) {
+        self.max_value_len.set(len);
+    }
+
+    /// Sets the number of bytes the store can hold, counting both keys and
+    /// values.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.set(capacity);
+    }
+
+    /// Stores a value directly, bypassing the size limits. Used to populate the
+    /// store before the code under test runs.
+    pub fn insert(&self, key: &[u8], value: &[u8]) {
+        self.store.borrow_mut().insert(key.into(), value.into());
+    }
+
+    /// Returns the value stored under `key`, if any.
+    pub fn value(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.store.borrow().get(key).cloned()
+    }
+
+    // Returns the number of bytes used by the store, excluding the entry for
+    // `key`.
+    fn used_excluding(&self, key: &[u8]) -> usize {
+        self.store
+            .borrow()
+            .iter()
+            .filter(|(stored_key, _)| stored_key.as_slice() != key)
+            .map(|(key, value)| key.len() + value.len())
+            .sum()
+    }
+
+    // Performs an operation, returning the length of the value on success and
+    // the error and value length on failure.
+    fn operation(&self, command_num: u32) -> Result<usize, (ErrorCode, usize)> {
+        let key = self.key.take();
+        let input = self.input.take();
+        let mut output = self.output.take();
+        let result = self.operation_with_buffers(command_num, &key, &input, &mut output);
+        self.key.set(key);
+        self.input.set(input);
+        self.output.set(output);
+        result
+    }
+
+    fn operation_with_buffers(
+        &self,
+        command_num: u32,
+        key: &[u8],
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, (ErrorCode, usize)> {
+        if key.len() > self.max_key_len.get() {
+            return Err((ErrorCode::Size, 0));
+        }
+        let exists = self.store.borrow().contains_key(key);
+        match command_num {
+            GET => {
+                let store = self.store.borrow();
+                let value = store.get(key).ok_or((ErrorCode::NoSupport, 0))?;
+                let len = value.len().min(output.len());
+                output[..len].copy_from_slice(&value[..len]);
+                if len < value.len() {
+                    return Err((ErrorCode::Size, value.len()));
+                }
+                Ok(value.len())
+            }
+            DELETE => match self.store.borrow_mut().remove(key) {
+                Some(_) => Ok(0),
+                None => Err((ErrorCode::NoSupport, 0)),
+            },
+            _ => {
+                if (command_num == ADD && exists) || (command_num == UPDATE && !exists) {
+                    return Err((ErrorCode::NoSupport, 0));
+                }
+                if input.len() > self.max_value_len.get() {
+                    return Err((ErrorCode::Size, 0));
+                }
+                if self.used_excluding(key) + key.len() + input.len() > self.capacity.get() {
+                    return Err((ErrorCode::NoMem, 0));
+                }
+                self.insert(key, input);
+                Ok(input.len())
+            }
+        }
+    }
+}
+
+impl crate::fake::SyscallDriver for KeyValue {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key.replace(buffer)),
+            ALLOW_INPUT => Ok(self.input.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_OUTPUT {
+            Ok(self.output.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => return crate::command_return::success(),
+            GET | SET | DELETE | ADD | UPDATE => {}
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        let (status, len) = match self.operation(command_num) {
+            Ok(len) => (0, len),
+            Err((error, len)) => (error as u32, len),
+        };
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (status, len as u32, 0))
+            .expect("Unable to schedule upcall {}");
+        crate::command_return::success()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x50003;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const GET: u32 = 1;
+const SET: u32 = 2;
+const DELETE: u32 = 3;
+const ADD: u32 = 4;
+const UPDATE: u32 = 5;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_INPUT: u32 = 1;
+const ALLOW_OUTPUT: u32 = 0;
+
+const DEFAULT_MAX_KEY_LEN: usize = 64;
+const DEFAULT_MAX_VALUE_LEN: usize = 256;
+const DEFAULT_CAPACITY: usize = 4096;
@@ -0,0 +1,97 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let kv = fake::KeyValue::new();
+    assert!(kv.command(fake::kv::DRIVER_CHECK, 1, 2).is_success());
+    assert!(kv.allow_readonly(1, RoAllowBuffer::default()).is_ok());
+    assert!(kv.allow_readonly(2, RoAllowBuffer::default()).is_err());
+    assert!(kv.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(kv.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+    assert_eq!(
+        kv.command(6, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+
+    kv.insert(b"key", b"value");
+    assert_eq!(kv.value(b"key"), Some(b"value".to_vec()));
+    assert_eq!(kv.value(b"other"), None);
+}
+
+// Integration test that verifies KeyValue works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let kv = fake::KeyValue::new();
+    kernel.add_driver(&kv);
+    kv.set_capacity(10);
+
+    let mut output = [0; 2];
+    let done = core::cell::Cell::new(Option::<(u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::kv::DRIVER_NUM }, { fake::kv::ALLOW_KEY }>,
+            AllowRo<_, { fake::kv::DRIVER_NUM }, { fake::kv::ALLOW_INPUT }>,
+            AllowRw<_, { fake::kv::DRIVER_NUM }, { fake::kv::ALLOW_OUTPUT }>,
+            Subscribe<_, { fake::kv::DRIVER_NUM }, { fake::kv::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_input, allow_output, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::kv::DRIVER_NUM },
+            { fake::kv::ALLOW_KEY },
+        >(allow_key, b"abc")
+        .unwrap();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::kv::DRIVER_NUM },
+            { fake::kv::ALLOW_INPUT },
+        >(allow_input, b"1234")
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::kv::DRIVER_NUM },
+            { fake::kv::ALLOW_OUTPUT },
+        >(allow_output, &mut output)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::kv::DRIVER_NUM },
+            { fake::kv::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        let command = |command_num| {
+            assert!(fake::Syscalls::command(fake::kv::DRIVER_NUM, command_num, 0, 0).is_success());
+            fake::Syscalls::yield_wait();
+            done.take()
+        };
+        let no_support = ErrorCode::NoSupport as u32;
+        assert_eq!(command(fake::kv::UPDATE), Some((no_support, 0)));
+        assert_eq!(command(fake::kv::ADD), Some((0, 4)));
+        assert_eq!(command(fake::kv::ADD), Some((no_support, 0)));
+        assert_eq!(command(fake::kv::GET), Some((ErrorCode::Size as u32, 4)));
+
+        // Replacing the value does not count the old value against the
+        // capacity, but a second entry would exceed it.
+        assert_eq!(command(fake::kv::SET), Some((0, 4)));
+        kv.insert(b"wxyz", b"");
+        assert_eq!(command(fake::kv::SET), Some((ErrorCode::NoMem as u32, 0)));
+
+        assert_eq!(command(fake::kv::DELETE), Some((0, 0)));
+        assert_eq!(command(fake::kv::GET), Some((no_support, 0)));
+    });
+    assert_eq!(output, *b"12");
+}
@@ -9,23 +9,73 @@
 //! `use libtock_unittest::fake` and refer to the type with the `fake::` prefix
 //! (e.g. `fake::Console`).
 
+mod adc;
+mod aes;
+mod alarm;
+mod ambient_light;
+mod ble;
 mod buttons;
+mod buzzer;
 mod console;
 mod gpio;
+mod hmac;
+mod humidity;
+mod i2c_master;
+mod ieee802154;
+mod ipc;
 mod kernel;
+mod kv;
 mod leds;
 mod low_level_debug;
+mod ninedof;
+mod nonvolatile_storage;
+mod proximity;
+mod pwm;
+mod rng;
+mod screen;
+mod sha;
+mod sound_pressure;
+mod spi_controller;
 mod syscall_driver;
 mod syscalls;
+mod temperature;
+mod text_screen;
+mod touch;
+mod udp;
 
+pub use self::aes::Aes;
+pub use self::hmac::Hmac;
+pub use adc::Adc;
+pub use alarm::Alarm;
+pub use ambient_light::AmbientLight;
+pub use ble::Ble;
 pub use buttons::Buttons;
+pub use buzzer::{Buzzer, Tone};
 pub use console::Console;
 pub use gpio::{Gpio, GpioMode, InterruptEdge, PullMode};
+pub use humidity::Humidity;
+pub use i2c_master::{I2cDevice, I2cMaster};
+pub use ieee802154::{Ieee802154, Ieee802154TxFrame};
+pub use ipc::{Ipc, IpcProcess};
 pub use kernel::Kernel;
+pub use kv::KeyValue;
 pub use leds::Leds;
 pub use low_level_debug::{LowLevelDebug, Message};
+pub use ninedof::Ninedof;
+pub use nonvolatile_storage::NonvolatileStorage;
+pub use proximity::Proximity;
+pub use pwm::{Pwm, PwmOutput};
+pub use rng::Rng;
+pub use screen::Screen;
+pub use sha::Sha;
+pub use sound_pressure::SoundPressure;
+pub use spi_controller::{SpiController, SpiFrame};
 pub use syscall_driver::SyscallDriver;
 pub use syscalls::Syscalls;
+pub use temperature::Temperature;
+pub use text_screen::TextScreen;
+pub use touch::{RecordedTouch, Touch, TouchPoint};
+pub use udp::{Udp, UdpSocket};
 
 #[cfg(test)]
 mod kernel_tests;
@@ -0,0 +1,130 @@
+//! Fake implementation of the Ninedof API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60004_ninedof.md
+//!
+//! `Ninedof` returns samples that the test scripted ahead of time. Each sensor
+//! (accelerometer, magnetometer, and gyroscope) has its own queue of samples. A
+//! read command takes the next sample from the corresponding queue and delivers
+//! it through an upcall. If the queue is empty, the read stays outstanding until
+//! the test adds a sample for that sensor.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::upcall;
+
+pub struct Ninedof {
+    // The sensor (identified by its read command number) an outstanding read is
+    // waiting for, if any.
+    pending: Cell<Option<u32>>,
+    accelerometer: RefCell<VecDeque<(i32, i32, i32)>>,
+    magnetometer: RefCell<VecDeque<(i32, i32, i32)>>,
+    gyroscope: RefCell<VecDeque<(i32, i32, i32)>>,
+}
+
+impl Ninedof {
+    pub fn new() -> std::rc::Rc<Ninedof> {
+        std::rc::Rc::new(Ninedof {
+            pending: Cell::new(None),
+            accelerometer: Default::default(),
+            magnetometer: Default::default(),
+            gyroscope: Default::default(),
+        })
+    }
+
+    /// Returns true if a read is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.pending.get().is_some()
+    }
+
+    /// Queues an (x, y, z) sample for the accelerometer.
+    pub fn add_accelerometer_sample(&self, sample: (i32, i32, i32)) {
+        self.add_sample(READ_ACCELEROMETER, sample);
+    }
+
+    /// Queues an (x, y, z) sample for the magnetometer.
+    pub fn add_magnetometer_sample(&self, sample: (i32, i32, i32)) {
+        self.add_sample(READ_MAGNETOMETER, sample);
+    }
+
+    /// Queues an (x, y, z) sample for the gyroscope.
+    pub fn add_gyroscope_sample(&self, sample: (i32, i32, i32)) {
+        self.add_sample(READ_GYROSCOPE, sample);
+    }
+
+    /// Returns the number of scripted samples that have not been read yet,
+    /// across all three sensors.
+    pub fn remaining_samples(&self) -> usize {
+        self.accelerometer.borrow().len()
+            + self.magnetometer.borrow().len()
+            + self.gyroscope.borrow().len()
+    }
+
+    fn add_sample(&self, sensor: u32, sample: (i32, i32, i32)) {
+        self.samples(sensor).borrow_mut().push_back(sample);
+        if self.pending.get() == Some(sensor) {
+            self.deliver_sample(sensor);
+        }
+    }
+
+    fn samples(&self, sensor: u32) -> &RefCell<VecDeque<(i32, i32, i32)>> {
+        match sensor {
+            READ_ACCELEROMETER => &self.accelerometer,
+            READ_MAGNETOMETER => &self.magnetometer,
+            _ => &self.gyroscope,
+        }
+    }
+
+    // Completes the outstanding read if a sample is available for it.
+    fn deliver_sample(&self, sensor: u32) {
+        if let Some((x, y, z)) = self.samples(sensor).borrow_mut().pop_front() {
+            self.pending.set(None);
+            upcall::schedule(
+                DRIVER_NUM,
+                SUBSCRIBE_READING,
+                (x as u32, y as u32, z as u32),
+            )
+            .expect("Unable to schedule upcall {}");
+        }
+    }
+}
+
+impl crate::fake::SyscallDriver for Ninedof {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            READ_ACCELEROMETER | READ_MAGNETOMETER | READ_GYROSCOPE => {
+                if self.is_busy() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.pending.set(Some(command_num));
+                self.deliver_sample(command_num);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x60004;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const READ_ACCELEROMETER: u32 = 1;
+const READ_MAGNETOMETER: u32 = 100;
+const READ_GYROSCOPE: u32 = 200;
+const SUBSCRIBE_READING: u32 = 0;
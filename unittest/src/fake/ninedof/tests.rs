@@ -0,0 +1,80 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ninedof = fake::Ninedof::new();
+    assert!(ninedof
+        .command(fake::ninedof::DRIVER_CHECK, 1, 2)
+        .is_success());
+    assert_eq!(
+        ninedof.command(3, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+
+    // With no scripted sample, the read stays outstanding.
+    assert!(ninedof
+        .command(fake::ninedof::READ_GYROSCOPE, 0, 0)
+        .is_success());
+    assert!(ninedof.is_busy());
+    assert_eq!(
+        ninedof
+            .command(fake::ninedof::READ_ACCELEROMETER, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    // Samples for other sensors do not complete the read.
+    ninedof.add_magnetometer_sample((1, 2, 3));
+    assert!(ninedof.is_busy());
+    assert_eq!(ninedof.remaining_samples(), 1);
+}
+
+// Integration test that verifies Ninedof works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ninedof = fake::Ninedof::new();
+    kernel.add_driver(&ninedof);
+
+    let sample = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::ninedof::DRIVER_NUM },
+            { fake::ninedof::SUBSCRIBE_READING },
+        >(subscribe, &sample)
+        .unwrap();
+
+        ninedof.add_accelerometer_sample((1, -2, 3));
+        assert!(fake::Syscalls::command(
+            fake::ninedof::DRIVER_NUM,
+            fake::ninedof::READ_ACCELEROMETER,
+            0,
+            0
+        )
+        .is_success());
+        assert!(!ninedof.is_busy());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sample.take(), Some((1, -2i32 as u32, 3)));
+
+        assert!(fake::Syscalls::command(
+            fake::ninedof::DRIVER_NUM,
+            fake::ninedof::READ_MAGNETOMETER,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        ninedof.add_magnetometer_sample((4, 5, 6));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sample.take(), Some((4, 5, 6)));
+        assert_eq!(ninedof.remaining_samples(), 0);
+    });
+}
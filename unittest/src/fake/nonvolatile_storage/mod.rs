@@ -0,0 +1,170 @@
+//! Fake implementation of the nonvolatile storage API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/50001_nonvolatile_storage.md
+//!
+//! `NonvolatileStorage` is backed by a `Vec<u8>`, which starts out erased (all
+//! 0xFF). Tests can pre-load an image into it with `load_image` and inspect it
+//! with `contents`. `set_write_error` makes writes fail, which simulates a worn
+//! out or write-protected device. Reads and writes complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct NonvolatileStorage {
+    storage: RefCell<Vec<u8>>,
+    write_error: Cell<Option<ErrorCode>>,
+    read_buffer: Cell<RwAllowBuffer>,
+    write_buffer: Cell<RoAllowBuffer>,
+}
+
+impl NonvolatileStorage {
+    /// Creates a fake storage device containing `size` erased bytes.
+    pub fn new(size: usize) -> std::rc::Rc<NonvolatileStorage> {
+        std::rc::Rc::new(NonvolatileStorage {
+            storage: RefCell::new(vec![ERASED; size]),
+            write_error: Cell::new(None),
+            read_buffer: Default::default(),
+            write_buffer: Default::default(),
+        })
+    }
+
+    /// Copies `image` to the start of storage. Panics if the image is larger
+    /// than the storage.
+    pub fn load_image(&self, image: &[u8]) {
+        let mut storage = self.storage.borrow_mut();
+        assert!(
+            image.len() <= storage.len(),
+            "Image is larger than the storage"
+        );
+        storage[..image.len()].copy_from_slice(image);
+    }
+
+    /// Returns a copy of the storage's contents.
+    pub fn contents(&self) -> Vec<u8> {
+        self.storage.borrow().clone()
+    }
+
+    /// Makes all writes fail with the given error, or succeed if `None`.
+    pub fn set_write_error(&self, error: Option<ErrorCode>) {
+        self.write_error.set(error);
+    }
+
+    // Returns the range of storage accessed by an operation, or Invalid if it
+    // extends past the end of storage.
+    fn range(&self, offset: u32, len: u32) -> Result<core::ops::Range<usize>, ErrorCode> {
+        let start = offset as usize;
+        match start.checked_add(len as usize) {
+            Some(end) if end <= self.storage.borrow().len() => Ok(start..end),
+            _ => Err(ErrorCode::Invalid),
+        }
+    }
+
+    fn read(&self, offset: u32, len: u32) -> Result<(), ErrorCode> {
+        let range = self.range(offset, len)?;
+        let mut buffer = self.read_buffer.take();
+        let result = match buffer.get_mut(..range.len()) {
+            None => Err(ErrorCode::Size),
+            Some(buffer) => {
+                buffer.copy_from_slice(&self.storage.borrow()[range]);
+                Ok(())
+            }
+        };
+        self.read_buffer.set(buffer);
+        result?;
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_READ, (len, 0, 0))
+            .expect("Unable to schedule upcall {}");
+        Ok(())
+    }
+
+    fn write(&self, offset: u32, len: u32) -> Result<(), ErrorCode> {
+        let range = self.range(offset, len)?;
+        if let Some(error) = self.write_error.get() {
+            return Err(error);
+        }
+        let buffer = self.write_buffer.take();
+        let result = match buffer.get(..range.len()) {
+            None => Err(ErrorCode::Size),
+            Some(data) => {
+                self.storage.borrow_mut()[range].copy_from_slice(data);
+                Ok(())
+            }
+        };
+        self.write_buffer.set(buffer);
+        result?;
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_WRITE, (len, 0, 0))
+            .expect("Unable to schedule upcall {}");
+        Ok(())
+    }
+}
+
+impl crate::fake::SyscallDriver for NonvolatileStorage {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        2
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let result = match command_num {
+            DRIVER_CHECK => Ok(()),
+            SIZE => return crate::command_return::success_u32(self.storage.borrow().len() as u32),
+            READ => self.read(argument0, argument1),
+            WRITE => self.write(argument0, argument1),
+            _ => Err(ErrorCode::NoSupport),
+        };
+        match result {
+            Ok(()) => crate::command_return::success(),
+            Err(error) => crate::command_return::failure(error),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x50001;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SIZE: u32 = 1;
+const READ: u32 = 2;
+const WRITE: u32 = 3;
+
+const SUBSCRIBE_READ: u32 = 0;
+const SUBSCRIBE_WRITE: u32 = 1;
+const ALLOW_READ: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+
+// The value of an erased byte of flash.
+const ERASED: u8 = 0xff;
@@ -0,0 +1,133 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let storage = fake::NonvolatileStorage::new(8);
+    assert!(storage
+        .command(fake::nonvolatile_storage::DRIVER_CHECK, 1, 2)
+        .is_success());
+    assert_eq!(
+        storage
+            .command(fake::nonvolatile_storage::SIZE, 0, 0)
+            .get_success_u32(),
+        Some(8)
+    );
+    assert!(storage.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(storage.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(storage.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(storage
+        .allow_readwrite(1, RwAllowBuffer::default())
+        .is_err());
+
+    // Operations may not extend past the end of storage or the shared buffer.
+    assert_eq!(
+        storage
+            .command(fake::nonvolatile_storage::READ, 4, 5)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        storage
+            .command(fake::nonvolatile_storage::WRITE, 0, 1)
+            .get_failure(),
+        Some(ErrorCode::Size)
+    );
+
+    storage.load_image(&[1, 2]);
+    assert_eq!(
+        storage.contents(),
+        [1, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+}
+
+// Integration test that verifies NonvolatileStorage works with fake::Kernel
+// and libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let storage = fake::NonvolatileStorage::new(8);
+    kernel.add_driver(&storage);
+
+    let mut buffer = [0; 3];
+    let read = core::cell::Cell::new(Option::<(u32,)>::None);
+    let write = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::nonvolatile_storage::DRIVER_NUM }, 0>,
+            AllowRw<_, { fake::nonvolatile_storage::DRIVER_NUM }, 0>,
+            Subscribe<_, { fake::nonvolatile_storage::DRIVER_NUM }, 0>,
+            Subscribe<_, { fake::nonvolatile_storage::DRIVER_NUM }, 1>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe_read, subscribe_write) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::nonvolatile_storage::DRIVER_NUM },
+            { fake::nonvolatile_storage::ALLOW_WRITE },
+        >(allow_ro, &[5, 6])
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::nonvolatile_storage::DRIVER_NUM },
+            { fake::nonvolatile_storage::ALLOW_READ },
+        >(allow_rw, &mut buffer)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::nonvolatile_storage::DRIVER_NUM },
+            { fake::nonvolatile_storage::SUBSCRIBE_READ },
+        >(subscribe_read, &read)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::nonvolatile_storage::DRIVER_NUM },
+            { fake::nonvolatile_storage::SUBSCRIBE_WRITE },
+        >(subscribe_write, &write)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::nonvolatile_storage::DRIVER_NUM,
+            fake::nonvolatile_storage::WRITE,
+            1,
+            2
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(write.take(), Some((2,)));
+
+        storage.set_write_error(Some(ErrorCode::Fail));
+        assert_eq!(
+            fake::Syscalls::command(
+                fake::nonvolatile_storage::DRIVER_NUM,
+                fake::nonvolatile_storage::WRITE,
+                0,
+                1
+            )
+            .get_failure(),
+            Some(ErrorCode::Fail)
+        );
+
+        assert!(fake::Syscalls::command(
+            fake::nonvolatile_storage::DRIVER_NUM,
+            fake::nonvolatile_storage::READ,
+            0,
+            3
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(read.take(), Some((3,)));
+    });
+    assert_eq!(buffer, [0xff, 5, 6]);
+}
@@ -0,0 +1,124 @@
+//! Fake implementation of the proximity API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60005_proximity.md
+//!
+//! Like the real API, `Proximity` takes a reading when the process requests
+//! one and reports it through an upcall. Tests provide readings either with
+//! `set_value` (which completes an outstanding read) or `set_value_sync` (which
+//! is applied as soon as the next read starts). A read on interrupt only
+//! completes once a reading is outside the thresholds the process selected.
+//! `set_error_sync` makes the next read fail instead.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+
+pub struct Proximity {
+    request: Cell<Option<Request>>,
+    upcall_on_command: Cell<Option<Result<u8, ErrorCode>>>,
+}
+
+impl Proximity {
+    pub fn new() -> std::rc::Rc<Proximity> {
+        std::rc::Rc::new(Proximity {
+            request: Cell::new(None),
+            upcall_on_command: Cell::new(None),
+        })
+    }
+
+    /// Returns true if a read is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.request.get().is_some()
+    }
+
+    /// Returns the `(low, high)` thresholds of the outstanding read on
+    /// interrupt, if there is one.
+    pub fn thresholds(&self) -> Option<(u8, u8)> {
+        match self.request.get() {
+            Some(Request::OnInterrupt { low, high }) => Some((low, high)),
+            _ => None,
+        }
+    }
+
+    /// Provides a reading of the given proximity, which completes the
+    /// outstanding read if there is one and the reading satisfies it.
+    pub fn set_value(&self, value: u8) {
+        let done = match self.request.get() {
+            None => false,
+            Some(Request::Read) => true,
+            Some(Request::OnInterrupt { low, high }) => value < low || value > high,
+        };
+        if done {
+            self.request.set(None);
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_READING, (value.into(), 0, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+
+    /// Provides a reading of the given proximity when the next read starts.
+    pub fn set_value_sync(&self, value: u8) {
+        self.upcall_on_command.set(Some(Ok(value)));
+    }
+
+    /// Makes the next read fail with the given error.
+    pub fn set_error_sync(&self, error: ErrorCode) {
+        self.upcall_on_command.set(Some(Err(error)));
+    }
+
+    fn start(&self, request: Request) -> CommandReturn {
+        if self.request.get().is_some() {
+            return crate::command_return::failure(ErrorCode::Busy);
+        }
+        match self.upcall_on_command.take() {
+            Some(Err(error)) => return crate::command_return::failure(error),
+            Some(Ok(value)) => {
+                self.request.set(Some(request));
+                self.set_value(value);
+            }
+            None => self.request.set(Some(request)),
+        }
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Proximity {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            READ => self.start(Request::Read),
+            READ_ON_INTERRUPT => self.start(Request::OnInterrupt {
+                low: argument0 as u8,
+                high: argument1 as u8,
+            }),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, Copy)]
+enum Request {
+    Read,
+    OnInterrupt { low: u8, high: u8 },
+}
+
+const DRIVER_NUM: u32 = 0x60005;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const READ: u32 = 1;
+const READ_ON_INTERRUPT: u32 = 2;
+const SUBSCRIBE_READING: u32 = 0;
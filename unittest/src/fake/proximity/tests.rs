@@ -0,0 +1,90 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let proximity = fake::Proximity::new();
+    assert!(proximity
+        .command(fake::proximity::DRIVER_CHECK, 1, 2)
+        .is_success());
+
+    assert!(proximity
+        .command(fake::proximity::READ_ON_INTERRUPT, 10, 200)
+        .is_success());
+    assert!(proximity.is_busy());
+    assert_eq!(proximity.thresholds(), Some((10, 200)));
+    assert_eq!(
+        proximity.command(fake::proximity::READ, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    // Readings within the thresholds do not complete the read.
+    proximity.set_value(100);
+    assert!(proximity.is_busy());
+
+    let proximity = fake::Proximity::new();
+    proximity.set_error_sync(ErrorCode::Fail);
+    assert_eq!(
+        proximity.command(fake::proximity::READ, 0, 0).get_failure(),
+        Some(ErrorCode::Fail)
+    );
+    assert!(!proximity.is_busy());
+    assert_eq!(proximity.thresholds(), None);
+}
+
+// Integration test that verifies Proximity works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let proximity = fake::Proximity::new();
+    kernel.add_driver(&proximity);
+
+    let reading = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::proximity::DRIVER_NUM },
+            { fake::proximity::SUBSCRIBE_READING },
+        >(subscribe, &reading)
+        .unwrap();
+
+        assert!(
+            fake::Syscalls::command(fake::proximity::DRIVER_NUM, fake::proximity::READ, 0, 0)
+                .is_success()
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        proximity.set_value(40);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((40,)));
+
+        assert!(fake::Syscalls::command(
+            fake::proximity::DRIVER_NUM,
+            fake::proximity::READ_ON_INTERRUPT,
+            20,
+            60
+        )
+        .is_success());
+        proximity.set_value(20);
+        proximity.set_value(60);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        proximity.set_value(61);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((61,)));
+
+        proximity.set_value_sync(5);
+        assert!(fake::Syscalls::command(
+            fake::proximity::DRIVER_NUM,
+            fake::proximity::READ_ON_INTERRUPT,
+            20,
+            60
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((5,)));
+    });
+}
@@ -0,0 +1,108 @@
+//! Fake implementation of the PWM API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00010_pwm.md
+//!
+//! `Pwm` records the signal each pin is generating, so tests can check the
+//! frequencies and duty cycles a process selected. Every pin has the same
+//! maximum frequency, which tests can change with `set_max_frequency_hz`.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct Pwm {
+    max_frequency_hz: Cell<u32>,
+    outputs: RefCell<Vec<Option<PwmOutput>>>,
+}
+
+/// The signal a `Pwm` pin is generating. The duty cycle is in hundredths of a
+/// percent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PwmOutput {
+    pub frequency_hz: u32,
+    pub duty_cycle: u32,
+}
+
+impl Pwm {
+    /// Creates a PWM controller with `pin_count` pins, which are all stopped.
+    pub fn new(pin_count: usize) -> std::rc::Rc<Pwm> {
+        std::rc::Rc::new(Pwm {
+            max_frequency_hz: Cell::new(DEFAULT_MAX_FREQUENCY_HZ),
+            outputs: RefCell::new(vec![None; pin_count]),
+        })
+    }
+
+    pub fn set_max_frequency_hz(&self, max_frequency_hz: u32) {
+        self.max_frequency_hz.set(max_frequency_hz);
+    }
+
+    /// Returns the signal `pin` is generating, or `None` if it is stopped.
+    /// Panics if `pin` does not exist.
+    pub fn output(&self, pin: usize) -> Option<PwmOutput> {
+        self.outputs.borrow()[pin]
+    }
+}
+
+impl crate::fake::SyscallDriver for Pwm {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        0
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let mut outputs = self.outputs.borrow_mut();
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            START => {
+                let output = match outputs.get_mut((argument0 & 0xffff) as usize) {
+                    Some(output) => output,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                let duty_cycle = argument0 >> 16;
+                if argument1 == 0 || argument1 > self.max_frequency_hz.get() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                if duty_cycle > MAX_DUTY_CYCLE {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                *output = Some(PwmOutput {
+                    frequency_hz: argument1,
+                    duty_cycle,
+                });
+                crate::command_return::success()
+            }
+            STOP => match outputs.get_mut(argument0 as usize) {
+                Some(output) => {
+                    *output = None;
+                    crate::command_return::success()
+                }
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            MAX_FREQUENCY => match outputs.get(argument0 as usize) {
+                Some(_) => crate::command_return::success_u32(self.max_frequency_hz.get()),
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            PIN_COUNT => crate::command_return::success_u32(outputs.len() as u32),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const START: u32 = 1;
+const STOP: u32 = 2;
+const MAX_FREQUENCY: u32 = 3;
+const PIN_COUNT: u32 = 5;
+
+const DEFAULT_MAX_FREQUENCY_HZ: u32 = 16_000_000;
+const MAX_DUTY_CYCLE: u32 = 10_000;
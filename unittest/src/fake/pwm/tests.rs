@@ -0,0 +1,64 @@
+use crate::fake;
+use fake::pwm::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let pwm = Pwm::new(2);
+    assert!(pwm.command(DRIVER_CHECK, 0, 0).is_success());
+    assert_eq!(pwm.command(PIN_COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(
+        pwm.command(MAX_FREQUENCY, 1, 0).get_success_u32(),
+        Some(DEFAULT_MAX_FREQUENCY_HZ)
+    );
+    assert_eq!(
+        pwm.command(MAX_FREQUENCY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(pwm.command(START, 7500 << 16 | 1, 1000).is_success());
+    assert_eq!(pwm.output(0), None);
+    assert_eq!(
+        pwm.output(1),
+        Some(PwmOutput {
+            frequency_hz: 1000,
+            duty_cycle: 7500
+        })
+    );
+    assert_eq!(
+        pwm.command(START, 10_001 << 16, 1000).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    pwm.set_max_frequency_hz(500);
+    assert_eq!(
+        pwm.command(START, 0, 1000).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(pwm.command(STOP, 1, 0).is_success());
+    assert_eq!(pwm.output(1), None);
+    assert_eq!(
+        pwm.command(STOP, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies Pwm works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let pwm = Pwm::new(2);
+    kernel.add_driver(&pwm);
+    assert!(fake::Syscalls::command(DRIVER_NUM, DRIVER_CHECK, 0, 0).is_success());
+    assert!(fake::Syscalls::command(DRIVER_NUM, START, 5000 << 16, 20).is_success());
+    assert_eq!(
+        pwm.output(0),
+        Some(PwmOutput {
+            frequency_hz: 20,
+            duty_cycle: 5000
+        })
+    );
+}
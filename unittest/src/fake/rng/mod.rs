@@ -0,0 +1,108 @@
+//! Fake implementation of the RNG API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40001_rng.md
+//!
+//! Like the real API, `Rng` fills the shared buffer with random bytes and then
+//! calls the upcall. The bytes are generated by a deterministic PRNG, which
+//! tests can seed with `set_seed`. `set_max_fill` limits the number of bytes
+//! each request returns, which simulates an entropy source that cannot satisfy
+//! a request all at once.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::RwAllowBuffer;
+
+pub struct Rng {
+    state: Cell<u64>,
+    max_fill: Cell<usize>,
+    buffer: Cell<RwAllowBuffer>,
+}
+
+impl Rng {
+    pub fn new() -> std::rc::Rc<Rng> {
+        std::rc::Rc::new(Rng {
+            state: Cell::new(0),
+            max_fill: Cell::new(usize::MAX),
+            buffer: Default::default(),
+        })
+    }
+
+    /// Resets the PRNG to the given seed. The fake produces the same byte
+    /// sequence every time it is given the same seed.
+    pub fn set_seed(&self, seed: u64) {
+        self.state.set(seed);
+    }
+
+    /// Limits the number of bytes returned by each request.
+    pub fn set_max_fill(&self, max_fill: usize) {
+        self.max_fill.set(max_fill);
+    }
+
+    // Returns the next byte from the PRNG, which is an implementation of
+    // SplitMix64.
+    fn next_byte(&self) -> u8 {
+        let state = self.state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.state.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31)) as u8
+    }
+}
+
+impl crate::fake::SyscallDriver for Rng {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_BUFFER {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => {}
+            GET_BYTES => {
+                let mut buffer = self.buffer.take();
+                let count = (argument0 as usize)
+                    .min(buffer.len())
+                    .min(self.max_fill.get());
+                for byte in &mut buffer[..count] {
+                    *byte = self.next_byte();
+                }
+                self.buffer.set(buffer);
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, count as u32, 0))
+                    .expect("Unable to schedule upcall {}");
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x40001;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const GET_BYTES: u32 = 1;
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_BUFFER: u32 = 0;
@@ -0,0 +1,80 @@
+use crate::fake;
+use crate::RwAllowBuffer;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let rng = fake::Rng::new();
+    assert!(rng.command(fake::rng::DRIVER_CHECK, 1, 2).is_success());
+    assert!(rng.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(rng.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+}
+
+// Integration test that verifies Rng works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let rng = fake::Rng::new();
+    kernel.add_driver(&rng);
+
+    // Fills buffer using the given number of bytes per request, and returns
+    // the number of bytes each request returned.
+    let fill = |buffer: &mut [u8], requested: u32| -> u32 {
+        let done = core::cell::Cell::new(Option::<(u32, u32)>::None);
+        share::scope::<
+            (
+                AllowRw<_, { fake::rng::DRIVER_NUM }, { fake::rng::ALLOW_BUFFER }>,
+                Subscribe<_, { fake::rng::DRIVER_NUM }, { fake::rng::SUBSCRIBE_DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            fake::Syscalls::allow_rw::<
+                DefaultConfig,
+                { fake::rng::DRIVER_NUM },
+                { fake::rng::ALLOW_BUFFER },
+            >(allow_rw, buffer)
+            .unwrap();
+            fake::Syscalls::subscribe::<
+                _,
+                _,
+                DefaultConfig,
+                { fake::rng::DRIVER_NUM },
+                { fake::rng::SUBSCRIBE_DONE },
+            >(subscribe, &done)
+            .unwrap();
+            assert!(fake::Syscalls::command(
+                fake::rng::DRIVER_NUM,
+                fake::rng::GET_BYTES,
+                requested,
+                0
+            )
+            .is_success());
+            fake::Syscalls::yield_wait();
+        });
+        done.get().unwrap().1
+    };
+
+    // The same seed produces the same bytes.
+    let mut first = [0; 8];
+    let mut second = [0; 8];
+    rng.set_seed(1);
+    assert_eq!(fill(&mut first, 8), 8);
+    rng.set_seed(1);
+    assert_eq!(fill(&mut second, 8), 8);
+    assert_eq!(first, second);
+    assert_ne!(first, [first[0]; 8]);
+
+    // Requests are limited by the buffer size and max_fill.
+    rng.set_max_fill(3);
+    let mut buffer = [0; 4];
+    assert_eq!(fill(&mut buffer, 8), 3);
+    assert_eq!(buffer[3], 0);
+    rng.set_max_fill(usize::MAX);
+    assert_eq!(fill(&mut buffer, 8), 4);
+}
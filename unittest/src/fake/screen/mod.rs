@@ -0,0 +1,269 @@
+//! Fake implementation of the screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90001_screen.md
+//!
+//! `Screen` draws into an in-memory framebuffer, which tests can inspect to
+//! check what a process drew. The framebuffer is laid out row by row in the
+//! screen's current orientation and pixel format (RGB565 or RGB888, with each
+//! pixel stored big-endian). Changing the rotation or pixel format clears it.
+//! Like real display controllers, writes that run past the end of the write
+//! frame wrap around to its start. Each operation completes immediately, and
+//! its upcall is delivered the next time the process yields.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::RoAllowBuffer;
+
+pub struct Screen {
+    width: u32,
+    height: u32,
+    rotation: Cell<u32>,
+    pixel_format: Cell<u32>,
+    write_frame: Cell<Frame>,
+    // Index of the next pixel to write, relative to the write frame.
+    write_position: Cell<usize>,
+    framebuffer: RefCell<Vec<u8>>,
+    buffer: Cell<RoAllowBuffer>,
+}
+
+impl Screen {
+    /// Creates a `width` by `height` RGB565 screen.
+    pub fn new(width: u32, height: u32) -> std::rc::Rc<Screen> {
+        let screen = Screen {
+            width,
+            height,
+            rotation: Cell::new(0),
+            pixel_format: Cell::new(RGB565),
+            write_frame: Cell::new(Frame::default()),
+            write_position: Cell::new(0),
+            framebuffer: Default::default(),
+            buffer: Default::default(),
+        };
+        screen.reset();
+        std::rc::Rc::new(screen)
+    }
+
+    /// Returns the screen's `(width, height)` in its current orientation.
+    pub fn resolution(&self) -> (u32, u32) {
+        match self.rotation.get() % 2 {
+            0 => (self.width, self.height),
+            _ => (self.height, self.width),
+        }
+    }
+
+    /// Returns the screen's rotation, as the number of quarter turns.
+    pub fn rotation(&self) -> u32 {
+        self.rotation.get()
+    }
+
+    /// Returns the screen's pixel format number.
+    pub fn pixel_format(&self) -> u32 {
+        self.pixel_format.get()
+    }
+
+    /// Returns a copy of the framebuffer.
+    pub fn framebuffer(&self) -> Vec<u8> {
+        self.framebuffer.borrow().clone()
+    }
+
+    /// Returns the value of the pixel at `(x, y)`, or `None` if the point is
+    /// off the screen.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<u32> {
+        let (width, height) = self.resolution();
+        if x >= width || y >= height {
+            return None;
+        }
+        let bytes = self.bytes_per_pixel();
+        let start = (y * width + x) as usize * bytes;
+        let framebuffer = self.framebuffer.borrow();
+        let value = framebuffer[start..start + bytes]
+            .iter()
+            .fold(0, |value, &byte| value << 8 | byte as u32);
+        Some(value)
+    }
+
+    /// Returns the current write frame as `(x, y, width, height)`.
+    pub fn write_frame(&self) -> (u32, u32, u32, u32) {
+        let frame = self.write_frame.get();
+        (frame.x, frame.y, frame.width, frame.height)
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        match self.pixel_format.get() {
+            RGB888 => 3,
+            _ => 2,
+        }
+    }
+
+    // Clears the framebuffer and makes the write frame cover the whole screen.
+    fn reset(&self) {
+        let (width, height) = self.resolution();
+        self.framebuffer
+            .replace(vec![0; (width * height) as usize * self.bytes_per_pixel()]);
+        self.write_frame.set(Frame {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+        self.write_position.set(0);
+    }
+
+    fn write(&self, len: usize) {
+        let frame = self.write_frame.get();
+        let frame_pixels = (frame.width * frame.height) as usize;
+        if frame_pixels == 0 {
+            return;
+        }
+        let bytes = self.bytes_per_pixel();
+        let (width, _) = self.resolution();
+        let data = self.buffer.take();
+        let len = len.min(data.len());
+        let mut framebuffer = self.framebuffer.borrow_mut();
+        for pixel in data[..len].chunks_exact(bytes) {
+            let position = self.write_position.get();
+            let x = frame.x + position as u32 % frame.width;
+            let y = frame.y + position as u32 / frame.width;
+            let start = (y * width + x) as usize * bytes;
+            framebuffer[start..start + bytes].copy_from_slice(pixel);
+            self.write_position.set((position + 1) % frame_pixels);
+        }
+        drop(framebuffer);
+        self.buffer.set(data);
+    }
+
+    fn complete(&self, value0: u32, value1: u32) -> CommandReturn {
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, value0, value1))
+            .expect("Unable to schedule upcall {}");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Screen {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            RESOLUTION_MODES_COUNT => crate::command_return::success_u32(1),
+            RESOLUTION_MODE => match argument0 {
+                0 => crate::command_return::success_2_u32(self.width, self.height),
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            PIXEL_FORMATS_COUNT => {
+                crate::command_return::success_u32(SUPPORTED_PIXEL_FORMATS.len() as u32)
+            }
+            SUPPORTED_PIXEL_FORMAT => match SUPPORTED_PIXEL_FORMATS.get(argument0 as usize) {
+                Some(&format) => crate::command_return::success_u32(format),
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            GET_ROTATION => self.complete(self.rotation.get(), 0),
+            SET_ROTATION => {
+                if argument0 > 3 {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.rotation.set(argument0);
+                self.reset();
+                self.complete(0, 0)
+            }
+            GET_RESOLUTION => {
+                let (width, height) = self.resolution();
+                self.complete(width, height)
+            }
+            SET_RESOLUTION => {
+                // The screen only supports its native resolution.
+                if (argument0, argument1) != self.resolution() {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.complete(0, 0)
+            }
+            GET_PIXEL_FORMAT => self.complete(self.pixel_format.get(), 0),
+            SET_PIXEL_FORMAT => {
+                if !SUPPORTED_PIXEL_FORMATS.contains(&argument0) {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.pixel_format.set(argument0);
+                self.reset();
+                self.complete(0, 0)
+            }
+            SET_WRITE_FRAME => {
+                let frame = Frame {
+                    x: argument0 >> 16,
+                    y: argument0 & 0xffff,
+                    width: argument1 >> 16,
+                    height: argument1 & 0xffff,
+                };
+                let (width, height) = self.resolution();
+                if frame.x + frame.width > width || frame.y + frame.height > height {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.write_frame.set(frame);
+                self.write_position.set(0);
+                self.complete(0, 0)
+            }
+            WRITE => {
+                self.write(argument0 as usize);
+                self.complete(0, 0)
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Frame {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const RESOLUTION_MODES_COUNT: u32 = 11;
+const RESOLUTION_MODE: u32 = 12;
+const PIXEL_FORMATS_COUNT: u32 = 13;
+const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+const GET_ROTATION: u32 = 21;
+const SET_ROTATION: u32 = 22;
+const GET_RESOLUTION: u32 = 23;
+const SET_RESOLUTION: u32 = 24;
+const GET_PIXEL_FORMAT: u32 = 25;
+const SET_PIXEL_FORMAT: u32 = 26;
+const SET_WRITE_FRAME: u32 = 100;
+const WRITE: u32 = 200;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+
+// Pixel format numbers
+const RGB565: u32 = 2;
+const RGB888: u32 = 3;
+const SUPPORTED_PIXEL_FORMATS: [u32; 2] = [RGB565, RGB888];
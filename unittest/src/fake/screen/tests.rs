@@ -0,0 +1,122 @@
+use crate::fake;
+use crate::RoAllowBuffer;
+use libtock_platform::share;
+use libtock_platform::{AllowRo, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let screen = fake::Screen::new(4, 2);
+    assert!(screen.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(screen.allow_readonly(1, RoAllowBuffer::default()).is_err());
+
+    assert!(screen
+        .command(fake::screen::DRIVER_CHECK, 0, 0)
+        .is_success());
+    assert_eq!(
+        screen
+            .command(fake::screen::RESOLUTION_MODE, 0, 0)
+            .get_success_2_u32(),
+        Some((4, 2))
+    );
+    assert_eq!(
+        screen
+            .command(fake::screen::SUPPORTED_PIXEL_FORMAT, 1, 0)
+            .get_success_u32(),
+        Some(fake::screen::RGB888)
+    );
+    assert_eq!(
+        screen
+            .command(fake::screen::SET_PIXEL_FORMAT, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    // The frame extends past the right edge of the screen.
+    assert_eq!(
+        screen
+            .command(fake::screen::SET_WRITE_FRAME, 2 << 16, 3 << 16 | 1)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(screen.write_frame(), (0, 0, 4, 2));
+    assert_eq!(screen.framebuffer(), [0; 16]);
+    assert_eq!(screen.pixel(4, 0), None);
+}
+
+// Integration test that verifies Screen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let screen = fake::Screen::new(4, 2);
+    kernel.add_driver(&screen);
+
+    let done = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::screen::DRIVER_NUM }, { fake::screen::ALLOW_WRITE }>,
+            Subscribe<_, { fake::screen::DRIVER_NUM }, { fake::screen::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::screen::DRIVER_NUM },
+            { fake::screen::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::screen::DRIVER_NUM,
+            fake::screen::SET_ROTATION,
+            1,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 0, 0)));
+        assert!(fake::Syscalls::command(
+            fake::screen::DRIVER_NUM,
+            fake::screen::GET_RESOLUTION,
+            0,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 2, 4)));
+
+        assert!(fake::Syscalls::command(
+            fake::screen::DRIVER_NUM,
+            fake::screen::SET_WRITE_FRAME,
+            1 << 16 | 1,
+            1 << 16 | 2
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 0, 0)));
+
+        // Three pixels; the last wraps around to the start of the frame.
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::screen::DRIVER_NUM },
+            { fake::screen::ALLOW_WRITE },
+        >(allow_ro, &[0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc])
+        .unwrap();
+        assert!(
+            fake::Syscalls::command(fake::screen::DRIVER_NUM, fake::screen::WRITE, 6, 0)
+                .is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 0, 0)));
+    });
+    assert_eq!(screen.resolution(), (2, 4));
+    assert_eq!(screen.pixel(1, 1), Some(0x9abc));
+    assert_eq!(screen.pixel(1, 2), Some(0x5678));
+    assert_eq!(screen.pixel(0, 1), Some(0));
+}
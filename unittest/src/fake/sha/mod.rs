@@ -0,0 +1,160 @@
+//! Fake implementation of the SHA API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40005_sha.md
+//!
+//! `Sha` computes digests with the `sha2` crate, so tests can check the results
+//! against known-answer vectors. Like the real driver, it computes SHA-256
+//! digests until the process selects another algorithm. Each operation
+//! completes immediately, and its upcall is delivered the next time the process
+//! yields.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Sha {
+    hasher: RefCell<Hasher>,
+    data: Cell<RoAllowBuffer>,
+    digest: Cell<RwAllowBuffer>,
+}
+
+impl Sha {
+    pub fn new() -> std::rc::Rc<Sha> {
+        std::rc::Rc::new(Sha {
+            hasher: RefCell::new(Hasher::Sha256(Sha256::new())),
+            data: Default::default(),
+            digest: Default::default(),
+        })
+    }
+}
+
+impl crate::fake::SyscallDriver for Sha {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DATA {
+            Ok(self.data.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DIGEST {
+            Ok(self.digest.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            SET_ALGORITHM => {
+                let hasher = match argument0 {
+                    SHA256 => Hasher::Sha256(Sha256::new()),
+                    SHA384 => Hasher::Sha384(Sha384::new()),
+                    SHA512 => Hasher::Sha512(Sha512::new()),
+                    _ => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                self.hasher.replace(hasher);
+                crate::command_return::success()
+            }
+            UPDATE => {
+                let data = self.data.take();
+                self.hasher.borrow_mut().update(&data);
+                self.data.set(data);
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+                crate::command_return::success()
+            }
+            FINISH => {
+                let mut digest = self.digest.take();
+                let result = self.hasher.borrow_mut().finish(&mut digest);
+                self.digest.set(digest);
+                if let Err(error) = result {
+                    return crate::command_return::failure(error);
+                }
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    // Writes the digest into the start of output and resets the hasher. Fails
+    // without resetting the hasher if output is too short.
+    fn finish(&mut self, output: &mut [u8]) -> Result<(), ErrorCode> {
+        let digest = match self {
+            Hasher::Sha256(hasher) => hasher.clone().finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.clone().finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.clone().finalize().to_vec(),
+        };
+        output
+            .get_mut(..digest.len())
+            .ok_or(ErrorCode::Size)?
+            .copy_from_slice(&digest);
+        match self {
+            Hasher::Sha256(hasher) => hasher.reset(),
+            Hasher::Sha384(hasher) => hasher.reset(),
+            Hasher::Sha512(hasher) => hasher.reset(),
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const UPDATE: u32 = 3;
+const FINISH: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_DATA: u32 = 1;
+const ALLOW_DIGEST: u32 = 0;
+
+// Algorithm numbers
+const SHA256: u32 = 0;
+const SHA384: u32 = 1;
+const SHA512: u32 = 2;
@@ -0,0 +1,91 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// SHA-256("abc"), from FIPS 180-2.
+const SHA256_ABC: [u8; 32] = [
+    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let sha = fake::Sha::new();
+    assert!(sha.allow_readonly(1, RoAllowBuffer::default()).is_ok());
+    assert!(sha.allow_readonly(0, RoAllowBuffer::default()).is_err());
+    assert!(sha.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(sha.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert!(sha.command(fake::sha::DRIVER_CHECK, 0, 0).is_success());
+    assert!(sha
+        .command(fake::sha::SET_ALGORITHM, fake::sha::SHA512, 0)
+        .is_success());
+    assert_eq!(
+        sha.command(fake::sha::SET_ALGORITHM, 3, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    // No digest buffer is shared.
+    assert_eq!(
+        sha.command(fake::sha::FINISH, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+}
+
+// Integration test that verifies Sha works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let sha = fake::Sha::new();
+    kernel.add_driver(&sha);
+
+    let mut digest = [0; 32];
+    let done = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::sha::DRIVER_NUM }, { fake::sha::ALLOW_DATA }>,
+            AllowRw<_, { fake::sha::DRIVER_NUM }, { fake::sha::ALLOW_DIGEST }>,
+            Subscribe<_, { fake::sha::DRIVER_NUM }, { fake::sha::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::sha::DRIVER_NUM },
+            { fake::sha::ALLOW_DATA },
+        >(allow_ro, b"abc")
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::sha::DRIVER_NUM },
+            { fake::sha::ALLOW_DIGEST },
+        >(allow_rw, &mut digest)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::sha::DRIVER_NUM },
+            { fake::sha::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(
+            fake::Syscalls::command(fake::sha::DRIVER_NUM, fake::sha::UPDATE, 0, 0).is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+        assert!(
+            fake::Syscalls::command(fake::sha::DRIVER_NUM, fake::sha::FINISH, 0, 0).is_success()
+        );
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0,)));
+    });
+    assert_eq!(digest, SHA256_ABC);
+}
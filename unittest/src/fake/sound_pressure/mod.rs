@@ -0,0 +1,114 @@
+//! Fake implementation of the sound pressure API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60006_sound_pressure.md
+//!
+//! Like the real API, `SoundPressure` takes a reading when the process requests
+//! one and reports it through an upcall. Tests provide readings either with
+//! `set_value` (which completes an outstanding read) or `set_value_sync` (which
+//! completes the next read as soon as it starts). `set_error_sync` makes the
+//! next read fail instead. Whether the process enabled the sensor is recorded,
+//! but does not affect reads.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+
+pub struct SoundPressure {
+    busy: Cell<bool>,
+    enabled: Cell<bool>,
+    upcall_on_command: Cell<Option<Result<u8, ErrorCode>>>,
+}
+
+impl SoundPressure {
+    pub fn new() -> std::rc::Rc<SoundPressure> {
+        std::rc::Rc::new(SoundPressure {
+            busy: Cell::new(false),
+            enabled: Cell::new(false),
+            upcall_on_command: Cell::new(None),
+        })
+    }
+
+    /// Returns true if a read is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.busy.get()
+    }
+
+    /// Returns true if the process enabled the sensor.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    /// Completes the outstanding read, if there is one, with the given sound
+    /// pressure (in dB).
+    pub fn set_value(&self, value: u8) {
+        if self.busy.replace(false) {
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_READING, (value.into(), 0, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+
+    /// Makes the next read complete immediately with the given sound pressure
+    /// (in dB).
+    pub fn set_value_sync(&self, value: u8) {
+        self.upcall_on_command.set(Some(Ok(value)));
+    }
+
+    /// Makes the next read fail with the given error.
+    pub fn set_error_sync(&self, error: ErrorCode) {
+        self.upcall_on_command.set(Some(Err(error)));
+    }
+}
+
+impl crate::fake::SyscallDriver for SoundPressure {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            READ => {
+                if self.busy.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                match self.upcall_on_command.take() {
+                    Some(Err(error)) => return crate::command_return::failure(error),
+                    Some(Ok(value)) => {
+                        self.busy.set(true);
+                        self.set_value(value);
+                    }
+                    None => self.busy.set(true),
+                }
+                crate::command_return::success()
+            }
+            ENABLE => {
+                self.enabled.set(true);
+                crate::command_return::success()
+            }
+            DISABLE => {
+                self.enabled.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x60006;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const READ: u32 = 1;
+const ENABLE: u32 = 2;
+const DISABLE: u32 = 3;
+const SUBSCRIBE_READING: u32 = 0;
@@ -0,0 +1,87 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let sound_pressure = fake::SoundPressure::new();
+    assert!(sound_pressure
+        .command(fake::sound_pressure::DRIVER_CHECK, 1, 2)
+        .is_success());
+
+    assert!(sound_pressure
+        .command(fake::sound_pressure::ENABLE, 0, 0)
+        .is_success());
+    assert!(sound_pressure.is_enabled());
+    assert!(sound_pressure
+        .command(fake::sound_pressure::DISABLE, 0, 0)
+        .is_success());
+    assert!(!sound_pressure.is_enabled());
+
+    assert!(sound_pressure
+        .command(fake::sound_pressure::READ, 0, 0)
+        .is_success());
+    assert!(sound_pressure.is_busy());
+    assert_eq!(
+        sound_pressure
+            .command(fake::sound_pressure::READ, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    let sound_pressure = fake::SoundPressure::new();
+    sound_pressure.set_error_sync(ErrorCode::Fail);
+    assert_eq!(
+        sound_pressure
+            .command(fake::sound_pressure::READ, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Fail)
+    );
+    assert!(!sound_pressure.is_busy());
+}
+
+// Integration test that verifies SoundPressure works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let sound_pressure = fake::SoundPressure::new();
+    kernel.add_driver(&sound_pressure);
+
+    let reading = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::sound_pressure::DRIVER_NUM },
+            { fake::sound_pressure::SUBSCRIBE_READING },
+        >(subscribe, &reading)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::sound_pressure::DRIVER_NUM,
+            fake::sound_pressure::READ,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        sound_pressure.set_value(58);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((58,)));
+
+        sound_pressure.set_value_sync(72);
+        assert!(fake::Syscalls::command(
+            fake::sound_pressure::DRIVER_NUM,
+            fake::sound_pressure::READ,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((72,)));
+    });
+}
@@ -0,0 +1,203 @@
+//! Fake implementation of the SPI controller API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/20001_spi_controller.md
+//!
+//! `SpiController` records each transfer as an `SpiFrame`, which tests can
+//! inspect with `take_frames`. The bytes the peripheral sends back (MISO) are
+//! taken from a script provided with `add_miso`; once the script runs out, the
+//! fake echoes the bytes written (MOSI). Transfers complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+/// A transfer performed by the fake SPI controller.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpiFrame {
+    /// The chip select line that was asserted during the transfer.
+    pub chip_select: u32,
+    /// The bytes written to the peripheral.
+    pub mosi: Vec<u8>,
+    /// The bytes the peripheral sent back.
+    pub miso: Vec<u8>,
+}
+
+pub struct SpiController {
+    chip_select: Cell<u32>,
+    rate: Cell<u32>,
+    phase: Cell<u32>,
+    polarity: Cell<u32>,
+    miso_script: RefCell<VecDeque<u8>>,
+    frames: RefCell<Vec<SpiFrame>>,
+    write_buffer: Cell<RoAllowBuffer>,
+    read_buffer: Cell<RwAllowBuffer>,
+}
+
+impl SpiController {
+    pub fn new() -> std::rc::Rc<SpiController> {
+        std::rc::Rc::new(SpiController {
+            chip_select: Cell::new(0),
+            rate: Cell::new(DEFAULT_RATE),
+            phase: Cell::new(0),
+            polarity: Cell::new(0),
+            miso_script: Default::default(),
+            frames: Default::default(),
+            write_buffer: Default::default(),
+            read_buffer: Default::default(),
+        })
+    }
+
+    pub fn chip_select(&self) -> u32 {
+        self.chip_select.get()
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate.get()
+    }
+
+    pub fn phase(&self) -> u32 {
+        self.phase.get()
+    }
+
+    pub fn polarity(&self) -> u32 {
+        self.polarity.get()
+    }
+
+    /// Appends bytes to the MISO script. Each byte transferred consumes one
+    /// byte of the script.
+    pub fn add_miso(&self, miso: &[u8]) {
+        self.miso_script.borrow_mut().extend(miso);
+    }
+
+    /// Returns the transfers performed since the last call to `take_frames`.
+    pub fn take_frames(&self) -> Vec<SpiFrame> {
+        self.frames.take()
+    }
+
+    fn read_write(&self, len: usize) -> Result<(), ErrorCode> {
+        let write_buffer = self.write_buffer.take();
+        let mut read_buffer = self.read_buffer.take();
+        let result =
+            if len > write_buffer.len() || (!read_buffer.is_empty() && len > read_buffer.len()) {
+                Err(ErrorCode::Size)
+            } else {
+                let mosi = write_buffer[..len].to_vec();
+                let mut script = self.miso_script.borrow_mut();
+                let miso: Vec<u8> = mosi
+                    .iter()
+                    .map(|&byte| script.pop_front().unwrap_or(byte))
+                    .collect();
+                if !read_buffer.is_empty() {
+                    read_buffer[..len].copy_from_slice(&miso);
+                }
+                self.frames.borrow_mut().push(SpiFrame {
+                    chip_select: self.chip_select.get(),
+                    mosi,
+                    miso,
+                });
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (len as u32, 0, 0))
+                    .expect("Unable to schedule upcall {}");
+                Ok(())
+            };
+        self.write_buffer.set(write_buffer);
+        self.read_buffer.set(read_buffer);
+        result
+    }
+}
+
+impl crate::fake::SyscallDriver for SpiController {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        use crate::command_return::{failure, success, success_u32};
+        match command_num {
+            DRIVER_CHECK => success(),
+            READ_WRITE => match self.read_write(argument0 as usize) {
+                Ok(()) => success(),
+                Err(error) => failure(error),
+            },
+            SET_CHIP_SELECT if argument0 < NUM_CHIP_SELECTS => {
+                self.chip_select.set(argument0);
+                success()
+            }
+            GET_CHIP_SELECT => success_u32(self.chip_select.get()),
+            SET_RATE if argument0 > 0 => {
+                self.rate.set(argument0);
+                success()
+            }
+            GET_RATE => success_u32(self.rate.get()),
+            SET_PHASE if argument0 <= 1 => {
+                self.phase.set(argument0);
+                success()
+            }
+            GET_PHASE => success_u32(self.phase.get()),
+            SET_POLARITY if argument0 <= 1 => {
+                self.polarity.set(argument0);
+                success()
+            }
+            GET_POLARITY => success_u32(self.polarity.get()),
+            SET_CHIP_SELECT | SET_RATE | SET_PHASE | SET_POLARITY => failure(ErrorCode::Invalid),
+            _ => failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x20001;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const READ_WRITE: u32 = 2;
+const SET_CHIP_SELECT: u32 = 3;
+const GET_CHIP_SELECT: u32 = 4;
+const SET_RATE: u32 = 5;
+const GET_RATE: u32 = 6;
+const SET_PHASE: u32 = 7;
+const GET_PHASE: u32 = 8;
+const SET_POLARITY: u32 = 9;
+const GET_POLARITY: u32 = 10;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
+
+// The number of chip select lines the fake controller has.
+const NUM_CHIP_SELECTS: u32 = 4;
+const DEFAULT_RATE: u32 = 1_000_000;
@@ -0,0 +1,122 @@
+use crate::fake::{self, SpiFrame};
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let spi = fake::SpiController::new();
+    assert!(spi
+        .command(fake::spi_controller::DRIVER_CHECK, 1, 2)
+        .is_success());
+    assert!(spi.allow_readonly(0, RoAllowBuffer::default()).is_ok());
+    assert!(spi.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(spi.allow_readwrite(0, RwAllowBuffer::default()).is_ok());
+    assert!(spi.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    assert!(spi
+        .command(fake::spi_controller::SET_CHIP_SELECT, 3, 0)
+        .is_success());
+    assert_eq!(
+        spi.command(fake::spi_controller::SET_CHIP_SELECT, 4, 0)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        spi.command(fake::spi_controller::GET_CHIP_SELECT, 0, 0)
+            .get_success_u32(),
+        Some(3)
+    );
+    assert!(spi
+        .command(fake::spi_controller::SET_RATE, 400_000, 0)
+        .is_success());
+    assert_eq!(spi.rate(), 400_000);
+    assert!(spi
+        .command(fake::spi_controller::SET_PHASE, 1, 0)
+        .is_success());
+    assert_eq!(
+        spi.command(fake::spi_controller::SET_POLARITY, 2, 0)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!((spi.phase(), spi.polarity()), (1, 0));
+
+    // Transfers may not be longer than the write buffer.
+    assert_eq!(
+        spi.command(fake::spi_controller::READ_WRITE, 1, 0)
+            .get_failure(),
+        Some(ErrorCode::Size)
+    );
+}
+
+// Integration test that verifies SpiController works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let spi = fake::SpiController::new();
+    kernel.add_driver(&spi);
+    spi.add_miso(&[0xa0, 0xa1]);
+
+    let mut buffer = [0; 3];
+    let done = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::spi_controller::DRIVER_NUM }, { fake::spi_controller::ALLOW_WRITE }>,
+            AllowRw<_, { fake::spi_controller::DRIVER_NUM }, { fake::spi_controller::ALLOW_READ }>,
+            Subscribe<
+                _,
+                { fake::spi_controller::DRIVER_NUM },
+                { fake::spi_controller::SUBSCRIBE_DONE },
+            >,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::spi_controller::DRIVER_NUM },
+            { fake::spi_controller::ALLOW_WRITE },
+        >(allow_ro, &[1, 2, 3])
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::spi_controller::DRIVER_NUM },
+            { fake::spi_controller::ALLOW_READ },
+        >(allow_rw, &mut buffer)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::spi_controller::DRIVER_NUM },
+            { fake::spi_controller::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::spi_controller::DRIVER_NUM,
+            fake::spi_controller::READ_WRITE,
+            3,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((3,)));
+    });
+    // The script supplies the first two bytes; the third is echoed.
+    assert_eq!(buffer, [0xa0, 0xa1, 3]);
+    assert_eq!(
+        spi.take_frames(),
+        [SpiFrame {
+            chip_select: 0,
+            mosi: vec![1, 2, 3],
+            miso: vec![0xa0, 0xa1, 3],
+        }]
+    );
+    assert_eq!(spi.take_frames(), []);
+}
@@ -54,4 +54,15 @@ pub trait SyscallDriver: 'static {
         let _ = buffer_num; // Silences the unused variable warning.
         Err((buffer, ErrorCode::NoSupport))
     }
+
+    // -------------------------------------------------------------------------
+    // Idle
+    // -------------------------------------------------------------------------
+
+    /// Called by `fake::Kernel` when the process calls Yield-Wait while no
+    /// upcall is queued, which is when a real process would sleep until an
+    /// interrupt occurs. Drivers that emulate the passage of time (such as
+    /// `fake::Alarm`) can use this to advance their clock and queue an upcall.
+    /// The default implementation does nothing.
+    fn idle(&self) {}
 }
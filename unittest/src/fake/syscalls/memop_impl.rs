@@ -0,0 +1,65 @@
+//! `fake::Kernel`'s implementation of the Memop system call.
+
+use crate::kernel_data::with_kernel_data;
+use crate::{ExpectedSyscall, SyscallLogEntry};
+use libtock_platform::{return_variant, Register};
+use std::convert::TryInto;
+
+pub(super) fn memop(memop_num: Register, argument0: Register) -> [Register; 2] {
+    let memop_num = memop_num.try_into().expect("Too large memop number");
+    let argument0: usize = argument0.into();
+
+    let result = with_kernel_data(|option_kernel_data| {
+        let kernel_data = option_kernel_data.expect("Memop called but no fake::Kernel exists");
+
+        kernel_data.syscall_log.push(SyscallLogEntry::Memop {
+            memop_num,
+            argument0,
+        });
+
+        // Check for an expected syscall entry. Returns an error from the lambda
+        // if this syscall was expected and return_error was specified. Panics
+        // if a different syscall was expected.
+        match kernel_data.expected_syscalls.pop_front() {
+            None => {}
+            Some(ExpectedSyscall::Memop {
+                memop_num: expected_memop_num,
+                argument0: expected_argument0,
+                return_error,
+            }) => {
+                assert_eq!(
+                    memop_num, expected_memop_num,
+                    "expected different memop_num"
+                );
+                assert_eq!(
+                    argument0, expected_argument0,
+                    "expected different argument0"
+                );
+                if let Some(error_code) = return_error {
+                    return Err(error_code);
+                }
+            }
+            Some(expected_syscall) => expected_syscall.panic_wrong_call("Memop"),
+        };
+
+        kernel_data.memory.memop(memop_num, argument0)
+    });
+
+    match result {
+        Ok(None) => {
+            let r0: u32 = return_variant::SUCCESS.into();
+            // The value of r1 isn't specified by TRD 104 for Success, so we
+            // zero it.
+            [r0.into(), 0u32.into()]
+        }
+        Ok(Some(value)) => {
+            let r0: u32 = return_variant::SUCCESS_U32.into();
+            [r0.into(), value]
+        }
+        Err(error_code) => {
+            let r0: u32 = return_variant::FAILURE.into();
+            let r1: u32 = error_code as u32;
+            [r0.into(), r1.into()]
+        }
+    }
+}
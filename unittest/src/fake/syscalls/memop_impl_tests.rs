@@ -0,0 +1,207 @@
+use super::memop_impl::*;
+use crate::{fake, ExpectedSyscall, SyscallLogEntry};
+use libtock_platform::{memop_id, return_variant, ErrorCode, Register};
+use std::convert::TryInto;
+use std::panic::catch_unwind;
+
+// Returns the return variant and r1 (as a usize) of a memop call.
+fn call(memop_num: u32, argument0: usize) -> (u32, usize) {
+    let [r0, r1] = memop(memop_num.into(), argument0.into());
+    (r0.try_into().expect("too large r0"), r1.into())
+}
+
+fn success_u32() -> u32 {
+    return_variant::SUCCESS_U32.into()
+}
+
+#[test]
+fn brk_and_sbrk() {
+    let kernel = fake::Kernel::new();
+    let (_, memory_start) = call(memop_id::MEMORY_START, 0);
+    let (_, grant_start) = call(memop_id::GRANT_START, 0);
+    assert_eq!(kernel.app_break() as usize, memory_start);
+
+    assert_eq!(
+        call(memop_id::BRK, memory_start + 16),
+        (return_variant::SUCCESS.into(), 0)
+    );
+    assert_eq!(kernel.app_break() as usize, memory_start + 16);
+
+    assert_eq!(call(memop_id::SBRK, 8), (success_u32(), memory_start + 16));
+    assert_eq!(
+        call(memop_id::SBRK, -4isize as usize),
+        (success_u32(), memory_start + 24)
+    );
+    assert_eq!(kernel.app_break() as usize, memory_start + 20);
+
+    // The break cannot be moved below the start of RAM or into the grant
+    // region.
+    let failure = return_variant::FAILURE.into();
+    assert_eq!(
+        call(memop_id::BRK, memory_start - 1),
+        (failure, ErrorCode::NoMem as usize)
+    );
+    assert_eq!(
+        call(memop_id::BRK, grant_start + 1),
+        (failure, ErrorCode::NoMem as usize)
+    );
+    assert_eq!(
+        call(memop_id::SBRK, -24isize as usize),
+        (failure, ErrorCode::NoMem as usize)
+    );
+    assert_eq!(kernel.app_break() as usize, memory_start + 20);
+
+    assert_eq!(
+        call(memop_id::BRK, grant_start),
+        (return_variant::SUCCESS.into(), 0)
+    );
+}
+
+#[test]
+fn memory_layout() {
+    let kernel = fake::Kernel::new();
+    let (_, memory_start) = call(memop_id::MEMORY_START, 0);
+    let (_, memory_end) = call(memop_id::MEMORY_END, 0);
+    let (_, grant_start) = call(memop_id::GRANT_START, 0);
+    let (_, flash_start) = call(memop_id::FLASH_START, 0);
+    let (_, flash_end) = call(memop_id::FLASH_END, 0);
+    assert!(memory_start < grant_start);
+    assert!(grant_start < memory_end);
+    assert!(flash_start < flash_end);
+
+    assert_eq!(call(memop_id::FLASH_REGION_COUNT, 0), (success_u32(), 0));
+    kernel.add_writeable_flash_region(0x100, 0x200);
+    assert_eq!(call(memop_id::FLASH_REGION_COUNT, 0), (success_u32(), 1));
+    assert_eq!(
+        call(memop_id::FLASH_REGION_START, 0),
+        (success_u32(), flash_start + 0x100)
+    );
+    assert_eq!(
+        call(memop_id::FLASH_REGION_END, 0),
+        (success_u32(), flash_start + 0x300)
+    );
+    assert_eq!(
+        call(memop_id::FLASH_REGION_START, 1),
+        (return_variant::FAILURE.into(), ErrorCode::Invalid as usize)
+    );
+}
+
+#[test]
+fn debug_hints() {
+    let kernel = fake::Kernel::new();
+    assert_eq!(kernel.debug_stack_start(), None);
+    assert_eq!(kernel.debug_heap_start(), None);
+    assert_eq!(
+        call(memop_id::DEBUG_STACK_START, 0x1234),
+        (return_variant::SUCCESS.into(), 0)
+    );
+    assert_eq!(
+        call(memop_id::DEBUG_HEAP_START, 0x5678),
+        (return_variant::SUCCESS.into(), 0)
+    );
+    assert_eq!(kernel.debug_stack_start(), Some(0x1234));
+    assert_eq!(kernel.debug_heap_start(), Some(0x5678));
+}
+
+#[test]
+fn expected_syscall() {
+    let kernel = fake::Kernel::new();
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::SBRK,
+        argument0: 16,
+        return_error: Some(ErrorCode::NoMem),
+    });
+    let app_break = kernel.app_break();
+    assert_eq!(
+        call(memop_id::SBRK, 16),
+        (return_variant::FAILURE.into(), ErrorCode::NoMem as usize)
+    );
+    assert_eq!(kernel.app_break(), app_break);
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::SBRK,
+        argument0: 16,
+        return_error: None,
+    });
+    assert_eq!(
+        call(memop_id::SBRK, 16),
+        (success_u32(), app_break as usize)
+    );
+
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: 16,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: 16,
+            },
+        ]
+    );
+}
+
+// Tests calls that do not match the expected system call.
+#[test]
+fn expected_wrong() {
+    let kernel = fake::Kernel::new();
+
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: 1,
+        command_id: 2,
+        argument0: 3,
+        argument1: 4,
+        override_return: None,
+    });
+    assert!(catch_unwind(|| memop(2u32.into(), 0u32.into()))
+        .expect_err("failed to catch wrong syscall class")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("but Memop was called instead"));
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: 2,
+        argument0: 0,
+        return_error: None,
+    });
+    assert!(catch_unwind(|| memop(3u32.into(), 0u32.into()))
+        .expect_err("failed to catch wrong memop number")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different memop_num"));
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: 10,
+        argument0: 0,
+        return_error: None,
+    });
+    assert!(catch_unwind(|| memop(10u32.into(), 1u32.into()))
+        .expect_err("failed to catch wrong argument0")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different argument0"));
+}
+
+#[test]
+fn no_kernel() {
+    let result = catch_unwind(|| memop(2u32.into(), 0u32.into()));
+    assert!(result
+        .expect_err("failed to catch missing kernel")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("no fake::Kernel exists"));
+}
+
+#[test]
+fn unknown_memop() {
+    let _kernel = fake::Kernel::new();
+    let [r0, r1]: [Register; 2] = memop(100u32.into(), 0u32.into());
+    assert_eq!(
+        r0.try_into(),
+        Ok(Into::<u32>::into(return_variant::FAILURE))
+    );
+    assert_eq!(r1.try_into(), Ok(ErrorCode::NoSupport as u32));
+}
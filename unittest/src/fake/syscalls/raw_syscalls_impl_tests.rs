@@ -4,7 +4,7 @@
 // tests.
 
 use crate::{fake, SyscallLogEntry};
-use libtock_platform::{syscall_class, RawSyscalls};
+use libtock_platform::{memop_id, syscall_class, RawSyscalls};
 
 #[test]
 fn allow_ro() {
@@ -52,7 +52,30 @@ fn allow_rw() {
 
 // TODO: Implement Exit.
 
-// TODO: Implement Memop.
+#[test]
+fn memop() {
+    let kernel = fake::Kernel::new();
+    unsafe {
+        fake::Syscalls::syscall1::<{ syscall_class::MEMOP }>([memop_id::MEMORY_START.into()]);
+        fake::Syscalls::syscall2::<{ syscall_class::MEMOP }>([
+            memop_id::DEBUG_STACK_START.into(),
+            1234u32.into(),
+        ]);
+    }
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::MEMORY_START,
+                argument0: 0,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 1234,
+            }
+        ]
+    );
+}
 
 // TODO: Implement Subscribe.
 
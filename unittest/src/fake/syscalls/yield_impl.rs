@@ -53,17 +53,41 @@ pub(super) fn yield_wait() {
         return;
     }
 
+    if invoke_next_upcall() {
+        return;
+    }
+
     // In a real Tock system, a process that calls yield-wait with no queued
     // upcalls would be put to sleep until an upcall was queued (e.g. by an
-    // interrupt). However, in this single-threaded test environment, there is
-    // no possibility a new upcall will be enqueued while we wait. Panicing is
-    // friendlier than hanging, so we panic if there's no upcall.
+    // interrupt). We emulate the passage of time by letting the drivers queue
+    // upcalls. However, in this single-threaded test environment, there is no
+    // other way a new upcall could be enqueued while we wait. Panicing is
+    // friendlier than hanging, so we panic if there's still no upcall.
+    idle_drivers();
     assert!(
         invoke_next_upcall(),
         "yield-wait called with no queued upcall"
     );
 }
 
+// Calls idle() on every driver, in order of driver number. The drivers are
+// collected before being called because idle() may queue upcalls, which
+// requires access to the kernel data.
+fn idle_drivers() {
+    let mut drivers: Vec<_> = with_kernel_data(|option_kernel_data| {
+        option_kernel_data
+            .unwrap()
+            .drivers
+            .iter()
+            .map(|(&driver_num, driver_data)| (driver_num, driver_data.driver.clone()))
+            .collect()
+    });
+    drivers.sort_by_key(|&(driver_num, _)| driver_num);
+    for (_, driver) in drivers {
+        driver.idle();
+    }
+}
+
 // Pops the next upcall off the kernel data's upcall queue and invokes it, or
 // does nothing if the upcall queue was entry. The return value indicates
 // whether an upcall was run. Panics if no kernel data is present.
@@ -0,0 +1,96 @@
+//! Fake implementation of the Temperature API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60000_ambient_temperature.md
+//!
+//! Like the real API, `Temperature` takes a reading when the process requests
+//! one and reports it through an upcall. Tests provide readings either with
+//! `set_value` (which completes an outstanding read) or `set_value_sync` (which
+//! completes the next read as soon as it starts). `set_error_sync` makes the
+//! next read fail instead.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+
+pub struct Temperature {
+    busy: Cell<bool>,
+    upcall_on_command: Cell<Option<Result<i32, ErrorCode>>>,
+}
+
+impl Temperature {
+    pub fn new() -> std::rc::Rc<Temperature> {
+        std::rc::Rc::new(Temperature {
+            busy: Cell::new(false),
+            upcall_on_command: Cell::new(None),
+        })
+    }
+
+    /// Returns true if a read is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.busy.get()
+    }
+
+    /// Completes the outstanding read, if there is one, with the given
+    /// temperature (in hundredths of a degree Celsius).
+    pub fn set_value(&self, value: i32) {
+        if self.busy.replace(false) {
+            upcall::schedule(DRIVER_NUM, SUBSCRIBE_READING, (value as u32, 0, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+
+    /// Makes the next read complete immediately with the given temperature (in
+    /// hundredths of a degree Celsius).
+    pub fn set_value_sync(&self, value: i32) {
+        self.upcall_on_command.set(Some(Ok(value)));
+    }
+
+    /// Makes the next read fail with the given error.
+    pub fn set_error_sync(&self, error: ErrorCode) {
+        self.upcall_on_command.set(Some(Err(error)));
+    }
+}
+
+impl crate::fake::SyscallDriver for Temperature {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            READ_TEMPERATURE => {
+                if self.busy.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                match self.upcall_on_command.take() {
+                    Some(Err(error)) => return crate::command_return::failure(error),
+                    Some(Ok(value)) => {
+                        self.busy.set(true);
+                        self.set_value(value);
+                    }
+                    None => self.busy.set(true),
+                }
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x60000;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const READ_TEMPERATURE: u32 = 1;
+const SUBSCRIBE_READING: u32 = 0;
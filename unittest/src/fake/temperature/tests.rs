@@ -0,0 +1,78 @@
+use crate::fake;
+use libtock_platform::{share, DefaultConfig, ErrorCode, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let temperature = fake::Temperature::new();
+    assert!(temperature
+        .command(fake::temperature::DRIVER_CHECK, 1, 2)
+        .is_success());
+
+    assert!(temperature
+        .command(fake::temperature::READ_TEMPERATURE, 0, 0)
+        .is_success());
+    assert!(temperature.is_busy());
+    assert_eq!(
+        temperature
+            .command(fake::temperature::READ_TEMPERATURE, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    let temperature = fake::Temperature::new();
+    temperature.set_error_sync(ErrorCode::Fail);
+    assert_eq!(
+        temperature
+            .command(fake::temperature::READ_TEMPERATURE, 0, 0)
+            .get_failure(),
+        Some(ErrorCode::Fail)
+    );
+    assert!(!temperature.is_busy());
+}
+
+// Integration test that verifies Temperature works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let temperature = fake::Temperature::new();
+    kernel.add_driver(&temperature);
+
+    let reading = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::temperature::DRIVER_NUM },
+            { fake::temperature::SUBSCRIBE_READING },
+        >(subscribe, &reading)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::temperature::DRIVER_NUM,
+            fake::temperature::READ_TEMPERATURE,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        temperature.set_value(-150);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((-150i32 as u32,)));
+
+        temperature.set_value_sync(2250);
+        assert!(fake::Syscalls::command(
+            fake::temperature::DRIVER_NUM,
+            fake::temperature::READ_TEMPERATURE,
+            0,
+            0
+        )
+        .is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.take(), Some((2250,)));
+    });
+}
@@ -0,0 +1,191 @@
+//! Fake implementation of the text screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90003_text_screen.md
+//!
+//! `TextScreen` keeps the screen's characters in memory, so tests can check
+//! what a process displayed. The cursor advances past each character written,
+//! wrapping to the start of the next row at the end of a row and back to the
+//! top left corner at the end of the screen. The display starts off, with the
+//! cursor hidden and not blinking. Each operation completes immediately, and
+//! its upcall is delivered the next time the process yields.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::upcall;
+use crate::RoAllowBuffer;
+
+pub struct TextScreen {
+    columns: u32,
+    rows: u32,
+    display_on: Cell<bool>,
+    blinking: Cell<bool>,
+    cursor_visible: Cell<bool>,
+    cursor: Cell<(u32, u32)>,
+    characters: RefCell<Vec<u8>>,
+    buffer: Cell<RoAllowBuffer>,
+}
+
+impl TextScreen {
+    /// Creates a screen that displays `rows` rows of `columns` characters.
+    pub fn new(columns: u32, rows: u32) -> std::rc::Rc<TextScreen> {
+        std::rc::Rc::new(TextScreen {
+            columns,
+            rows,
+            display_on: Cell::new(false),
+            blinking: Cell::new(false),
+            cursor_visible: Cell::new(false),
+            cursor: Cell::new((0, 0)),
+            characters: RefCell::new(vec![b' '; (columns * rows) as usize]),
+            buffer: Default::default(),
+        })
+    }
+
+    pub fn is_display_on(&self) -> bool {
+        self.display_on.get()
+    }
+
+    pub fn is_blinking(&self) -> bool {
+        self.blinking.get()
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible.get()
+    }
+
+    /// Returns the cursor's `(column, row)`.
+    pub fn cursor(&self) -> (u32, u32) {
+        self.cursor.get()
+    }
+
+    /// Returns the screen's contents, with a newline after each row.
+    pub fn text(&self) -> String {
+        self.characters
+            .borrow()
+            .chunks(self.columns as usize)
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect()
+    }
+
+    fn write(&self, len: usize) {
+        let data = self.buffer.take();
+        let len = len.min(data.len());
+        let mut characters = self.characters.borrow_mut();
+        for &character in &data[..len] {
+            let (column, row) = self.cursor.get();
+            characters[(row * self.columns + column) as usize] = character;
+            self.cursor
+                .set(match (column + 1 < self.columns, row + 1 < self.rows) {
+                    (true, _) => (column + 1, row),
+                    (false, true) => (0, row + 1),
+                    (false, false) => (0, 0),
+                });
+        }
+        drop(characters);
+        self.buffer.set(data);
+    }
+
+    fn complete(&self, value0: u32, value1: u32) -> CommandReturn {
+        upcall::schedule(DRIVER_NUM, SUBSCRIBE_DONE, (0, value0, value1))
+            .expect("Unable to schedule upcall {}");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for TextScreen {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            GET_SIZE => self.complete(self.columns, self.rows),
+            DISPLAY_ON => {
+                self.display_on.set(true);
+                self.complete(0, 0)
+            }
+            DISPLAY_OFF => {
+                self.display_on.set(false);
+                self.complete(0, 0)
+            }
+            BLINK_ON => {
+                self.blinking.set(true);
+                self.complete(0, 0)
+            }
+            BLINK_OFF => {
+                self.blinking.set(false);
+                self.complete(0, 0)
+            }
+            SHOW_CURSOR => {
+                self.cursor_visible.set(true);
+                self.complete(0, 0)
+            }
+            HIDE_CURSOR => {
+                self.cursor_visible.set(false);
+                self.complete(0, 0)
+            }
+            WRITE => {
+                self.write(argument0 as usize);
+                self.complete(0, 0)
+            }
+            CLEAR => {
+                self.characters.borrow_mut().fill(b' ');
+                self.cursor.set((0, 0));
+                self.complete(0, 0)
+            }
+            HOME => {
+                self.cursor.set((0, 0));
+                self.complete(0, 0)
+            }
+            SET_CURSOR => {
+                if argument0 >= self.columns || argument1 >= self.rows {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.cursor.set((argument0, argument1));
+                self.complete(0, 0)
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const GET_SIZE: u32 = 1;
+const DISPLAY_ON: u32 = 2;
+const DISPLAY_OFF: u32 = 3;
+const BLINK_ON: u32 = 4;
+const BLINK_OFF: u32 = 5;
+const SHOW_CURSOR: u32 = 6;
+const HIDE_CURSOR: u32 = 7;
+const WRITE: u32 = 8;
+const CLEAR: u32 = 9;
+const HOME: u32 = 10;
+const SET_CURSOR: u32 = 11;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
@@ -0,0 +1,91 @@
+use crate::fake;
+use crate::RoAllowBuffer;
+use libtock_platform::share;
+use libtock_platform::{AllowRo, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let text_screen = fake::TextScreen::new(4, 2);
+    assert!(text_screen
+        .allow_readonly(0, RoAllowBuffer::default())
+        .is_ok());
+    assert!(text_screen
+        .allow_readonly(1, RoAllowBuffer::default())
+        .is_err());
+
+    assert!(text_screen
+        .command(fake::text_screen::DRIVER_CHECK, 0, 0)
+        .is_success());
+    assert_eq!(
+        text_screen
+            .command(fake::text_screen::SET_CURSOR, 4, 0)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(!text_screen.is_display_on());
+    assert!(!text_screen.is_cursor_visible());
+    assert!(!text_screen.is_blinking());
+    assert_eq!(text_screen.cursor(), (0, 0));
+    assert_eq!(text_screen.text(), "    \n    \n");
+}
+
+// Integration test that verifies TextScreen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let text_screen = fake::TextScreen::new(4, 2);
+    kernel.add_driver(&text_screen);
+
+    let done = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::text_screen::DRIVER_NUM }, { fake::text_screen::ALLOW_WRITE }>,
+            Subscribe<_, { fake::text_screen::DRIVER_NUM }, { fake::text_screen::SUBSCRIBE_DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::text_screen::DRIVER_NUM },
+            { fake::text_screen::SUBSCRIBE_DONE },
+        >(subscribe, &done)
+        .unwrap();
+
+        assert!(fake::Syscalls::command(
+            fake::text_screen::DRIVER_NUM,
+            fake::text_screen::GET_SIZE,
+            0,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 4, 2)));
+
+        // The text wraps onto the second row, then back to the first.
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::text_screen::DRIVER_NUM },
+            { fake::text_screen::ALLOW_WRITE },
+        >(allow_ro, b"abcdefghij")
+        .unwrap();
+        assert!(fake::Syscalls::command(
+            fake::text_screen::DRIVER_NUM,
+            fake::text_screen::WRITE,
+            10,
+            0
+        )
+        .is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(done.take(), Some((0, 0, 0)));
+    });
+    assert_eq!(text_screen.text(), "ijcd\nefgh\n");
+    assert_eq!(text_screen.cursor(), (2, 0));
+}
@@ -0,0 +1,191 @@
+//! Fake implementation of the touch API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90002_touch.md
+//!
+//! `Touch` replays touch sequences recorded by the test: each time the process
+//! waits (with Yield-Wait), the next recorded event the process is listening
+//! for is delivered, and the events before it are dropped. Like the real
+//! driver, a multi-touch event is dropped (and counted as such) if the process
+//! has not acknowledged the previous one, and touches that do not fit in the
+//! process' buffer are lost.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::upcall;
+use crate::RwAllowBuffer;
+
+pub struct Touch {
+    number_of_touches: u32,
+    recording: RefCell<VecDeque<RecordedTouch>>,
+    buffer: Cell<RwAllowBuffer>,
+    acked: Cell<bool>,
+    dropped_events: Cell<u32>,
+}
+
+/// An event in a recorded touch sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordedTouch {
+    /// A single-touch event. The touch's `id` is ignored.
+    Touch(TouchPoint),
+    /// A multi-touch event, with one entry per finger on the panel.
+    MultiTouch(Vec<TouchPoint>),
+    /// A gesture, with the driver's gesture number (1 for a swipe up through 6
+    /// for a zoom out).
+    Gesture(u32),
+}
+
+/// A finger's position on the panel. `status` is 0 when the finger is released,
+/// 1 when it is pressed, and 2 when it moves.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TouchPoint {
+    pub id: u8,
+    pub status: u8,
+    pub x: u16,
+    pub y: u16,
+    pub size: u8,
+    pub pressure: u8,
+}
+
+impl Touch {
+    /// Creates a touch panel that tracks up to `number_of_touches` fingers.
+    pub fn new(number_of_touches: u32) -> std::rc::Rc<Touch> {
+        std::rc::Rc::new(Touch {
+            number_of_touches,
+            recording: Default::default(),
+            buffer: Default::default(),
+            acked: Cell::new(true),
+            dropped_events: Cell::new(0),
+        })
+    }
+
+    /// Appends `events` to the sequence being replayed.
+    pub fn replay<I: IntoIterator<Item = RecordedTouch>>(&self, events: I) {
+        self.recording.borrow_mut().extend(events);
+    }
+
+    /// Returns the number of recorded events that have not been delivered yet.
+    pub fn pending_events(&self) -> usize {
+        self.recording.borrow().len()
+    }
+
+    fn deliver(&self, event: RecordedTouch) {
+        match event {
+            RecordedTouch::Touch(point) => upcall::schedule(
+                DRIVER_NUM,
+                SUBSCRIBE_SINGLE_TOUCH,
+                (
+                    point.status.into(),
+                    u32::from(point.x) << 16 | u32::from(point.y),
+                    u32::from(point.pressure) << 16 | u32::from(point.size),
+                ),
+            ),
+            RecordedTouch::Gesture(gesture) => {
+                upcall::schedule(DRIVER_NUM, SUBSCRIBE_GESTURE, (gesture, 0, 0))
+            }
+            RecordedTouch::MultiTouch(points) => {
+                if !self.acked.get() {
+                    self.dropped_events.set(self.dropped_events.get() + 1);
+                    return;
+                }
+                let mut buffer = self.buffer.take();
+                let mut written = 0;
+                for (record, point) in buffer.chunks_exact_mut(RECORD_LEN).zip(&points) {
+                    record[0] = point.id;
+                    record[1] = point.status;
+                    record[2..4].copy_from_slice(&point.x.to_be_bytes());
+                    record[4..6].copy_from_slice(&point.y.to_be_bytes());
+                    record[6] = point.size;
+                    record[7] = point.pressure;
+                    written += 1;
+                }
+                self.buffer.set(buffer);
+                self.acked.set(false);
+                upcall::schedule(
+                    DRIVER_NUM,
+                    SUBSCRIBE_MULTI_TOUCH,
+                    (
+                        written as u32,
+                        self.dropped_events.replace(0),
+                        (points.len() - written) as u32,
+                    ),
+                )
+            }
+        }
+        .expect("Unable to schedule upcall {}");
+    }
+}
+
+impl crate::fake::SyscallDriver for Touch {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        3
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_MULTI_TOUCH {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            ACK_MULTI_TOUCH => {
+                self.acked.set(true);
+                crate::command_return::success()
+            }
+            NUMBER_OF_TOUCHES => crate::command_return::success_u32(self.number_of_touches),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        // Events the process is not listening for are dropped, so keep
+        // replaying until one is delivered.
+        while !upcall_queued() {
+            let event = self.recording.borrow_mut().pop_front();
+            match event {
+                Some(event) => self.deliver(event),
+                None => return,
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+// Returns true if an upcall is waiting to be invoked.
+fn upcall_queued() -> bool {
+    crate::kernel_data::with_kernel_data(|kernel_data| {
+        kernel_data.map_or(false, |kernel_data| !kernel_data.upcall_queue.is_empty())
+    })
+}
+
+const DRIVER_NUM: u32 = 0x90002;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const ACK_MULTI_TOUCH: u32 = 10;
+const NUMBER_OF_TOUCHES: u32 = 100;
+
+const SUBSCRIBE_SINGLE_TOUCH: u32 = 0;
+const SUBSCRIBE_GESTURE: u32 = 1;
+const SUBSCRIBE_MULTI_TOUCH: u32 = 2;
+const ALLOW_MULTI_TOUCH: u32 = 2;
+
+// The length of a touch's record in the multi-touch buffer.
+const RECORD_LEN: usize = 8;
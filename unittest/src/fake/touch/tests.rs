@@ -0,0 +1,107 @@
+use crate::fake;
+use crate::RwAllowBuffer;
+use fake::touch::*;
+use libtock_platform::share;
+use libtock_platform::{AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let touch = Touch::new(2);
+
+    assert!(touch.command(DRIVER_CHECK, 0, 0).is_success());
+    assert_eq!(
+        touch.command(NUMBER_OF_TOUCHES, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert!(touch.command(ACK_MULTI_TOUCH, 0, 0).is_success());
+    assert_eq!(
+        touch.command(1, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(touch
+        .allow_readwrite(ALLOW_MULTI_TOUCH, RwAllowBuffer::default())
+        .is_ok());
+    assert!(touch.allow_readwrite(0, RwAllowBuffer::default()).is_err());
+
+    touch.replay([RecordedTouch::Gesture(1), RecordedTouch::Gesture(2)]);
+    assert_eq!(touch.pending_events(), 2);
+}
+
+// Integration test that verifies Touch works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let touch = Touch::new(2);
+    kernel.add_driver(&touch);
+    let point = TouchPoint {
+        id: 1,
+        status: 1,
+        x: 0x0102,
+        y: 0x0304,
+        size: 5,
+        pressure: 6,
+    };
+    touch.replay([
+        RecordedTouch::Touch(point),
+        RecordedTouch::MultiTouch(vec![point; 3]),
+        RecordedTouch::MultiTouch(vec![point]),
+        RecordedTouch::Gesture(4),
+        RecordedTouch::MultiTouch(vec![point]),
+    ]);
+
+    let mut buffer = [0; 16];
+    let single = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    let gesture = core::cell::Cell::new(Option::<(u32,)>::None);
+    let multi = core::cell::Cell::new(Option::<(u32, u32, u32)>::None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_MULTI_TOUCH>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SINGLE_TOUCH>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_GESTURE>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_MULTI_TOUCH>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, sub_single, sub_gesture, sub_multi) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_MULTI_TOUCH>(
+            allow_rw,
+            &mut buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SINGLE_TOUCH>(
+            sub_single, &single,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_GESTURE>(
+            sub_gesture,
+            &gesture,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_MULTI_TOUCH>(
+            sub_multi, &multi,
+        )
+        .unwrap();
+
+        fake::Syscalls::yield_wait();
+        assert_eq!(single.take(), Some((1, 0x01020304, 0x00060005)));
+        // Only two of the three touches fit in the buffer.
+        fake::Syscalls::yield_wait();
+        assert_eq!(multi.take(), Some((2, 0, 1)));
+        // The next multi-touch event is dropped, as the previous event has not
+        // been acknowledged.
+        fake::Syscalls::yield_wait();
+        assert_eq!(multi.take(), None);
+        assert_eq!(gesture.take(), Some((4,)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, ACK_MULTI_TOUCH, 0, 0).is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(multi.take(), Some((1, 1, 0)));
+    });
+    assert_eq!(buffer[..8], [1, 1, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(buffer[8..], buffer[..8]);
+    assert_eq!(touch.pending_events(), 0);
+}
@@ -0,0 +1,310 @@
+//! Fake implementation of the UDP API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30002_udp.md
+//!
+//! `Udp` models a network containing the process, which has the address
+//! returned by `address`, and any number of `UdpSocket`s created by the test
+//! with `add_socket`. Packets sent by the process or by a socket are routed to
+//! the socket (or the process' bound port) with the destination address and
+//! port; packets with no matching destination are dropped. Packets are
+//! delivered to the process one at a time, each time the process waits for an
+//! upcall (i.e. each time the fake kernel is idle) while it has shared its
+//! receive buffers. Payloads longer than the receive buffer are truncated, but
+//! the receive upcall reports their full length.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+use crate::upcall;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+
+pub struct Udp {
+    network: Rc<Network>,
+    max_payload_len: Cell<usize>,
+    tx_payload: Cell<RoAllowBuffer>,
+    config: Cell<RoAllowBuffer>,
+    rx_payload: Cell<RwAllowBuffer>,
+    rx_addresses: Cell<RwAllowBuffer>,
+}
+
+/// A socket in the fake network, used by tests to exchange packets with the
+/// process.
+pub struct UdpSocket {
+    network: Weak<Network>,
+    address: [u8; 16],
+    port: u16,
+    inbox: RefCell<VecDeque<Packet>>,
+}
+
+// The state shared between Udp and its sockets.
+struct Network {
+    address: [u8; 16],
+    bound_port: Cell<Option<u16>>,
+    sockets: RefCell<Vec<Rc<UdpSocket>>>,
+    // Packets waiting to be delivered to the process.
+    received: RefCell<VecDeque<Packet>>,
+}
+
+struct Packet {
+    source: ([u8; 16], u16),
+    destination: ([u8; 16], u16),
+    payload: Vec<u8>,
+}
+
+impl Udp {
+    pub fn new() -> Rc<Udp> {
+        Rc::new(Udp {
+            network: Rc::new(Network {
+                address: PROCESS_ADDRESS,
+                bound_port: Cell::new(None),
+                sockets: Default::default(),
+                received: Default::default(),
+            }),
+            max_payload_len: Cell::new(DEFAULT_MAX_PAYLOAD_LEN),
+            tx_payload: Default::default(),
+            config: Default::default(),
+            rx_payload: Default::default(),
+            rx_addresses: Default::default(),
+        })
+    }
+
+    /// Returns the process' IPv6 address.
+    pub fn address(&self) -> [u8; 16] {
+        self.network.address
+    }
+
+    /// Returns the port the process has bound, if any.
+    pub fn bound_port(&self) -> Option<u16> {
+        self.network.bound_port.get()
+    }
+
+    /// Sets the size of the largest payload the process may send.
+    pub fn set_max_payload_len(&self, len: usize) {
+        self.max_payload_len.set(len);
+    }
+
+    /// Adds a socket with the given address and port to the network. A socket
+    /// with the process' address models another process on the same device;
+    /// the process cannot bind that socket's port.
+    pub fn add_socket(&self, address: [u8; 16], port: u16) -> Rc<UdpSocket> {
+        let socket = Rc::new(UdpSocket {
+            network: Rc::downgrade(&self.network),
+            address,
+            port,
+            inbox: Default::default(),
+        });
+        self.network.sockets.borrow_mut().push(socket.clone());
+        socket
+    }
+
+    /// Returns the number of packets routed to the process that have not been
+    /// delivered to it yet.
+    pub fn pending_packets(&self) -> usize {
+        self.network.received.borrow().len()
+    }
+
+    // Reads the socket address at the start of the config buffer.
+    fn config_address(&self) -> Option<([u8; 16], u16)> {
+        let config = self.config.take();
+        let address = (config.len() >= SOCKET_ADDR_LEN).then(|| {
+            let mut address = [0; 16];
+            address.copy_from_slice(&config[..16]);
+            (address, u16::from_be_bytes([config[16], config[17]]))
+        });
+        self.config.set(config);
+        address
+    }
+
+    fn send(&self) -> Result<(), ErrorCode> {
+        let source_port = self.network.bound_port.get().ok_or(ErrorCode::Invalid)?;
+        let destination = self.config_address().ok_or(ErrorCode::Invalid)?;
+        let payload = self.tx_payload.take();
+        let len = payload.len();
+        let packet = Packet {
+            source: (self.network.address, source_port),
+            destination,
+            payload: payload.to_vec(),
+        };
+        self.tx_payload.set(payload);
+        if len > self.max_payload_len.get() {
+            return Err(ErrorCode::Size);
+        }
+        self.network.route(packet);
+        Ok(())
+    }
+
+    fn bind(&self) -> Result<(), ErrorCode> {
+        let (_, port) = self.config_address().ok_or(ErrorCode::Invalid)?;
+        if port == 0 {
+            return Err(ErrorCode::Invalid);
+        }
+        let taken = self
+            .network
+            .sockets
+            .borrow()
+            .iter()
+            .any(|socket| socket.address == self.network.address && socket.port == port);
+        if taken {
+            return Err(ErrorCode::Busy);
+        }
+        self.network.bound_port.set(Some(port));
+        Ok(())
+    }
+}
+
+impl Network {
+    fn route(&self, packet: Packet) {
+        let (address, port) = packet.destination;
+        if address == self.address && Some(port) == self.bound_port.get() {
+            self.received.borrow_mut().push_back(packet);
+            return;
+        }
+        let sockets = self.sockets.borrow();
+        if let Some(socket) = sockets
+            .iter()
+            .find(|socket| socket.address == address && socket.port == port)
+        {
+            socket.inbox.borrow_mut().push_back(packet);
+        }
+    }
+}
+
+impl UdpSocket {
+    pub fn address(&self) -> [u8; 16] {
+        self.address
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Sends a packet from this socket to the given address and port.
+    pub fn send_to(&self, address: [u8; 16], port: u16, payload: &[u8]) {
+        let network = self
+            .network
+            .upgrade()
+            .expect("UdpSocket used after its fake::Udp was dropped");
+        network.route(Packet {
+            source: (self.address, self.port),
+            destination: (address, port),
+            payload: payload.to_vec(),
+        });
+    }
+
+    /// Returns the oldest packet received by this socket, as its source
+    /// address, source port, and payload.
+    pub fn recv_from(&self) -> Option<([u8; 16], u16, Vec<u8>)> {
+        let packet = self.inbox.borrow_mut().pop_front()?;
+        Some((packet.source.0, packet.source.1, packet.payload))
+    }
+}
+
+impl crate::fake::SyscallDriver for Udp {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        2
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_TX_PAYLOAD => Ok(self.tx_payload.replace(buffer)),
+            ALLOW_CONFIG => Ok(self.config.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_RX_PAYLOAD => Ok(self.rx_payload.replace(buffer)),
+            ALLOW_RX_ADDRESSES => Ok(self.rx_addresses.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            DRIVER_CHECK => crate::command_return::success(),
+            SEND => match self.send() {
+                Ok(()) => {
+                    upcall::schedule(DRIVER_NUM, SUBSCRIBE_SENT, (0, 0, 0))
+                        .expect("Unable to schedule upcall {}");
+                    crate::command_return::success()
+                }
+                Err(error) => crate::command_return::failure(error),
+            },
+            BIND => match self.bind() {
+                Ok(()) => crate::command_return::success(),
+                Err(error) => crate::command_return::failure(error),
+            },
+            MAX_PAYLOAD_LEN => {
+                crate::command_return::success_u32(self.max_payload_len.get() as u32)
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn idle(&self) {
+        let mut addresses = self.rx_addresses.take();
+        if addresses.len() >= 2 * SOCKET_ADDR_LEN {
+            if let Some(packet) = self.network.received.borrow_mut().pop_front() {
+                let mut payload = self.rx_payload.take();
+                let copied = packet.payload.len().min(payload.len());
+                payload[..copied].copy_from_slice(&packet.payload[..copied]);
+                self.rx_payload.set(payload);
+                for (i, (address, port)) in [packet.source, packet.destination].iter().enumerate() {
+                    let start = i * SOCKET_ADDR_LEN;
+                    addresses[start..start + 16].copy_from_slice(address);
+                    addresses[start + 16..start + 18].copy_from_slice(&port.to_be_bytes());
+                }
+                upcall::schedule(
+                    DRIVER_NUM,
+                    SUBSCRIBE_RECEIVED,
+                    (packet.payload.len() as u32, 0, 0),
+                )
+                .expect("Unable to schedule upcall {}");
+            }
+        }
+        self.rx_addresses.set(addresses);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 0x30002;
+
+// Command numbers
+const DRIVER_CHECK: u32 = 0;
+const SEND: u32 = 2;
+const BIND: u32 = 3;
+const MAX_PAYLOAD_LEN: u32 = 4;
+
+const SUBSCRIBE_RECEIVED: u32 = 0;
+const SUBSCRIBE_SENT: u32 = 1;
+const ALLOW_TX_PAYLOAD: u32 = 0;
+const ALLOW_CONFIG: u32 = 1;
+const ALLOW_RX_PAYLOAD: u32 = 0;
+const ALLOW_RX_ADDRESSES: u32 = 1;
+
+// An IPv6 address followed by a big-endian port.
+const SOCKET_ADDR_LEN: usize = 18;
+
+const DEFAULT_MAX_PAYLOAD_LEN: usize = 200;
+
+// fe80::1
+const PROCESS_ADDRESS: [u8; 16] = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
@@ -0,0 +1,145 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::share;
+use libtock_platform::{AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe};
+
+const REMOTE: [u8; 16] = [0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let udp = fake::Udp::new();
+    assert!(udp.allow_readonly(1, RoAllowBuffer::default()).is_ok());
+    assert!(udp.allow_readonly(2, RoAllowBuffer::default()).is_err());
+    assert!(udp.allow_readwrite(1, RwAllowBuffer::default()).is_ok());
+    assert!(udp.allow_readwrite(2, RwAllowBuffer::default()).is_err());
+
+    assert!(udp.command(fake::udp::DRIVER_CHECK, 0, 0).is_success());
+    udp.set_max_payload_len(100);
+    assert_eq!(
+        udp.command(fake::udp::MAX_PAYLOAD_LEN, 0, 0)
+            .get_success_u32(),
+        Some(100)
+    );
+    // Neither sending nor binding works without a config buffer.
+    assert_eq!(
+        udp.command(fake::udp::BIND, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        udp.command(fake::udp::SEND, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(udp.bound_port(), None);
+
+    // Sockets route packets between each other, and drop packets with no
+    // destination.
+    let a = udp.add_socket(REMOTE, 1);
+    let b = udp.add_socket(udp.address(), 2);
+    a.send_to(udp.address(), 2, b"hello");
+    a.send_to(udp.address(), 3, b"dropped");
+    assert_eq!(b.recv_from(), Some((REMOTE, 1, b"hello".to_vec())));
+    assert_eq!(b.recv_from(), None);
+    assert_eq!(udp.pending_packets(), 0);
+}
+
+// Integration test that verifies Udp works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let udp = fake::Udp::new();
+    kernel.add_driver(&udp);
+    let remote = udp.add_socket(REMOTE, 7);
+
+    let mut config = [0; 18];
+    config[..16].copy_from_slice(&REMOTE);
+    config[16..].copy_from_slice(&7u16.to_be_bytes());
+    let mut payload = [0; 4];
+    let mut addresses = [0; 36];
+    let sent = core::cell::Cell::new(Option::<(u32,)>::None);
+    let received = core::cell::Cell::new(Option::<(u32,)>::None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::udp::DRIVER_NUM }, { fake::udp::ALLOW_TX_PAYLOAD }>,
+            AllowRo<_, { fake::udp::DRIVER_NUM }, { fake::udp::ALLOW_CONFIG }>,
+            AllowRw<_, { fake::udp::DRIVER_NUM }, { fake::udp::ALLOW_RX_PAYLOAD }>,
+            AllowRw<_, { fake::udp::DRIVER_NUM }, { fake::udp::ALLOW_RX_ADDRESSES }>,
+            Subscribe<_, { fake::udp::DRIVER_NUM }, { fake::udp::SUBSCRIBE_SENT }>,
+            Subscribe<_, { fake::udp::DRIVER_NUM }, { fake::udp::SUBSCRIBE_RECEIVED }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_tx, allow_config, allow_rx, allow_addresses, subscribe_sent, subscribe_rx) =
+            handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::ALLOW_TX_PAYLOAD },
+        >(allow_tx, b"ping")
+        .unwrap();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::ALLOW_CONFIG },
+        >(allow_config, &config)
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::ALLOW_RX_PAYLOAD },
+        >(allow_rx, &mut payload)
+        .unwrap();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::ALLOW_RX_ADDRESSES },
+        >(allow_addresses, &mut addresses)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::SUBSCRIBE_SENT },
+        >(subscribe_sent, &sent)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::udp::DRIVER_NUM },
+            { fake::udp::SUBSCRIBE_RECEIVED },
+        >(subscribe_rx, &received)
+        .unwrap();
+
+        // The process cannot send before it has bound a port. The config
+        // buffer holds port 7, which is bound next.
+        assert_eq!(
+            fake::Syscalls::command(fake::udp::DRIVER_NUM, fake::udp::SEND, 0, 0).get_failure(),
+            Some(ErrorCode::Invalid)
+        );
+        assert!(fake::Syscalls::command(fake::udp::DRIVER_NUM, fake::udp::BIND, 0, 0).is_success());
+        assert_eq!(udp.bound_port(), Some(7));
+        assert!(fake::Syscalls::command(fake::udp::DRIVER_NUM, fake::udp::SEND, 0, 0).is_success());
+        fake::Syscalls::yield_wait();
+        assert_eq!(sent.take(), Some((0,)));
+        assert_eq!(
+            remote.recv_from(),
+            Some((udp.address(), 7, b"ping".to_vec()))
+        );
+
+        remote.send_to(udp.address(), 7, b"pong!");
+        fake::Syscalls::yield_wait();
+        // The payload is truncated, but its full length is reported.
+        assert_eq!(received.take(), Some((5,)));
+    });
+    assert_eq!(payload, *b"pong");
+    assert_eq!(addresses[..16], REMOTE);
+    assert_eq!(addresses[16..18], [0, 7]);
+    assert_eq!(addresses[18..34], udp.address());
+    assert_eq!(addresses[34..], [0, 7]);
+}
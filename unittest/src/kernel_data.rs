@@ -21,6 +21,7 @@ pub(crate) struct KernelData {
 
     pub drivers: std::collections::HashMap<u32, DriverData>,
     pub expected_syscalls: std::collections::VecDeque<crate::ExpectedSyscall>,
+    pub memory: crate::process_memory::ProcessMemory,
     pub syscall_log: Vec<crate::SyscallLogEntry>,
     pub upcall_queue: crate::upcall::UpcallQueue,
 }
@@ -10,6 +10,7 @@ mod exit_test;
 mod expected_syscall;
 pub mod fake;
 mod kernel_data;
+mod process_memory;
 mod syscall_log;
 pub mod upcall;
 
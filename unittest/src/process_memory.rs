@@ -0,0 +1,151 @@
+//! `ProcessMemory` emulates the memory layout that the Tock kernel gives a
+//! process, and implements the Memop operations that inspect and modify it.
+//!
+//! Unlike most of `fake::Kernel`'s state, the process memory is backed by real
+//! host allocations, so that code under test can store data below the process
+//! break (e.g. a heap allocator that grows the heap using `sbrk`).
+
+use libtock_platform::{memop_id, ErrorCode, Register};
+
+/// Size of the fake process' RAM region, including the grant region.
+pub(crate) const RAM_SIZE: usize = 0x4000;
+
+/// Size of the grant region, which is located at the end of RAM.
+pub(crate) const GRANT_SIZE: usize = 0x400;
+
+/// Size of the fake process' flash region.
+pub(crate) const FLASH_SIZE: usize = 0x1000;
+
+pub(crate) struct ProcessMemory {
+    // The RAM and flash regions. These are allocated in new() using
+    // Box::into_raw and deallocated in drop(). They are stored as raw pointers
+    // rather than Boxes because code under test holds pointers into these
+    // buffers, and accessing the buffers through a Box would invalidate those
+    // pointers under Stacked Borrows.
+    ram: *mut u8,
+    flash: *mut u8,
+
+    // The process break, as an offset into ram.
+    app_break: usize,
+
+    // The writeable flash regions, as (offset into flash, length) pairs.
+    writeable_flash_regions: Vec<(usize, usize)>,
+
+    // The addresses passed to the debug Memop operations, if they have been
+    // called.
+    debug_stack_start: Option<usize>,
+    debug_heap_start: Option<usize>,
+}
+
+impl ProcessMemory {
+    pub fn new() -> ProcessMemory {
+        ProcessMemory {
+            ram: Box::into_raw(vec![0u8; RAM_SIZE].into_boxed_slice()) as *mut u8,
+            flash: Box::into_raw(vec![0u8; FLASH_SIZE].into_boxed_slice()) as *mut u8,
+            app_break: 0,
+            writeable_flash_regions: Vec::new(),
+            debug_stack_start: None,
+            debug_heap_start: None,
+        }
+    }
+
+    /// Returns the current process break.
+    pub fn app_break(&self) -> *mut u8 {
+        self.ram.wrapping_add(self.app_break)
+    }
+
+    pub fn add_writeable_flash_region(&mut self, offset: usize, len: usize) {
+        assert!(
+            offset
+                .checked_add(len)
+                .map_or(false, |end| end <= FLASH_SIZE),
+            "Writeable flash region extends past the end of flash"
+        );
+        self.writeable_flash_regions.push((offset, len));
+    }
+
+    pub fn debug_stack_start(&self) -> Option<usize> {
+        self.debug_stack_start
+    }
+
+    pub fn debug_heap_start(&self) -> Option<usize> {
+        self.debug_heap_start
+    }
+
+    /// Performs a Memop operation. On success, returns the value the kernel
+    /// returns in r1, or `None` if the operation does not return a value.
+    pub fn memop(
+        &mut self,
+        memop_num: u32,
+        argument0: usize,
+    ) -> Result<Option<Register>, ErrorCode> {
+        match memop_num {
+            memop_id::BRK => {
+                self.app_break = self.break_offset(argument0)?;
+                Ok(None)
+            }
+            memop_id::SBRK => {
+                let previous_break = self.app_break();
+                // argument0 is a sign-extended i32, so a wrapping add
+                // correctly handles negative increments.
+                let new_break = (previous_break as usize).wrapping_add(argument0);
+                self.app_break = self.break_offset(new_break)?;
+                Ok(Some(previous_break.into()))
+            }
+            memop_id::MEMORY_START => Ok(Some(self.ram.into())),
+            memop_id::MEMORY_END => Ok(Some(self.ram.wrapping_add(RAM_SIZE).into())),
+            memop_id::FLASH_START => Ok(Some(self.flash.into())),
+            memop_id::FLASH_END => Ok(Some(self.flash.wrapping_add(FLASH_SIZE).into())),
+            memop_id::GRANT_START => Ok(Some(self.ram.wrapping_add(RAM_SIZE - GRANT_SIZE).into())),
+            memop_id::FLASH_REGION_COUNT => Ok(Some(self.writeable_flash_regions.len().into())),
+            memop_id::FLASH_REGION_START => self
+                .writeable_flash_region(argument0)
+                .map(|(offset, _)| Some(self.flash.wrapping_add(offset).into())),
+            memop_id::FLASH_REGION_END => self
+                .writeable_flash_region(argument0)
+                .map(|(offset, len)| Some(self.flash.wrapping_add(offset + len).into())),
+            memop_id::DEBUG_STACK_START => {
+                self.debug_stack_start = Some(argument0);
+                Ok(None)
+            }
+            memop_id::DEBUG_HEAP_START => {
+                self.debug_heap_start = Some(argument0);
+                Ok(None)
+            }
+            _ => Err(ErrorCode::NoSupport),
+        }
+    }
+
+    // Converts a requested break address into an offset into RAM. Like the
+    // real kernel, returns NoMem if the break would be outside the process'
+    // accessible memory (i.e. below the start of RAM or inside the grant
+    // region).
+    fn break_offset(&self, address: usize) -> Result<usize, ErrorCode> {
+        match address.checked_sub(self.ram as usize) {
+            Some(offset) if offset <= RAM_SIZE - GRANT_SIZE => Ok(offset),
+            _ => Err(ErrorCode::NoMem),
+        }
+    }
+
+    fn writeable_flash_region(&self, region: usize) -> Result<(usize, usize), ErrorCode> {
+        self.writeable_flash_regions
+            .get(region)
+            .copied()
+            .ok_or(ErrorCode::Invalid)
+    }
+}
+
+impl Drop for ProcessMemory {
+    fn drop(&mut self) {
+        // Safety: ram and flash were created by Box::into_raw in new() with
+        // lengths RAM_SIZE and FLASH_SIZE, and are only deallocated here.
+        unsafe {
+            drop(Box::from_raw(core::ptr::slice_from_raw_parts_mut(
+                self.ram, RAM_SIZE,
+            )));
+            drop(Box::from_raw(core::ptr::slice_from_raw_parts_mut(
+                self.flash, FLASH_SIZE,
+            )));
+        }
+    }
+}
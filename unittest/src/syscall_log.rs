@@ -43,6 +43,15 @@ pub enum SyscallLogEntry {
         buffer_num: u32,
         len: usize,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        memop_num: u32,
+
+        // 0 for memop operations that do not take an argument.
+        argument0: usize,
+    },
     // TODO: Add Exit.
 }